@@ -1,10 +1,12 @@
 use bls12_381::Scalar;
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
 use ff::Field;
 use rand_core::OsRng;
 use simple_logger::SimpleLogger;
 use std::time::Duration;
-use ziglet_okamoto::bls12_381_plain::{verify_signature, KeyPair, Signer, User};
+use ziglet_okamoto::bls12_381_plain::{
+    sign_in_process, verify_signature, verify_signature_prepared, KeyPair, Signer, User,
+};
 
 #[allow(non_snake_case)]
 fn bench(criterion: &mut Criterion) {
@@ -15,53 +17,239 @@ fn bench(criterion: &mut Criterion) {
     group.sample_size(1000);
 
     group.bench_function("generate", |b| {
-        let mut rng = OsRng;
+        let rng = OsRng;
         b.iter(|| {
-            let _ = KeyPair::generate(&mut rng);
+            let _ = KeyPair::generate(rng);
         });
     });
 
     group.bench_function("sign", |b| {
         let mut rng = OsRng;
-        let key_pair = KeyPair::generate(&mut rng);
+        let key_pair = KeyPair::generate(rng);
         let m0 = Scalar::random(&mut rng);
         let m1 = Scalar::random(&mut rng);
 
         b.iter(|| {
-            let mut user = User::new(&key_pair.public_key, rng.clone());
-            let mut signer = Signer::new(&key_pair, rng.clone());
-            user.set_message(m0, m1).unwrap();
-            signer.set_message(m0).unwrap();
-            let (W, X) = user.commit().unwrap();
-            let eta = signer.commit(W, X).unwrap();
-            let (b1, b2, b3) = user.compute_witness(&eta).unwrap();
-            signer.verify_witness(b1, b2, b3).unwrap();
-            let (Y, R, l) = signer.sign().unwrap();
-            user.sign(&Y, &R, &l).unwrap();
+            sign_in_process(&key_pair, m0, m1, rng, rng).unwrap();
         });
     });
 
     group.bench_function("verify", |b| {
         let mut rng = OsRng;
-        let key_pair = KeyPair::generate(&mut rng);
+        let key_pair = KeyPair::generate(rng);
+        let m0 = Scalar::random(&mut rng);
+        let m1 = Scalar::random(&mut rng);
+        let signature = sign_in_process(&key_pair, m0, m1, rng, rng).unwrap();
+
+        b.iter(|| {
+            verify_signature(
+                &key_pair.public_key,
+                &m0,
+                &m1,
+                &signature.sigma,
+                &signature.alpha,
+                &signature.beta,
+            )
+            .unwrap();
+        });
+    });
+
+    group.bench_function("verify_prepared", |b| {
+        let mut rng = OsRng;
+        let key_pair = KeyPair::generate(rng);
         let m0 = Scalar::random(&mut rng);
         let m1 = Scalar::random(&mut rng);
-        let mut user = User::new(&key_pair.public_key, rng.clone());
-        let mut signer = Signer::new(&key_pair, rng.clone());
-        user.set_message(m0, m1).unwrap();
-        signer.set_message(m0).unwrap();
-        let (W, X) = user.commit().unwrap();
-        let eta = signer.commit(W, X).unwrap();
-        let (b1, b2, b3) = user.compute_witness(&eta).unwrap();
-        signer.verify_witness(b1, b2, b3).unwrap();
-        let (Y, R, l) = signer.sign().unwrap();
-        let (sigma, alpha, beta) = user.sign(&Y, &R, &l).unwrap();
+        let signature = sign_in_process(&key_pair, m0, m1, rng, rng).unwrap();
+        let prepared = key_pair.public_key.prepare().unwrap();
+
+        b.iter(|| {
+            verify_signature_prepared(&prepared, &m0, &m1, &signature).unwrap();
+        });
+    });
+}
+
+/// Each interactive step on its own. The [User] and [Signer] are advanced to the state before the step once and
+/// cloned for every iteration, so only the step itself is measured.
+fn bench_steps(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("okamoto_steps");
+    group.measurement_time(Duration::from_secs(30));
+
+    let mut rng = OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let m0 = Scalar::random(&mut rng);
+    let m1 = Scalar::random(&mut rng);
+    let mut user = User::new(&key_pair.public_key, rng);
+    let mut signer = Signer::new(&key_pair, rng);
+    user.set_message(m0, m1).unwrap();
+    signer.set_message(m0).unwrap();
+
+    group.bench_function("user.commit", |b| {
+        b.iter_batched(
+            || user.clone(),
+            |mut user| user.commit().unwrap(),
+            BatchSize::SmallInput,
+        );
+    });
+    let commitment = user.commit().unwrap();
+
+    group.bench_function("signer.commit", |b| {
+        b.iter_batched(
+            || signer.clone(),
+            |mut signer| signer.commit(commitment).unwrap(),
+            BatchSize::SmallInput,
+        );
+    });
+    let eta = signer.commit(commitment).unwrap();
+
+    group.bench_function("user.compute_witness", |b| {
+        b.iter_batched(
+            || user.clone(),
+            |mut user| user.compute_witness(&eta).unwrap(),
+            BatchSize::SmallInput,
+        );
+    });
+    let witness = user.compute_witness(&eta).unwrap();
+
+    group.bench_function("signer.verify_witness", |b| {
+        b.iter_batched(
+            || signer.clone(),
+            |mut signer| signer.verify_witness(witness).unwrap(),
+            BatchSize::SmallInput,
+        );
+    });
+    signer.verify_witness(witness).unwrap();
+
+    group.bench_function("signer.sign", |b| {
+        b.iter_batched(
+            || signer.clone(),
+            |mut signer| signer.sign().unwrap(),
+            BatchSize::SmallInput,
+        );
+    });
+    let partial = signer.sign().unwrap();
+
+    group.bench_function("user.sign", |b| {
+        b.iter_batched(
+            || user.clone(),
+            |mut user| user.sign(&partial).unwrap(),
+            BatchSize::SmallInput,
+        );
+    });
+}
+
+/// [batch_verify] against [batch_verify_deterministic] on 1000 signatures
+fn bench_batch_verify(criterion: &mut Criterion) {
+    use ziglet_okamoto::bls12_381_plain::{batch_verify, batch_verify_deterministic};
+
+    let mut group = criterion.benchmark_group("okamoto_batch");
+    group.measurement_time(Duration::from_secs(60));
+    group.sample_size(10);
+
+    let mut rng = OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let items: Vec<_> = (0..1000)
+        .map(|_| {
+            let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+            (m0, m1, sign_in_process(&key_pair, m0, m1, rng, rng).unwrap())
+        })
+        .collect();
+
+    group.bench_function("batch_verify_1000", |b| {
+        b.iter(|| batch_verify(&key_pair.public_key, &items, rng).unwrap());
+    });
+
+    group.bench_function("batch_verify_deterministic_1000", |b| {
+        b.iter(|| batch_verify_deterministic(&key_pair.public_key, &items).unwrap());
+    });
+}
+
+/// [verify_common_info] against verifying 64 signatures on the same $m_0$ one after the other
+fn bench_verify_common_info(criterion: &mut Criterion) {
+    use ziglet_okamoto::bls12_381_plain::verify_common_info;
+
+    let mut group = criterion.benchmark_group("okamoto_common_info");
+    group.measurement_time(Duration::from_secs(30));
+
+    let mut rng = OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let m0 = Scalar::random(&mut rng);
+    let items: Vec<_> = (0..64)
+        .map(|_| {
+            let m1 = Scalar::random(&mut rng);
+            (m1, sign_in_process(&key_pair, m0, m1, rng, rng).unwrap())
+        })
+        .collect();
+
+    group.bench_function("verify_sequential_64", |b| {
+        b.iter(|| {
+            for (m1, signature) in &items {
+                verify_signature(
+                    &key_pair.public_key,
+                    &m0,
+                    m1,
+                    &signature.sigma,
+                    &signature.alpha,
+                    &signature.beta,
+                )
+                .unwrap();
+            }
+        });
+    });
+
+    group.bench_function("verify_common_info_64", |b| {
+        b.iter(|| verify_common_info(&key_pair.public_key, &m0, &items).unwrap());
+    });
+}
+
+/// [verify_many] against verifying the same signatures one after the other
+#[cfg(feature = "rayon")]
+fn bench_verify_many(criterion: &mut Criterion) {
+    use ziglet_okamoto::bls12_381_plain::verify_many;
+
+    let mut group = criterion.benchmark_group("okamoto_many");
+    group.measurement_time(Duration::from_secs(30));
+
+    let mut rng = OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let m0 = Scalar::random(&mut rng);
+    let items: Vec<_> = (0..64)
+        .map(|_| {
+            let m1 = Scalar::random(&mut rng);
+            (m0, m1, sign_in_process(&key_pair, m0, m1, rng, rng).unwrap())
+        })
+        .collect();
+    let prepared = key_pair.public_key.prepare().unwrap();
+
+    group.bench_function("verify_sequential_64", |b| {
+        b.iter(|| {
+            for (m0, m1, signature) in &items {
+                verify_signature_prepared(&prepared, m0, m1, signature).unwrap();
+            }
+        });
+    });
 
+    group.bench_function("verify_many_64", |b| {
         b.iter(|| {
-            verify_signature(&key_pair.public_key, &m0, &m1, &sigma, &alpha, &beta).unwrap();
+            assert!(verify_many(&key_pair.public_key, &items).iter().all(Result::is_ok));
         });
     });
 }
 
-criterion_group!(benches, bench);
+#[cfg(feature = "rayon")]
+criterion_group!(
+    benches,
+    bench,
+    bench_steps,
+    bench_batch_verify,
+    bench_verify_common_info,
+    bench_verify_many
+);
+#[cfg(not(feature = "rayon"))]
+criterion_group!(
+    benches,
+    bench,
+    bench_steps,
+    bench_batch_verify,
+    bench_verify_common_info
+);
 criterion_main!(benches);
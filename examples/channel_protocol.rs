@@ -0,0 +1,89 @@
+//! Run the [User] and the [Signer] on separate threads that only exchange byte encodings over channels
+//!
+//! Every message crosses the channel as the output of its `to_bytes` and is decoded with `from_bytes` on the other
+//! side, so the example doubles as a check that the wire format round-trips through a real transport. It exits with
+//! an error if any step fails.
+//!
+//! ```text
+//! cargo run --example channel_protocol
+//! ```
+
+use bls12_381::Scalar;
+use ff::Field;
+use rand_core::OsRng;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+use ziglet_okamoto::bls12_381_plain::{
+    hash_to_scalar, verify_signature, Challenge, Commitment, Error, KeyPair, PartialSignature, PublicKey, Signer, User,
+    Witness, INFO_DST,
+};
+
+/// The shared message $m_0$ both sides agree on out of band
+const INFO: &[u8] = b"denomination=100";
+
+/// `from_bytes` checks the length of each frame, so frames are passed on as received
+fn receive(rx: &Receiver<Vec<u8>>) -> Result<Vec<u8>, Error> {
+    rx.recv().map_err(|_| Error::TransportFailed)
+}
+
+fn send(tx: &Sender<Vec<u8>>, bytes: &[u8]) -> Result<(), Error> {
+    tx.send(bytes.to_vec()).map_err(|_| Error::TransportFailed)
+}
+
+fn signer(tx: Sender<Vec<u8>>, rx: Receiver<Vec<u8>>) -> Result<(), Error> {
+    let key_pair = KeyPair::generate(OsRng);
+    send(&tx, &key_pair.public_key.to_bytes())?;
+
+    let mut signer = Signer::new(&key_pair, OsRng);
+    signer.set_message_bytes(INFO)?;
+
+    let commitment = Commitment::from_bytes(&receive(&rx)?)?;
+    let eta = signer.commit(commitment)?;
+    send(&tx, &eta.to_bytes())?;
+
+    let witness = Witness::from_bytes(&receive(&rx)?)?;
+    signer.verify_witness(witness)?;
+
+    send(&tx, &signer.sign()?.to_bytes())
+}
+
+fn user(tx: Sender<Vec<u8>>, rx: Receiver<Vec<u8>>) -> Result<(), Error> {
+    let public_key = PublicKey::from_bytes(&receive(&rx)?)?;
+    let m1 = Scalar::random(OsRng);
+
+    let mut user = User::new(&public_key, OsRng);
+    user.set_message(hash_to_scalar(INFO_DST, INFO), m1)?;
+
+    send(&tx, &user.commit()?.to_bytes())?;
+
+    let eta = Challenge::from_bytes(&receive(&rx)?)?;
+    send(&tx, &user.compute_witness(&eta)?.to_bytes())?;
+
+    let partial = PartialSignature::from_bytes(&receive(&rx)?)?;
+    let signature = user.sign(&partial)?;
+
+    verify_signature(
+        &public_key,
+        &user.info()?,
+        &m1,
+        &signature.sigma,
+        &signature.alpha,
+        &signature.beta,
+    )?;
+    println!("signature: {}", signature.to_hex());
+
+    Ok(())
+}
+
+fn main() -> Result<(), Error> {
+    let (to_user, from_signer) = channel();
+    let (to_signer, from_user) = channel();
+
+    let signer = thread::spawn(move || signer(to_user, from_user));
+    let user = thread::spawn(move || user(to_signer, from_signer));
+
+    // A failure on one side drops its channels, which makes the other side fail as well
+    let signer = signer.join().expect("signer thread panicked");
+    let user = user.join().expect("user thread panicked");
+    signer.and(user)
+}
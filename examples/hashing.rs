@@ -0,0 +1,91 @@
+//! Derive $m_0$ and $m_1$ from application bytes with [hash_to_scalar], run the protocol and verify the signature
+//!
+//! $m_0$ (aka `info`) is a JSON policy document that both parties know, $m_1$ (aka `message`) the user id that only the
+//! user knows. Each is hashed under its own domain separation tag, [INFO_DST] and [MESSAGE_DST], so the same bytes
+//! never yield the same scalar for both slots.
+//!
+//! The signer and the user must hash identical bytes to agree on $m_0$. Two JSON texts of the same document can
+//! differ in key order and whitespace, so neither party hashes the text it received: each parses it into [Policy] and
+//! hashes the serialization of that struct, whose fields always come out in declaration order.
+//!
+//! ```text
+//! cargo run --example hashing
+//! ```
+
+use rand_core::OsRng;
+use serde::{Deserialize, Serialize};
+use ziglet_okamoto::bls12_381_plain::{
+    hash_to_scalar, verify_signature, Error, KeyPair, Signer, User, INFO_DST, MESSAGE_DST,
+};
+
+/// The policy both parties agree on out of band
+const SIGNER_POLICY: &str = r#"{"issuer":"example.org","denomination":100,"expires":"2026-12-31"}"#;
+
+/// The same policy as the user received it, with other key order and whitespace
+const USER_POLICY: &str = r#"{ "expires": "2026-12-31", "denomination": 100, "issuer": "example.org" }"#;
+
+/// The user id signed blindly as $m_1$
+const USER_ID: &[u8] = b"user-4242";
+
+#[derive(Serialize, Deserialize)]
+struct Policy {
+    issuer: String,
+    denomination: u64,
+    expires: String,
+}
+
+/// The canonical bytes of a policy: the text is parsed and serialized again
+fn canonical(policy: &str) -> Vec<u8> {
+    let policy: Policy = serde_json::from_str(policy).expect("valid policy");
+    serde_json::to_vec(&policy).expect("serializable policy")
+}
+
+fn main() -> Result<(), Error> {
+    // Hashing the received texts directly would make the parties disagree on $m_0$
+    assert_ne!(
+        hash_to_scalar(INFO_DST, SIGNER_POLICY.as_bytes()),
+        hash_to_scalar(INFO_DST, USER_POLICY.as_bytes())
+    );
+
+    let signer_m0 = hash_to_scalar(INFO_DST, &canonical(SIGNER_POLICY));
+    let user_m0 = hash_to_scalar(INFO_DST, &canonical(USER_POLICY));
+    assert_eq!(signer_m0, user_m0);
+
+    let m1 = hash_to_scalar(MESSAGE_DST, USER_ID);
+
+    let key_pair = KeyPair::generate(OsRng);
+    let mut signer = Signer::new(&key_pair, OsRng);
+    let mut user = User::new(&key_pair.public_key, OsRng);
+
+    signer.set_message(signer_m0)?;
+    user.set_message(user_m0, m1)?;
+
+    let challenge = signer.commit(user.commit()?)?;
+    signer.verify_witness(user.compute_witness(&challenge)?)?;
+    let signature = user.sign(&signer.sign()?)?;
+
+    // A verifier derives both scalars from the same bytes and tags
+    verify_signature(
+        &key_pair.public_key,
+        &hash_to_scalar(INFO_DST, &canonical(SIGNER_POLICY)),
+        &hash_to_scalar(MESSAGE_DST, USER_ID),
+        &signature.sigma,
+        &signature.alpha,
+        &signature.beta,
+    )?;
+
+    // Swapping the tags yields other scalars, so the signature does not verify
+    assert!(verify_signature(
+        &key_pair.public_key,
+        &hash_to_scalar(MESSAGE_DST, &canonical(SIGNER_POLICY)),
+        &hash_to_scalar(INFO_DST, USER_ID),
+        &signature.sigma,
+        &signature.alpha,
+        &signature.beta,
+    )
+    .is_err());
+
+    println!("signature: {}", signature.to_hex());
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+//! Produce a signature in Rust and verify it through the JavaScript bindings
+//!
+//! Prints the byte encodings as JavaScript `Uint8Array`s that can be passed to `verifySignature` in the browser,
+//! then checks them with the same binding natively.
+//!
+//! ```text
+//! cargo run --example wasm_verify --features wasm
+//! ```
+
+use bls12_381::Scalar;
+use ff::Field;
+use rand_core::OsRng;
+use ziglet_okamoto::bls12_381_plain::{sign_in_process, wasm, KeyPair};
+
+fn to_js(name: &str, bytes: &[u8]) -> String {
+    let values: Vec<String> = bytes.iter().map(|byte| byte.to_string()).collect();
+    format!("const {} = new Uint8Array([{}]);", name, values.join(", "))
+}
+
+fn main() {
+    let mut rng = OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let m0 = Scalar::random(&mut rng);
+    let m1 = Scalar::random(&mut rng);
+    let signature = sign_in_process(&key_pair, m0, m1, rng, rng).expect("protocol completes");
+
+    let public_key = key_pair.public_key.to_bytes();
+    let m0 = m0.to_bytes();
+    let m1 = m1.to_bytes();
+    let signature = signature.to_bytes();
+
+    println!("{}", to_js("publicKey", &public_key));
+    println!("{}", to_js("m0", &m0));
+    println!("{}", to_js("m1", &m1));
+    println!("{}", to_js("signature", &signature));
+    println!("console.assert(verifySignature(publicKey, m0, m1, signature));");
+
+    let valid = wasm::verify_signature(&public_key, &m0, &m1, &signature).unwrap_or(false);
+    assert!(valid);
+    let valid = wasm::verify_signature(&public_key, &m1, &m0, &signature).unwrap_or(true);
+    assert!(!valid);
+}
@@ -0,0 +1,40 @@
+//! Decoding arbitrary bytes must return an error rather than panic
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ziglet_okamoto::bls12_381_plain::{
+    Challenge, Commitment, PartialSignature, PublicKey, Signature, Witness, CHALLENGE_BYTES, COMMITMENT_BYTES,
+    PARTIAL_SIGNATURE_BYTES, PUBLIC_KEY_BYTES, SIGNATURE_BYTES, WITNESS_BYTES,
+};
+
+/// The whole input, which is rejected unless it happens to have the right length, and its prefix of exactly `len`
+/// bytes so the decoders themselves are reached
+fn inputs(data: &[u8], len: usize) -> impl Iterator<Item = &[u8]> {
+    core::iter::once(data).chain(data.get(..len))
+}
+
+fuzz_target!(|data: &[u8]| {
+    for bytes in inputs(data, PUBLIC_KEY_BYTES) {
+        let _ = PublicKey::from_bytes(bytes);
+    }
+    for bytes in inputs(data, SIGNATURE_BYTES) {
+        let _ = Signature::from_bytes(bytes);
+    }
+    for bytes in inputs(data, PARTIAL_SIGNATURE_BYTES) {
+        let _ = PartialSignature::from_bytes(bytes);
+    }
+    for bytes in inputs(data, COMMITMENT_BYTES) {
+        let _ = Commitment::from_bytes(bytes);
+    }
+    for bytes in inputs(data, CHALLENGE_BYTES) {
+        let _ = Challenge::from_bytes(bytes);
+    }
+    for bytes in inputs(data, WITNESS_BYTES) {
+        let _ = Witness::from_bytes(bytes);
+    }
+    if let Ok(text) = core::str::from_utf8(data) {
+        let _ = PublicKey::from_hex(text);
+        let _ = Signature::from_hex(text);
+    }
+});
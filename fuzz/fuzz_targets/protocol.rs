@@ -0,0 +1,43 @@
+//! Feeding structured, possibly malformed messages to each step of a flow must return an error rather than panic
+//!
+//! The messages come from the `arbitrary` implementations, so most points are valid and the steps get past their
+//! input checks. Each party is advanced to the step under test with honest messages from a fixed key before the
+//! fuzzed message is delivered.
+
+#![no_main]
+
+use bls12_381::Scalar;
+use libfuzzer_sys::fuzz_target;
+use std::sync::OnceLock;
+use ziglet_okamoto::bls12_381_plain::{
+    verify_signature, Challenge, Commitment, HmacDrbg, KeyPair, PartialSignature, Signature, Signer, User, Witness,
+};
+
+fn key_pair() -> &'static KeyPair {
+    static KEY_PAIR: OnceLock<KeyPair> = OnceLock::new();
+    KEY_PAIR.get_or_init(|| KeyPair::generate(HmacDrbg::new(&[b"fuzz"])))
+}
+
+fuzz_target!(|input: (Challenge, Commitment, Witness, PartialSignature, Signature)| {
+    let (challenge, commitment, witness, partial, signature) = input;
+    let key_pair = key_pair();
+    let pk = &key_pair.public_key;
+    let (m0, m1) = (Scalar::one(), Scalar::from(2));
+
+    let mut signer = Signer::new(key_pair, HmacDrbg::new(&[b"signer"]));
+    let mut user = User::new(pk, HmacDrbg::new(&[b"user"]));
+    signer.set_message(m0).unwrap();
+    user.set_message(m0, m1).unwrap();
+    let honest_commitment = user.commit().unwrap();
+
+    let _ = signer.clone().commit(commitment);
+    let eta = signer.commit(honest_commitment).unwrap();
+    let _ = user.clone().compute_witness(&challenge);
+    let _ = signer.clone().verify_witness(witness);
+
+    signer.verify_witness(user.compute_witness(&eta).unwrap()).unwrap();
+    let _ = user.clone().sign(&partial);
+    let _ = user.verify_partial(&partial);
+
+    let _ = verify_signature(pk, &m0, &m1, &signature.sigma, &signature.alpha, &signature.beta);
+});
@@ -0,0 +1,76 @@
+//! Verifying an arbitrary signature must return an error rather than panic
+//!
+//! Random bytes almost never decode to a valid public key, so signatures are checked against a fixed key derived
+//! from a constant seed. If the input is long enough a second, fuzzed key is decoded from its tail and used as well.
+//! The input starts with $m_0$, $m_1$ and a signature; inputs that do not decode are skipped since decoding is
+//! covered by the `from_bytes` target.
+
+#![no_main]
+
+use bls12_381::Scalar;
+use libfuzzer_sys::fuzz_target;
+use std::sync::OnceLock;
+use ziglet_okamoto::bls12_381_plain::{
+    sign_in_process, verify_signature, verify_signature_prepared, HmacDrbg, KeyPair, PreparedPublicKey, PublicKey,
+    Signature, PUBLIC_KEY_BYTES, SIGNATURE_BYTES,
+};
+
+fn fixed_key() -> &'static (PreparedPublicKey, Signature) {
+    static KEY: OnceLock<(PreparedPublicKey, Signature)> = OnceLock::new();
+    KEY.get_or_init(|| {
+        let key_pair = KeyPair::generate(HmacDrbg::new(&[b"fuzz"]));
+        let signature = sign_in_process(
+            &key_pair,
+            Scalar::from(1),
+            Scalar::from(2),
+            HmacDrbg::new(&[b"fuzz", b"user"]),
+            HmacDrbg::new(&[b"fuzz", b"signer"]),
+        )
+        .expect("signs");
+        (
+            key_pair.public_key.prepare().expect("generated keys are consistent"),
+            signature,
+        )
+    })
+}
+
+fn check(public_key: &PublicKey, m0: &Scalar, m1: &Scalar, signature: &Signature) {
+    let valid = verify_signature(public_key, m0, m1, &signature.sigma, &signature.alpha, &signature.beta).is_ok();
+    if let Ok(prepared) = public_key.prepare() {
+        assert_eq!(verify_signature_prepared(&prepared, m0, m1, signature).is_ok(), valid);
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 32 + 32 + SIGNATURE_BYTES {
+        return;
+    }
+    let (m0, rest) = data.split_at(32);
+    let (m1, rest) = rest.split_at(32);
+    let (signature, rest) = rest.split_at(SIGNATURE_BYTES);
+
+    let Some(m0) = Option::<Scalar>::from(Scalar::from_bytes(m0.try_into().unwrap())) else {
+        return;
+    };
+    let Some(m1) = Option::<Scalar>::from(Scalar::from_bytes(m1.try_into().unwrap())) else {
+        return;
+    };
+
+    let (prepared, valid_signature) = fixed_key();
+    // Mix the fuzzed bytes into a valid signature so that the pairing check is reached
+    let mut bytes = valid_signature.to_bytes();
+    for (byte, fuzzed) in bytes.iter_mut().zip(signature) {
+        *byte ^= fuzzed;
+    }
+    let Ok(signature) = Signature::from_bytes(&bytes) else {
+        return;
+    };
+
+    check(prepared.public_key(), &m0, &m1, &signature);
+
+    if let Some(public_key) = rest.get(..PUBLIC_KEY_BYTES) {
+        if let Ok(public_key) = PublicKey::from_bytes(public_key) {
+            check(&public_key, &m0, &m1, &signature);
+        }
+    }
+});
@@ -0,0 +1,222 @@
+//! Okamoto Partially Blind Signatures implemented over the BLS12-381 Elliptic Curve in the common reference
+//! string (CRS) model
+//!
+//! Unlike [crate::bls12_381_plain], where [KeyPair::generate] derives the generators $g, h, u, v$ from each signer's
+//! secret key, the generators are fixed once by a [Crs] shared among all signers and each signer only contributes
+//! ${w_2} = {g_2}^x$. Keys over the same [Crs] can be checked against it with [Crs::check] and combined with
+//! [PublicKey::combine].
+//!
+//! The protocol is otherwise that of [crate::bls12_381_plain], which this module re-exports: the same [Signer],
+//! [User], messages, encodings, [Error] and verification functions, so switching between the two modules only
+//! changes how the [KeyPair] is created. The [User] proves knowledge of $s, t$ against the random challenge $\eta$ of
+//! the [Signer], so no random oracle is involved.
+//!
+//! # Step 3 is still interactive
+//! The [Crs] only fixes who chose $g, h, u, v$; it is not a proving key and carries no trapdoor that would let the
+//! [Signer] check $X$ on its own. $X = ({h_1}^{m_0} {g_1}^{m_1} u_1 {v_1}^{s})^{t}$ hides $m_1$ behind the random
+//! $s,t$, so without Step 3 a [User] could send an $X$ whose opening it does not know, for example one derived from
+//! another [User]'s commitment, and obtain a signature on a message it never chose. The [Signer] must therefore still
+//! send $\eta$ from [Signer::commit] and check the [Witness] with [Signer::verify_witness] before [Signer::sign],
+//! exactly as in [crate::bls12_381_plain]. Only the `fiat_shamir` feature removes that round trip, by deriving $\eta$
+//! from a hash of the [Commitment], and it does so in both modules alike.
+//!
+//! # Example
+//! ```rust
+//! use bls12_381::Scalar;
+//! use ff::Field;
+//! use ziglet_okamoto::bls12_381_crs::{verify_signature, Crs, Error, KeyPair, Signer, User};
+//!
+//! fn happy_path() -> Result<(), Error> {
+//!     // Setup: the CRS is generated once and distributed to every party
+//!     let mut rng = rand_core::OsRng;
+//!     let crs = Crs::generate(rng);
+//!     let key_pair = KeyPair::generate_with_crs(rng, &crs);
+//!
+//!     // The user checks that the signer's key is over the agreed CRS
+//!     crs.check(&key_pair.public_key)?;
+//!     let mut user = User::new(&key_pair.public_key, rng);
+//!     let mut signer = Signer::new(&key_pair, rng);
+//!
+//!     // Step 0: out-of-band, the [User] and the [Signer] perform application logic necessary to agree on $m_0$
+//!     let m0 = Scalar::random(&mut rng);
+//!     let m1 = Scalar::random(&mut rng);
+//!
+//!     // Steps 1 to 4 as in `bls12_381_plain`
+//!     user.set_message(m0, m1)?;
+//!     signer.set_message(m0)?;
+//!     let challenge = signer.commit(user.commit()?)?;
+//!     signer.verify_witness(user.compute_witness(&challenge)?)?;
+//!     let signature = user.sign(&signer.sign()?)?;
+//!
+//!     verify_signature(&key_pair.public_key, &m0, &m1, &signature.sigma, &signature.alpha, &signature.beta)
+//! }
+//!
+//! happy_path().expect("successful completion");
+//! ```
+
+pub use crate::bls12_381_plain::*;
+
+use crate::bls12_381_plain::{decode_g1, decode_g2, decode_scalar};
+use bls12_381::{G1Affine, G2Affine};
+use ff::Field;
+use rand_core::{CryptoRng, RngCore};
+
+/// Length of [Crs::to_bytes]: four compressed points in $\mathbb{G_1}$ and four in $\mathbb{G_2}$
+pub const CRS_BYTES: usize = 4 * 48 + 4 * 96;
+
+/// The common reference string: generators in $\mathbb{G_1}$ and matching generators for the pairing operation
+/// in $\mathbb{G_2}$ shared by every [Signer] and [User].
+///
+/// The exponents of $h, u, v$ must remain unknown to users: with them a single [Signature] yields signatures on any
+/// other messages (see [PublicKeyBuilder]). The party generating the [Crs] learns them and must discard them.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Crs {
+    pub g1: G1Affine,
+    pub h1: G1Affine,
+    pub u1: G1Affine,
+    pub v1: G1Affine,
+    pub g2: G2Affine,
+    pub h2: G2Affine,
+    pub u2: G2Affine,
+    pub v2: G2Affine,
+}
+
+impl Crs {
+    /// Generate a fresh [Crs]
+    ///
+    /// The generators are derived as by [KeyPair::generate] from a throwaway secret key, which is dropped together
+    /// with their exponents before this returns.
+    ///
+    /// # Panics
+    /// As [KeyPair::generate]
+    pub fn generate(rng: impl RngCore + CryptoRng) -> Crs {
+        Crs::from(&KeyPair::generate(rng).public_key)
+    }
+
+    /// Check that `public_key` is over this [Crs], e.g. before a [User] signs with it
+    ///
+    /// # Errors
+    /// [Error::InvalidPublicKey] if any generator other than $w_2$ differs
+    pub fn check(&self, public_key: &PublicKey) -> Result<(), Error> {
+        if Crs::from(public_key) != *self {
+            return Err(Error::InvalidPublicKey);
+        }
+
+        Ok(())
+    }
+
+    /// Serialize as the compressed encodings of $g_1, h_1, u_1, v_1, g_2, h_2, u_2, v_2$ in that order, i.e.
+    /// [PublicKey::to_bytes] without $w_2$
+    pub fn to_bytes(&self) -> [u8; CRS_BYTES] {
+        let mut bytes = [0u8; CRS_BYTES];
+        for (i, point) in [&self.g1, &self.h1, &self.u1, &self.v1].into_iter().enumerate() {
+            bytes[i * 48..(i + 1) * 48].copy_from_slice(&point.to_compressed());
+        }
+        for (i, point) in [&self.g2, &self.h2, &self.u2, &self.v2].into_iter().enumerate() {
+            bytes[192 + i * 96..192 + (i + 1) * 96].copy_from_slice(&point.to_compressed());
+        }
+        bytes
+    }
+
+    /// Deserialize from the encoding produced by [Crs::to_bytes]
+    ///
+    /// Fails with [Error::DecodingFailed] unless `bytes` is [CRS_BYTES] long, with [Error::PointNotOnCurve] or
+    /// [Error::NotInSubgroup] if any point is not a valid element of its group and with [Error::InvalidPublicKey] if
+    /// the generators fail the checks of [PublicKey::validate]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != CRS_BYTES {
+            return Err(Error::DecodingFailed);
+        }
+
+        let mut g1 = [G1Affine::identity(); 4];
+        let mut g2 = [G2Affine::identity(); 4];
+
+        for (i, point) in g1.iter_mut().enumerate() {
+            *point = decode_g1(&bytes[i * 48..(i + 1) * 48])?;
+        }
+        for (i, point) in g2.iter_mut().enumerate() {
+            *point = decode_g2(&bytes[192 + i * 96..192 + (i + 1) * 96])?;
+        }
+
+        let [g1, h1, u1, v1] = g1;
+        let [g2, h2, u2, v2] = g2;
+        let crs = Crs {
+            g1,
+            h1,
+            u1,
+            v1,
+            g2,
+            h2,
+            u2,
+            v2,
+        };
+
+        // $g_2$ stands in for the $w_2$ of a signer, which the CRS does not contain
+        crs.public_key(g2).validate()?;
+
+        Ok(crs)
+    }
+
+    /// The [PublicKey] over these generators with the signer's `w2`
+    fn public_key(&self, w2: G2Affine) -> PublicKey {
+        PublicKey {
+            g1: self.g1,
+            h1: self.h1,
+            u1: self.u1,
+            v1: self.v1,
+            g2: self.g2,
+            h2: self.h2,
+            u2: self.u2,
+            v2: self.v2,
+            w2,
+        }
+    }
+}
+
+/// The generators of a [PublicKey], dropping $w_2$
+impl From<&PublicKey> for Crs {
+    fn from(public_key: &PublicKey) -> Self {
+        Crs {
+            g1: public_key.g1,
+            h1: public_key.h1,
+            u1: public_key.u1,
+            v1: public_key.v1,
+            g2: public_key.g2,
+            h2: public_key.h2,
+            u2: public_key.u2,
+            v2: public_key.v2,
+        }
+    }
+}
+
+impl KeyPair {
+    /// Generate a fresh [KeyPair] over the generators of `crs`
+    ///
+    /// The secret key is drawn from `rng` as in [KeyPair::generate], redrawing a zero, and only $w_2$ is derived from
+    /// it. Restore the key with [KeyPair::from_secret_bytes_with_crs] and the same [Crs].
+    ///
+    /// # Panics
+    /// As [KeyPair::generate]
+    pub fn generate_with_crs(rng: impl RngCore + CryptoRng, crs: &Crs) -> KeyPair {
+        let secret_key = KeyPair::draw_secret_key(rng).expect("the RNG yielded only zero secret keys");
+        Self::from_parts(secret_key, crs.public_key(G2Affine::from(crs.g2 * secret_key)))
+    }
+
+    /// Restore a [KeyPair] created by [KeyPair::generate_with_crs] from [KeyPair::export_secret] and its [Crs]
+    ///
+    /// # Errors
+    /// As [KeyPair::from_secret_bytes]
+    pub fn from_secret_bytes_with_crs(bytes: &[u8; 32], crs: &Crs) -> Result<KeyPair, Error> {
+        let secret_key = decode_scalar(bytes)?;
+        if secret_key.is_zero().into() {
+            return Err(Error::ScalarIsZero);
+        }
+        Ok(Self::from_parts(
+            secret_key,
+            crs.public_key(G2Affine::from(crs.g2 * secret_key)),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,208 @@
+use crate::bls12_381_crs::{
+    verify_signature, Crs, Error, KeyPair, PublicKey, Signature, Signer, SignerState, StateKind, User, Witness,
+    CRS_BYTES,
+};
+use bls12_381::{G2Affine, Scalar};
+use ff::Field;
+use rand_core::{CryptoRng, OsRng, RngCore};
+
+/// Run the protocol between fresh parties over `key_pair`
+fn sign(key_pair: &KeyPair, m0: Scalar, m1: Scalar) -> Result<Signature, Error> {
+    let mut user = User::new(&key_pair.public_key, OsRng);
+    let mut signer = Signer::new(key_pair, OsRng);
+
+    user.set_message(m0, m1)?;
+    signer.set_message(m0)?;
+    let challenge = signer.commit(user.commit()?)?;
+    signer.verify_witness(user.compute_witness(&challenge)?)?;
+    user.sign(&signer.sign()?)
+}
+
+fn verify(public_key: &PublicKey, m0: &Scalar, m1: &Scalar, signature: &Signature) -> Result<(), Error> {
+    verify_signature(public_key, m0, m1, &signature.sigma, &signature.alpha, &signature.beta)
+}
+
+#[test]
+fn okamoto_crs_happy_path() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let crs = Crs::generate(rng);
+    let key_pair = KeyPair::generate_with_crs(rng, &crs);
+    crs.check(&key_pair.public_key)?;
+    key_pair.verify_consistency()?;
+
+    let m0 = Scalar::random(&mut rng);
+    let m1 = Scalar::random(&mut rng);
+    let signature = sign(&key_pair, m0, m1)?;
+
+    verify(&key_pair.public_key, &m0, &m1, &signature)?;
+    assert_eq!(
+        verify(&key_pair.public_key, &m1, &m0, &signature),
+        Err(Error::InvalidSignature)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn okamoto_crs_rejects_tampered_witness() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let crs = Crs::generate(rng);
+    let key_pair = KeyPair::generate_with_crs(rng, &crs);
+    let mut user = User::new(&key_pair.public_key, rng);
+    let mut signer = Signer::new(&key_pair, rng);
+
+    let m0 = Scalar::random(&mut rng);
+    let m1 = Scalar::random(&mut rng);
+
+    user.set_message(m0, m1)?;
+    signer.set_message(m0)?;
+    let challenge = signer.commit(user.commit()?)?;
+    let witness = user.compute_witness(&challenge)?;
+    let witness = Witness {
+        b1: witness.b1 + Scalar::one(),
+        ..witness
+    };
+
+    assert!(matches!(
+        signer.verify_witness(witness),
+        Err(Error::WitnessMismatch { .. })
+    ));
+    assert!(matches!(signer.get_state(), SignerState::Aborted(_)));
+    assert_eq!(
+        signer.sign(),
+        Err(Error::WrongState {
+            expected: StateKind::ReadyToSign,
+            actual: StateKind::Aborted
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn signers_over_one_crs_share_generators_but_not_signatures() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let crs = Crs::generate(rng);
+    let alice = KeyPair::generate_with_crs(rng, &crs);
+    let bob = KeyPair::generate_with_crs(rng, &crs);
+    assert_eq!(Crs::from(&alice.public_key), Crs::from(&bob.public_key));
+    assert_ne!(alice.public_key.w2, bob.public_key.w2);
+
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+    let signature = sign(&alice, m0, m1)?;
+    verify(&alice.public_key, &m0, &m1, &signature)?;
+    assert_eq!(
+        verify(&bob.public_key, &m0, &m1, &signature),
+        Err(Error::InvalidSignature)
+    );
+
+    // Keys over the same CRS combine; a key with its own generators is over another CRS
+    crs.check(&alice.public_key.combine(&bob.public_key)?)?;
+    let other = KeyPair::generate(rng);
+    assert_eq!(crs.check(&other.public_key), Err(Error::InvalidPublicKey));
+    assert_eq!(
+        alice.public_key.combine(&other.public_key),
+        Err(Error::InvalidPublicKey)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn crs_bytes_round_trip_and_are_validated() -> Result<(), Error> {
+    let crs = Crs::generate(OsRng);
+
+    let bytes = crs.to_bytes();
+    assert_eq!(bytes.len(), CRS_BYTES);
+    assert_eq!(Crs::from_bytes(&bytes)?, crs);
+    assert_eq!(Crs::from_bytes(&bytes[1..]), Err(Error::DecodingFailed));
+
+    // h2 in place of u2 breaks the pairing relation between u1 and u2
+    let mut swapped = bytes;
+    swapped.copy_within(288..384, 384);
+    assert_eq!(Crs::from_bytes(&swapped), Err(Error::InvalidPublicKey));
+
+    let mut identity = bytes;
+    identity[480..576].copy_from_slice(&G2Affine::identity().to_compressed());
+    assert_eq!(Crs::from_bytes(&identity), Err(Error::InvalidPublicKey));
+
+    Ok(())
+}
+
+#[test]
+fn crs_secret_key_export_round_trips() -> Result<(), Error> {
+    let crs = Crs::generate(OsRng);
+    let key_pair = KeyPair::generate_with_crs(OsRng, &crs);
+
+    let restored = KeyPair::from_secret_bytes_with_crs(key_pair.export_secret().as_bytes(), &crs)?;
+    assert_eq!(restored.public_key, key_pair.public_key);
+    assert_eq!(
+        KeyPair::from_secret_bytes_with_crs(&[0; 32], &crs).map(|_| ()),
+        Err(Error::ScalarIsZero)
+    );
+
+    Ok(())
+}
+
+/// Yields `zeros` all-zero draws before deferring to [OsRng]
+struct ZeroThenOsRng {
+    zeros: usize,
+}
+
+impl RngCore for ZeroThenOsRng {
+    fn next_u32(&mut self) -> u32 {
+        OsRng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        OsRng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if self.zeros > 0 {
+            self.zeros -= 1;
+            dest.fill(0);
+        } else {
+            OsRng.fill_bytes(dest);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for ZeroThenOsRng {}
+
+#[test]
+fn crs_key_generation_resamples_a_zero_secret_key() -> Result<(), Error> {
+    let crs = Crs::generate(OsRng);
+    let key_pair = KeyPair::generate_with_crs(ZeroThenOsRng { zeros: 1 }, &crs);
+
+    assert!(!bool::from(key_pair.public_key.w2.is_identity()));
+    key_pair.verify_consistency()?;
+
+    Ok(())
+}
+
+#[test]
+fn crs_signatures_with_a_degenerate_alpha_are_rejected() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+    let crs = Crs::generate(rng);
+    let key_pair = KeyPair::generate_with_crs(rng, &crs);
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+    let signature = sign(&key_pair, m0, m1)?;
+
+    for alpha in [G2Affine::identity(), -key_pair.public_key.w2] {
+        assert_eq!(
+            verify(&key_pair.public_key, &m0, &m1, &Signature { alpha, ..signature }),
+            Err(Error::InvalidSignature)
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+//! Aggregate verification of many signatures sharing the same $m_0$
+
+use super::{hash_to_scalar, well_formed, Error, PublicKey, Signature, AGGREGATE_DST};
+use alloc::vec::Vec;
+use bls12_381::{multi_miller_loop, G1Affine, G2Affine, G2Prepared, G2Projective, Gt, Scalar};
+use sha2::{Digest, Sha512};
+
+/// Signatures on distinct $m_1$ under a common $m_0$, created by [aggregate]
+///
+/// Each $\alpha_i$ is different so the pairings $e(\sigma_i, w_2\alpha_i)$ cannot be merged and the aggregate keeps
+/// every $(\sigma_i, \alpha_i, \beta_i)$. What is saved is verification: [AggregateSignature::verify] checks all
+/// $N$ signatures with $N + 1$ Miller loops and a single final exponentiation rather than $2N$ full pairings.
+///
+/// The signatures are held in a canonical order of their $m_1$ so the aggregate does not depend on the order in
+/// which they were collected, and the verifier may supply the messages in any order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AggregateSignature {
+    signatures: Vec<Signature>,
+}
+
+/// Aggregate `(m1, signature)` pairs that were all issued on the same $m_0$
+///
+/// No verification is done here; a single invalid signature makes [AggregateSignature::verify] fail for the whole
+/// aggregate.
+pub fn aggregate(sigs: &[(Scalar, Signature)]) -> AggregateSignature {
+    let mut sigs = sigs.to_vec();
+    sigs.sort_by_key(|(m1, _)| m1.to_bytes());
+
+    AggregateSignature {
+        signatures: sigs.into_iter().map(|(_, signature)| signature).collect(),
+    }
+}
+
+impl AggregateSignature {
+    /// The number of aggregated signatures
+    pub fn len(&self) -> usize {
+        self.signatures.len()
+    }
+
+    /// Whether no signatures were aggregated
+    pub fn is_empty(&self) -> bool {
+        self.signatures.is_empty()
+    }
+
+    /// Verify that every aggregated signature is valid on $(m_0, m_{1,i})$
+    ///
+    /// Checks the random linear combination
+    ///
+    /// $\prod_i e({\sigma_i}^{r_i}, w_2\alpha_i) = e(g_1, \prod_i ({h_2}^{m_0}{g_2}^{m_{1,i}}{u_2}{v_2}^{\beta_i})^{r_i})$
+    ///
+    /// where the $r_i$ are derived by hashing the [PublicKey], $m_0$, every $m_{1,i}$ and every signature under
+    /// [AGGREGATE_DST]. Since the coefficients are fixed only once the whole aggregate is, an invalid signature
+    /// cannot be offset by the others except with negligible probability.
+    ///
+    /// # Security
+    /// The messages must be distinct. Two entries on the same $m_1$ contribute identical message terms, so an
+    /// aggregate containing duplicates no longer attests to $N$ different messages and an adversary holding one
+    /// valid signature could pad the aggregate with it. Duplicates are therefore rejected.
+    ///
+    /// Fails with [Error::InvalidSignature] if the aggregate is empty, if `m1s` does not match it in length, if
+    /// `m1s` contains duplicates or if any signature is invalid.
+    pub fn verify(&self, pk: &PublicKey, m0: &Scalar, m1s: &[Scalar]) -> Result<(), Error> {
+        if self.signatures.is_empty() || m1s.len() != self.signatures.len() {
+            return Err(Error::InvalidSignature);
+        }
+
+        let mut m1s = m1s.to_vec();
+        m1s.sort_by_key(|m1| m1.to_bytes());
+        if m1s.windows(2).any(|pair| pair[0] == pair[1]) {
+            return Err(Error::InvalidSignature);
+        }
+
+        if !self
+            .signatures
+            .iter()
+            .all(|signature| bool::from(well_formed(&pk.w2, signature)))
+        {
+            return Err(Error::InvalidSignature);
+        }
+
+        let coefficients = self.coefficients(pk, m0, &m1s);
+
+        let mut terms: Vec<(G1Affine, G2Prepared)> = Vec::with_capacity(self.signatures.len() + 1);
+        let mut r_sum = Scalar::zero();
+        let mut r_m1 = Scalar::zero();
+        let mut r_beta = Scalar::zero();
+        for ((signature, m1), r) in self.signatures.iter().zip(&m1s).zip(&coefficients) {
+            let lhs2 = G2Affine::from(G2Projective::from(pk.w2) + signature.alpha);
+            terms.push((G1Affine::from(signature.sigma * r), G2Prepared::from(lhs2)));
+            r_sum += r;
+            r_m1 += r * m1;
+            r_beta += r * signature.beta;
+        }
+
+        let rhs2 = G2Affine::from((pk.h2 * m0 + pk.u2) * r_sum + pk.g2 * r_m1 + pk.v2 * r_beta);
+        terms.push((-pk.g1, G2Prepared::from(rhs2)));
+
+        let terms: Vec<(&G1Affine, &G2Prepared)> = terms.iter().map(|(a, b)| (a, b)).collect();
+        if multi_miller_loop(&terms).final_exponentiation() != Gt::identity() {
+            return Err(Error::InvalidSignature);
+        }
+
+        Ok(())
+    }
+
+    /// $r_i \leftarrow H(D \parallel i)$ where $D$ is the SHA-512 digest of the whole aggregate and its messages
+    fn coefficients(&self, pk: &PublicKey, m0: &Scalar, m1s: &[Scalar]) -> Vec<Scalar> {
+        let mut hasher = Sha512::new();
+        hasher.update(pk.to_bytes());
+        hasher.update(m0.to_bytes());
+        for (signature, m1) in self.signatures.iter().zip(m1s) {
+            hasher.update(m1.to_bytes());
+            hasher.update(signature.to_bytes());
+        }
+        let digest = hasher.finalize();
+
+        (0..self.signatures.len() as u64)
+            .map(|i| {
+                let mut msg = [0u8; 72];
+                msg[0..64].copy_from_slice(&digest);
+                msg[64..72].copy_from_slice(&i.to_be_bytes());
+                hash_to_scalar(AGGREGATE_DST, &msg)
+            })
+            .collect()
+    }
+}
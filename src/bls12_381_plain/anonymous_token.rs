@@ -0,0 +1,83 @@
+//! Anonymous tokens on byte strings, for applications that run both roles in one process
+//!
+//! [issue] hashes the public `info` and the private `message` to $m_0$ and $m_1$ as [User::set_message_bytes] and
+//! [Signer::set_message_bytes] do, runs the four steps with [sign_in_process] and returns a [Token] holding the
+//! [Signature] and both byte strings. [Token::verify] repeats the hashing and checks the [Signature], so neither side
+//! handles a [Scalar](bls12_381::Scalar) or a group element.
+//!
+//! ```rust
+//! use ziglet_okamoto::bls12_381_plain::anonymous_token::issue;
+//! use ziglet_okamoto::bls12_381_plain::{Error, KeyPair};
+//!
+//! let key_pair = KeyPair::generate(rand_core::OsRng);
+//! let token = issue(&key_pair, b"valid until 2026-12-31", b"serial 8f3a1c").expect("issued");
+//!
+//! assert_eq!(token.verify(&key_pair.public_key), Ok(()));
+//!
+//! let mut forged = token.clone();
+//! forged.info = b"valid until 2099-12-31".to_vec();
+//! assert_eq!(forged.verify(&key_pair.public_key), Err(Error::InvalidSignature));
+//! ```
+//!
+//! When the [User] and the [Signer] are different parties, run the protocol with them directly.
+
+use super::{
+    hash_to_scalar, sign_in_process, verify_signature, Error, KeyPair, PublicKey, Signature, INFO_DST, MESSAGE_DST,
+    SIGNATURE_BYTES,
+};
+#[cfg(doc)]
+use super::{Signer, User};
+use alloc::vec::Vec;
+
+/// A [Signature] on `info` and `message` in the encoding of [Signature::to_bytes]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Token {
+    /// [Signature::to_bytes]
+    pub signature: [u8; SIGNATURE_BYTES],
+    /// The shared message, hashed to $m_0$ under [INFO_DST]
+    pub info: Vec<u8>,
+    /// The blinded message, hashed to $m_1$ under [MESSAGE_DST]
+    pub message: Vec<u8>,
+}
+
+impl Token {
+    /// Check the [Signature] on `info` and `message` under `public_key`
+    ///
+    /// # Errors
+    /// - [Error::DecodingFailed], [Error::PointNotOnCurve], [Error::NotInSubgroup] or [Error::NonCanonicalScalar] as
+    ///   in [Signature::from_bytes]
+    /// - [Error::InvalidSignature] if the [Signature] does not verify
+    pub fn verify(&self, public_key: &PublicKey) -> Result<(), Error> {
+        let signature = Signature::from_bytes(&self.signature)?;
+
+        verify_signature(
+            public_key,
+            &hash_to_scalar(INFO_DST, &self.info),
+            &hash_to_scalar(MESSAGE_DST, &self.message),
+            &signature.sigma,
+            &signature.alpha,
+            &signature.beta,
+        )
+    }
+}
+
+/// Issue a [Token] on `info` and `message` under `key_pair` with the operating system RNG
+///
+/// # Errors
+/// Any [Error] of [sign_in_process], e.g. [Error::DuplicateMessages] in the negligible case that both byte strings
+/// hash to the same message
+pub fn issue(key_pair: &KeyPair, info: &[u8], message: &[u8]) -> Result<Token, Error> {
+    let signature = sign_in_process(
+        key_pair,
+        hash_to_scalar(INFO_DST, info),
+        hash_to_scalar(MESSAGE_DST, message),
+        rand_core::OsRng,
+        rand_core::OsRng,
+    )?;
+
+    Ok(Token {
+        signature: signature.to_bytes(),
+        info: info.to_vec(),
+        message: message.to_vec(),
+    })
+}
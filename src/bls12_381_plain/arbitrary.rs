@@ -0,0 +1,88 @@
+//! [Arbitrary] implementations for the protocol messages, for structured fuzzing
+//!
+//! Points are usually multiples of the generator and therefore valid, but an input may also select the identity or
+//! a point that is not on the curve. Scalars are usually a wide reduction of 64 input bytes and occasionally zero or
+//! one. Every value is a function of the [Unstructured] input alone, so a crashing input reproduces.
+
+use super::{Challenge, Commitment, PartialSignature, Signature, Witness};
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+use bls12_381::{G1Affine, G2Affine, Scalar};
+
+fn scalar(u: &mut Unstructured<'_>) -> Result<Scalar> {
+    Ok(match u.arbitrary::<u8>()? % 16 {
+        0 => Scalar::zero(),
+        1 => Scalar::one(),
+        _ => Scalar::from_bytes_wide(&u.arbitrary()?),
+    })
+}
+
+/// The flags of a compressed encoding must be clear in an uncompressed one
+fn clear_flags(bytes: &mut [u8]) {
+    bytes[0] &= 0b0001_1111;
+}
+
+fn g1(u: &mut Unstructured<'_>) -> Result<G1Affine> {
+    Ok(match u.arbitrary::<u8>()? % 16 {
+        0 => G1Affine::identity(),
+        1 => {
+            let mut bytes: [u8; 96] = u.arbitrary()?;
+            clear_flags(&mut bytes);
+            Option::from(G1Affine::from_uncompressed_unchecked(&bytes)).unwrap_or_else(G1Affine::identity)
+        }
+        _ => G1Affine::from(G1Affine::generator() * scalar(u)?),
+    })
+}
+
+fn g2(u: &mut Unstructured<'_>) -> Result<G2Affine> {
+    Ok(match u.arbitrary::<u8>()? % 16 {
+        0 => G2Affine::identity(),
+        1 => {
+            let mut bytes: [u8; 192] = u.arbitrary()?;
+            clear_flags(&mut bytes);
+            Option::from(G2Affine::from_uncompressed_unchecked(&bytes)).unwrap_or_else(G2Affine::identity)
+        }
+        _ => G2Affine::from(G2Affine::generator() * scalar(u)?),
+    })
+}
+
+impl<'a> Arbitrary<'a> for Challenge {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        scalar(u).map(Challenge)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Commitment {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Commitment { w: g1(u)?, x: g1(u)? })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Witness {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Witness {
+            b1: scalar(u)?,
+            b2: scalar(u)?,
+            b3: scalar(u)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for PartialSignature {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(PartialSignature {
+            y: g1(u)?,
+            r: g2(u)?,
+            l: scalar(u)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Signature {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Signature {
+            sigma: g1(u)?,
+            alpha: g2(u)?,
+            beta: scalar(u)?,
+        })
+    }
+}
@@ -0,0 +1,185 @@
+//! Batch verification of a stream of signatures under one [PublicKey] in constant memory
+
+use super::{hash_to_scalar, well_formed, Error, PublicKey, Signature, BATCH_DST};
+use bls12_381::{multi_miller_loop, G1Affine, G2Affine, G2Prepared, G2Projective, Gt, MillerLoopResult, Scalar};
+use ff::Field;
+use rand_core::{CryptoRng, RngCore};
+use sha2::{Digest, Sha512};
+
+/// The running aggregate of [BatchVerifier], shared with [batch_verify_deterministic]
+struct Accumulator<'a> {
+    public_key: &'a PublicKey,
+    miller_loops: MillerLoopResult,
+    /// $\sum_i r_i$, $\sum_i r_i m_{0,i}$, $\sum_i r_i m_{1,i}$ and $\sum_i r_i \beta_i$
+    sums: [Scalar; 4],
+    malformed: bool,
+}
+
+impl<'a> Accumulator<'a> {
+    fn new(public_key: &'a PublicKey) -> Self {
+        Self {
+            public_key,
+            miller_loops: MillerLoopResult::default(),
+            sums: [Scalar::zero(); 4],
+            malformed: false,
+        }
+    }
+
+    /// Multiply the Miller loop of $e({\sigma}^r, w_2\alpha)$ into the aggregate and add $r$ times the scalars to the
+    /// sums
+    ///
+    /// A signature that [super::verify_signature] rejects before the pairing is not aggregated but fails
+    /// [Accumulator::finalize].
+    fn add(&mut self, r: Scalar, m0: &Scalar, m1: &Scalar, sig: &Signature) {
+        let Signature { sigma, alpha, beta } = sig;
+        if !bool::from(well_formed(&self.public_key.w2, sig)) {
+            self.malformed = true;
+            return;
+        }
+
+        let lhs2 = G2Prepared::from(G2Affine::from(G2Projective::from(self.public_key.w2) + alpha));
+        self.miller_loops += multi_miller_loop(&[(&G1Affine::from(sigma * r), &lhs2)]);
+
+        let [r_sum, r_m0, r_m1, r_beta] = &mut self.sums;
+        *r_sum += r;
+        *r_m0 += r * m0;
+        *r_m1 += r * m1;
+        *r_beta += r * beta;
+    }
+
+    fn finalize(self) -> Result<(), Error> {
+        if self.malformed {
+            return Err(Error::InvalidSignature);
+        }
+
+        let pk = self.public_key;
+        let [r_sum, r_m0, r_m1, r_beta] = self.sums;
+        let rhs2 = G2Prepared::from(G2Affine::from(
+            pk.h2 * r_m0 + pk.g2 * r_m1 + pk.u2 * r_sum + pk.v2 * r_beta,
+        ));
+        let miller_loops = self.miller_loops + multi_miller_loop(&[(&-pk.g1, &rhs2)]);
+
+        if miller_loops.final_exponentiation() != Gt::identity() {
+            return Err(Error::InvalidSignature);
+        }
+
+        Ok(())
+    }
+}
+
+/// Verifies signatures as they are pushed, keeping only a running aggregate
+///
+/// Checks the random linear combination
+///
+/// $\prod_i e({\sigma_i}^{r_i}, w_2\alpha_i) = e(g_1, {h_2}^{\sum_i r_i m_{0,i}}{g_2}^{\sum_i r_i m_{1,i}}
+/// {u_2}^{\sum_i r_i}{v_2}^{\sum_i r_i \beta_i})$
+///
+/// with $r_i$ drawn from `rng` as each signature is pushed. Each $\alpha_i$ is different, so [BatchVerifier::push]
+/// runs the Miller loop of its own pairing and multiplies it into the aggregate; [BatchVerifier::finalize] adds the
+/// one on the right and does the single final exponentiation. Besides the aggregate only the four sums are kept, so
+/// memory does not grow with the number of signatures.
+///
+/// Unlike [super::AggregateSignature] the messages need not share $m_0$ or be distinct, since nothing is merged
+/// across signatures; a duplicate is simply verified twice. The result only says whether all signatures are valid,
+/// so verify them one by one with [super::verify_signature] to find the invalid ones after a failure.
+///
+/// [batch_verify] runs one over a slice and [batch_verify_deterministic] derives the $r_i$ from the batch instead.
+pub struct BatchVerifier<'a, R: RngCore + CryptoRng> {
+    accumulator: Accumulator<'a>,
+    rng: R,
+    len: usize,
+}
+
+impl<'a, R: RngCore + CryptoRng> BatchVerifier<'a, R> {
+    /// Start an empty batch under `public_key`
+    ///
+    /// `rng` draws the coefficients $r_i$, which must be unpredictable to whoever supplies the signatures.
+    pub fn new(public_key: &'a PublicKey, rng: R) -> Self {
+        Self {
+            accumulator: Accumulator::new(public_key),
+            rng,
+            len: 0,
+        }
+    }
+
+    /// Add a [Signature] on $(m_0, m_1)$ to the batch
+    ///
+    /// A malformed signature, which [super::verify_signature] rejects before the pairing, is not aggregated but makes
+    /// [BatchVerifier::finalize] fail.
+    pub fn push(&mut self, m0: &Scalar, m1: &Scalar, sig: &Signature) {
+        self.len += 1;
+
+        let r = loop {
+            let r = Scalar::random(&mut self.rng);
+            if !bool::from(r.is_zero()) {
+                break r;
+            }
+        };
+        self.accumulator.add(r, m0, m1, sig);
+    }
+
+    /// The number of signatures pushed so far
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether no signatures were pushed
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Verify every pushed signature at once
+    ///
+    /// An empty batch verifies.
+    ///
+    /// # Errors
+    /// [Error::InvalidSignature] if any pushed signature is malformed or invalid
+    pub fn finalize(self) -> Result<(), Error> {
+        self.accumulator.finalize()
+    }
+}
+
+/// Verify every `(m0, m1, signature)` in `items` at once with a [BatchVerifier] drawing from `rng`
+///
+/// # Errors
+/// [Error::InvalidSignature] if any signature is malformed or invalid
+pub fn batch_verify<R: RngCore + CryptoRng>(
+    pk: &PublicKey,
+    items: &[(Scalar, Scalar, Signature)],
+    rng: R,
+) -> Result<(), Error> {
+    let mut verifier = BatchVerifier::new(pk, rng);
+    for (m0, m1, signature) in items {
+        verifier.push(m0, m1, signature);
+    }
+    verifier.finalize()
+}
+
+/// [batch_verify] with the coefficients derived from the batch rather than drawn from an RNG
+///
+/// $r_i \leftarrow H(D \parallel i)$ under [BATCH_DST], where $D$ is the SHA-512 digest of the [PublicKey] and every
+/// `(m0, m1, signature)` in order. This is reproducible and avoids one RNG draw per signature, at the cost of hashing
+/// the batch twice. The coefficients are fixed only once the whole batch is, so whoever supplies the signatures cannot
+/// choose an invalid one that the others offset, as with [super::AggregateSignature::verify].
+///
+/// # Errors
+/// [Error::InvalidSignature] if any signature is malformed or invalid
+pub fn batch_verify_deterministic(pk: &PublicKey, items: &[(Scalar, Scalar, Signature)]) -> Result<(), Error> {
+    let mut hasher = Sha512::new();
+    hasher.update(pk.to_bytes());
+    for (m0, m1, signature) in items {
+        hasher.update(m0.to_bytes());
+        hasher.update(m1.to_bytes());
+        hasher.update(signature.to_bytes());
+    }
+    let digest = hasher.finalize();
+
+    let mut accumulator = Accumulator::new(pk);
+    for (i, (m0, m1, signature)) in items.iter().enumerate() {
+        let mut msg = [0u8; 72];
+        msg[0..64].copy_from_slice(&digest);
+        msg[64..72].copy_from_slice(&(i as u64).to_be_bytes());
+        accumulator.add(hash_to_scalar(BATCH_DST, &msg), m0, m1, signature);
+    }
+    accumulator.finalize()
+}
@@ -0,0 +1,108 @@
+//! The [User]'s blinding and unblinding on their own, for protocols that build on the signature without the proof of
+//! knowledge of [User::commit] and [User::compute_witness]
+
+use super::msm::msm4;
+use super::{decode_g1, Error, PartialSignature, RandomnessSource, Signature, User, UserState};
+use bls12_381::{G1Affine, Scalar};
+use ff::Field;
+
+/// Length of [BlindedMessage::to_bytes]: one compressed point in $\mathbb{G_1}$
+pub const BLINDED_MESSAGE_BYTES: usize = 48;
+
+/// The blinded message $X = ({h_1}^{m_0}{g_1}^{m_1}u_1{v_1}^{s})^t$ from [User::blind]
+///
+/// This is the $X$ of a [super::Commitment], which a [super::Signer] turns into the [PartialSignature]
+/// $Y = (X{v_1}^{l})^{1/(x + r)}$, $R = {g_2}^r$.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BlindedMessage {
+    /// $X \in \mathbb{G_1}$
+    pub x: G1Affine,
+}
+
+impl BlindedMessage {
+    /// Serialize as the compressed encoding of $X$
+    pub fn to_bytes(&self) -> [u8; BLINDED_MESSAGE_BYTES] {
+        self.x.to_compressed()
+    }
+
+    /// Deserialize from the encoding produced by [BlindedMessage::to_bytes]
+    ///
+    /// Fails with [Error::DecodingFailed] unless `bytes` is [BLINDED_MESSAGE_BYTES] long and with
+    /// [Error::PointNotOnCurve] or [Error::NotInSubgroup] if $X$ is not a valid group element
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != BLINDED_MESSAGE_BYTES {
+            return Err(Error::DecodingFailed);
+        }
+        let x = decode_g1(bytes)?;
+
+        Ok(Self { x })
+    }
+}
+
+impl<'a, R: RandomnessSource> User<'a, R> {
+    /// Blind $(m_0, m_1)$ with fresh $s, t$ into $X$, for a protocol that proves knowledge of $X$'s opening itself
+    ///
+    /// Replaces [User::set_message], [User::commit] and [User::compute_witness] and moves straight to
+    /// [UserState::ReadyToSign]; the [PartialSignature] for the [BlindedMessage] is then passed to [User::unblind].
+    /// $X$ binds both messages, so $m_0$ is given here along with $m_1$. The four-step flow remains the way to obtain
+    /// a partially blind signature.
+    ///
+    /// **The signer must still verify a proof of knowledge of $m_1, s, t$ with $X$ opening to the agreed $m_0$**
+    /// before it signs $X$, as [Signer::verify_witness](super::Signer::verify_witness) does for the $X$ of a
+    /// [Commitment](super::Commitment). This method produces no such proof. A signer that signs an unproven $X$
+    /// cannot tell which $m_0$ it contains, so the [User] can obtain a signature on any $m_0$, and the security
+    /// of the scheme against forgery no longer holds.
+    ///
+    /// # Errors
+    /// - [Error::WrongState] unless in [UserState::ReadyToSetMessage]
+    /// - [Error::ScalarIsZero] or [Error::DuplicateMessages] as in [User::set_message], which abort
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "User::blind", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
+    pub fn blind(&mut self, m0: Scalar, m1: Scalar) -> Result<BlindedMessage, Error> {
+        self.state.expect(UserState::ReadyToSetMessage)?;
+
+        self.check_messages(&m0, &m1)?;
+
+        let s = self.rng.next_scalar();
+        let t = loop {
+            let t = self.rng.next_scalar();
+            if !bool::from(t.is_zero()) {
+                break t;
+            }
+        };
+        let pk = self.public_key;
+
+        self.m0 = m0;
+        self.m1 = m1;
+        self.s = s;
+        self.t = t;
+        self.X = msm4([&pk.h1, &pk.g1, &pk.u1, &pk.v1], [&(m0 * t), &(m1 * t), &t, &(s * t)]);
+        self.set_state(UserState::ReadyToSign);
+
+        Ok(BlindedMessage {
+            x: G1Affine::from(self.X),
+        })
+    }
+
+    /// Unblind the [PartialSignature] $(Y, R, l)$ returned for a [BlindedMessage] into the [Signature] on
+    /// $(m_0, m_1)$
+    ///
+    /// $\sigma \leftarrow Y^{1/(ft)}$, $\alpha \leftarrow {w_2}^{f-1}R^f$, $\beta \leftarrow s + l/t$
+    ///
+    /// for a fresh $f$. This is the final step of [User::sign], with the same checks on $Y$ and $R$, and applies to
+    /// a [PartialSignature] following [User::blind] or [User::compute_witness] alike.
+    ///
+    /// # Errors
+    /// As [User::sign]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "User::unblind", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
+    pub fn unblind(&mut self, token: &PartialSignature) -> Result<Signature, Error> {
+        self.state.expect(UserState::ReadyToSign)?;
+
+        self.check_and_finish_sign(token)
+    }
+}
@@ -0,0 +1,112 @@
+//! Assembling a [PublicKey] from generators chosen outside of [KeyPair::generate]
+
+#[cfg(doc)]
+use super::KeyPair;
+use super::{Error, PublicKey};
+use bls12_381::{G1Affine, G2Affine};
+
+/// Builds a [PublicKey] from externally supplied generators, e.g. from a setup shared between several signers
+///
+/// [PublicKeyBuilder::build] checks the result with [PublicKey::validate]. In particular each of $h_2, u_2, v_2$ must
+/// have the same exponent relative to $g_2$ as $h_1, u_1, v_1$ relative to $g_1$, so only $g_1$ and $g_2$ can be
+/// derived independently, e.g. by hashing to the curve.
+///
+/// The exponents of $h, u, v$ must remain unknown to users. With them the verification equation reduces to a single
+/// exponent of $g_2$, and one [Signature](super::Signature) yields a $\beta$ that makes it hold for any other
+/// messages. Generators derived with public exponents, e.g. hashed to a [Scalar](bls12_381::Scalar), are therefore
+/// forgeable, and a setup without anyone knowing the exponents is not possible with this builder. Whoever derives
+/// the generators has to keep the exponents secret or discard them, as [KeyPair::generate] does for the signer.
+///
+/// ```rust
+/// # use bls12_381::{G2Affine, Scalar};
+/// # use ff::Field;
+/// # use rand_core::OsRng;
+/// # use ziglet_okamoto::bls12_381_plain::{KeyPair, PublicKeyBuilder};
+/// // Generators from a setup whose exponents stay secret, here those of another signer's key
+/// let setup = KeyPair::generate(OsRng).public_key;
+/// let secret_key = Scalar::random(OsRng);
+///
+/// let public_key = PublicKeyBuilder::new()
+///     .g1(setup.g1)
+///     .h1(setup.h1)
+///     .u1(setup.u1)
+///     .v1(setup.v1)
+///     .g2(setup.g2)
+///     .h2(setup.h2)
+///     .u2(setup.u2)
+///     .v2(setup.v2)
+///     .w2(G2Affine::from(setup.g2 * secret_key))
+///     .build()
+///     .expect("consistent generators");
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct PublicKeyBuilder {
+    public_key: PublicKey,
+}
+
+impl PublicKeyBuilder {
+    /// Start with every generator set to the identity, which [PublicKeyBuilder::build] rejects unless replaced
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set $g_1$
+    pub fn g1(mut self, g1: G1Affine) -> Self {
+        self.public_key.g1 = g1;
+        self
+    }
+
+    /// Set $h_1$
+    pub fn h1(mut self, h1: G1Affine) -> Self {
+        self.public_key.h1 = h1;
+        self
+    }
+
+    /// Set $u_1$
+    pub fn u1(mut self, u1: G1Affine) -> Self {
+        self.public_key.u1 = u1;
+        self
+    }
+
+    /// Set $v_1$
+    pub fn v1(mut self, v1: G1Affine) -> Self {
+        self.public_key.v1 = v1;
+        self
+    }
+
+    /// Set $g_2$
+    pub fn g2(mut self, g2: G2Affine) -> Self {
+        self.public_key.g2 = g2;
+        self
+    }
+
+    /// Set $h_2$, which must have the exponent of $h_1$
+    pub fn h2(mut self, h2: G2Affine) -> Self {
+        self.public_key.h2 = h2;
+        self
+    }
+
+    /// Set $u_2$, which must have the exponent of $u_1$
+    pub fn u2(mut self, u2: G2Affine) -> Self {
+        self.public_key.u2 = u2;
+        self
+    }
+
+    /// Set $v_2$, which must have the exponent of $v_1$
+    pub fn v2(mut self, v2: G2Affine) -> Self {
+        self.public_key.v2 = v2;
+        self
+    }
+
+    /// Set ${w_2} = {g_2}^x$ for the signer's secret key $x$
+    pub fn w2(mut self, w2: G2Affine) -> Self {
+        self.public_key.w2 = w2;
+        self
+    }
+
+    /// Produce the [PublicKey] if it passes [PublicKey::validate]
+    pub fn build(self) -> Result<PublicKey, Error> {
+        self.public_key.validate()?;
+        Ok(self.public_key)
+    }
+}
@@ -0,0 +1,75 @@
+//! Memoizing [verify_signature] for signatures that are seen repeatedly
+
+use super::{verify_signature, Error, PublicKey, Signature};
+use bls12_381::Scalar;
+use core::num::NonZeroUsize;
+use lru::LruCache;
+use sha2::{Digest, Sha256};
+
+/// Domain separation tag of the cache keys of [VerificationCache]
+const CACHE_KEY_DST: &[u8] = b"OKAMOTO-BLS12381-PLAIN-VERIFICATION-CACHE";
+
+/// The results of the last `capacity` distinct verifications, evicting the least recently used
+///
+/// Entries are keyed by SHA-256 over [PublicKey::fingerprint], $m_0$, $m_1$ and [Signature::to_bytes], and are only
+/// ever written with the outcome of [verify_signature] on exactly those inputs; there is no way to insert a result
+/// from outside. A retried or duplicated delivery of the same signature is answered without the pairings.
+#[derive(Debug)]
+pub struct VerificationCache {
+    results: LruCache<[u8; 32], Result<(), Error>>,
+    hits: u64,
+}
+
+impl VerificationCache {
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            results: LruCache::new(capacity),
+            hits: 0,
+        }
+    }
+
+    /// [verify_signature], returning the cached result if these inputs were verified before
+    pub fn verify_cached(
+        &mut self,
+        public_key: &PublicKey,
+        m0: &Scalar,
+        m1: &Scalar,
+        signature: &Signature,
+    ) -> Result<(), Error> {
+        let mut hasher = Sha256::new();
+        hasher.update(CACHE_KEY_DST);
+        hasher.update(public_key.fingerprint());
+        hasher.update(m0.to_bytes());
+        hasher.update(m1.to_bytes());
+        hasher.update(signature.to_bytes());
+        let key: [u8; 32] = hasher.finalize().into();
+
+        if let Some(result) = self.results.get(&key) {
+            self.hits += 1;
+            return *result;
+        }
+
+        let result = verify_signature(public_key, m0, m1, &signature.sigma, &signature.alpha, &signature.beta);
+        self.results.put(key, result);
+        result
+    }
+
+    /// The number of calls to [VerificationCache::verify_cached] answered from the cache
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// The number of cached results
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Drop every cached result, e.g. after a key was revoked
+    pub fn clear(&mut self) {
+        self.results.clear();
+    }
+}
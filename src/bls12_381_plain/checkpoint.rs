@@ -0,0 +1,137 @@
+//! Checkpointing a [Signer] between steps so that a flow survives a restart
+
+use super::{
+    decode_g1, decode_scalar, Error, KeyPair, RandomnessSource, Signer, SignerState, StateKind, SESSION_ID_BYTES,
+};
+use alloc::vec::Vec;
+use bls12_381::{G1Affine, G1Projective};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
+
+/// Domain separation tag of the authentication tag of [Signer::save_state]
+const CHECKPOINT_DST: &[u8] = b"OKAMOTO-BLS12381-PLAIN-SIGNER-CHECKPOINT";
+
+/// The version byte that starts [Signer::save_state]
+const CHECKPOINT_VERSION: u8 = 2;
+
+/// Length of [Signer::save_state]: version, state, Fiat-Shamir flag, session flag and id, [Signer::epoch], $m_0$, $W$,
+/// $X$, $\eta$ and the tag
+const CHECKPOINT_BYTES: usize = 4 + SESSION_ID_BYTES + 8 + 32 + 2 * 48 + 32 + 32;
+
+/// HMAC-SHA256 under the secret key of `key_pair` over [CHECKPOINT_DST] and `body`
+fn checkpoint_tag(key_pair: &KeyPair, body: &[u8]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(&key_pair.secret_key.to_bytes()).expect("HMAC accepts any key length");
+    mac.update(CHECKPOINT_DST);
+    mac.update(body);
+    mac.finalize().into_bytes().into()
+}
+
+impl<'a, R: RandomnessSource> Signer<'a, R> {
+    /// Serialize the flow in progress for [Signer::restore_state], e.g. between [Signer::commit] and [Signer::sign]
+    ///
+    /// The checkpoint holds the [SignerState], the session id of [Signer::new_with_session], [Signer::epoch], $m_0$,
+    /// $W$, $X$ and $\eta$, all of which the [User] knows, and no
+    /// secret: the nonces $r$ and $l$ are only drawn in [Signer::sign], which ends the flow. It is authenticated with
+    /// HMAC-SHA256 under the secret key, so [Signer::restore_state] rejects one that was altered, e.g. moved back
+    /// to an earlier state to verify another witness, or saved under another [KeyPair].
+    ///
+    /// An unaltered but older checkpoint would still resume a flow that has since moved on, e.g. verify a second
+    /// witness after [Signer::sign] and so hand out a second partial signature. Its authenticated [Signer::epoch] is
+    /// lower than the one reached since, which [Signer::restore_state] rejects.
+    ///
+    /// # Errors
+    /// - [Error::WrongState] with `expected` [StateKind::ReadyToSign] in [SignerState::Signed] or
+    ///   [SignerState::Aborted], which have nothing to resume
+    /// - [Error::Unsupported] for a [Signer::new_deterministic], whose nonce generator cannot be restored
+    pub fn save_state(&self) -> Result<Vec<u8>, Error> {
+        let state = match self.state {
+            SignerState::ReadyToSetMessage => 0,
+            SignerState::ReadyToCommit => 1,
+            SignerState::ReadyToVerifyWitness => 2,
+            SignerState::ReadyToSign => 3,
+            SignerState::Signed | SignerState::Aborted(_) => {
+                return Err(Error::WrongState {
+                    expected: StateKind::ReadyToSign,
+                    actual: self.state.kind(),
+                })
+            }
+        };
+        if self.nonces.is_some() {
+            return Err(Error::Unsupported);
+        }
+
+        let mut bytes = Vec::with_capacity(CHECKPOINT_BYTES);
+        bytes.extend_from_slice(&[
+            CHECKPOINT_VERSION,
+            state,
+            self.fiat_shamir as u8,
+            self.session_id.is_some() as u8,
+        ]);
+        bytes.extend_from_slice(&self.session_id.unwrap_or_default());
+        bytes.extend_from_slice(&self.epoch.to_le_bytes());
+        bytes.extend_from_slice(&self.m0.to_bytes());
+        bytes.extend_from_slice(&G1Affine::from(self.W).to_compressed());
+        bytes.extend_from_slice(&G1Affine::from(self.X).to_compressed());
+        bytes.extend_from_slice(&self.eta.to_bytes());
+        let tag = checkpoint_tag(self.key_pair, &bytes);
+        bytes.extend_from_slice(&tag);
+
+        Ok(bytes)
+    }
+
+    /// Resume the flow saved by [Signer::save_state] of a [Signer] of `key_pair`, drawing from `rng` from now on
+    ///
+    /// `latest_epoch` is the last [Signer::epoch] persisted by the caller, or 0 if there is none. The restored
+    /// [Signer] continues from the epoch of the checkpoint.
+    ///
+    /// # Errors
+    /// - [Error::DecodingFailed] unless `bytes` is a checkpoint of this version made under `key_pair` and unaltered
+    ///   since
+    /// - [Error::StaleCheckpoint] if it was saved at an epoch below `latest_epoch`
+    pub fn restore_state(key_pair: &'a KeyPair, bytes: &[u8], latest_epoch: u64, rng: R) -> Result<Self, Error> {
+        if bytes.len() != CHECKPOINT_BYTES || bytes[0] != CHECKPOINT_VERSION {
+            return Err(Error::DecodingFailed);
+        }
+        let (body, tag) = bytes.split_at(CHECKPOINT_BYTES - 32);
+        if !bool::from(checkpoint_tag(key_pair, body).ct_eq(tag)) {
+            return Err(Error::DecodingFailed);
+        }
+
+        let state = match bytes[1] {
+            0 => SignerState::ReadyToSetMessage,
+            1 => SignerState::ReadyToCommit,
+            2 => SignerState::ReadyToVerifyWitness,
+            3 => SignerState::ReadyToSign,
+            _ => return Err(Error::DecodingFailed),
+        };
+        let fiat_shamir = match bytes[2] {
+            0 => false,
+            1 => true,
+            _ => return Err(Error::DecodingFailed),
+        };
+        let (session_id, body) = body[4..].split_at(SESSION_ID_BYTES);
+        let session_id = match bytes[3] {
+            0 => None,
+            1 => Some(session_id.try_into().expect("split at SESSION_ID_BYTES")),
+            _ => return Err(Error::DecodingFailed),
+        };
+        let (epoch, body) = body.split_at(8);
+        let epoch = u64::from_le_bytes(epoch.try_into().expect("split at 8"));
+        if epoch < latest_epoch {
+            return Err(Error::StaleCheckpoint);
+        }
+
+        Ok(Self {
+            state,
+            fiat_shamir,
+            session_id,
+            epoch,
+            m0: decode_scalar(&body[..32])?,
+            W: G1Projective::from(decode_g1(&body[32..80])?),
+            X: G1Projective::from(decode_g1(&body[80..128])?),
+            eta: decode_scalar(&body[128..160])?,
+            ..Self::new(key_pair, rng)
+        })
+    }
+}
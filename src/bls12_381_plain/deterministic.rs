@@ -0,0 +1,106 @@
+//! HMAC-SHA256 deterministic random bit generator for [super::Signer::new_deterministic]
+
+use bls12_381::Scalar;
+use ff::Field;
+use hmac::{Hmac, Mac};
+use rand_core::{CryptoRng, RngCore};
+use sha2::Sha256;
+
+/// Domain separation tag mixed into the seed of every [HmacDrbg] instantiated by [super::Signer::new_deterministic]
+pub const DETERMINISTIC_DST: &[u8] = b"OKAMOTO-BLS12381-PLAIN-DETERMINISTIC";
+
+/// Domain separation tag for the [HmacDrbg] that derives the generators of a [super::PublicKey] from its secret key
+/// in [super::KeyPair::generate] and [super::KeyPair::from_secret_bytes], followed by the deployment's tag for
+/// [super::KeyPair::generate_with_dst]
+pub const KEYGEN_DST: &[u8] = b"OKAMOTO-BLS12381-PLAIN-KEYGEN";
+
+/// HMAC_DRBG with SHA-256 as specified in NIST SP 800-90A, the construction used for nonces in RFC 6979
+///
+/// Output is a deterministic function of the seed and of every input later passed to [HmacDrbg::reseed].
+#[derive(Clone)]
+pub struct HmacDrbg {
+    k: [u8; 32],
+    v: [u8; 32],
+}
+
+impl HmacDrbg {
+    /// Instantiate from the concatenation of `seed`
+    pub fn new(seed: &[&[u8]]) -> Self {
+        let mut drbg = Self {
+            k: [0x00; 32],
+            v: [0x01; 32],
+        };
+        drbg.update(seed);
+        drbg
+    }
+
+    /// Mix the concatenation of `data` into the state
+    pub fn reseed(&mut self, data: &[&[u8]]) {
+        self.update(data);
+    }
+
+    fn mac(&self) -> Hmac<Sha256> {
+        Hmac::<Sha256>::new_from_slice(&self.k).expect("HMAC accepts any key length")
+    }
+
+    /// $K \leftarrow HMAC(K, V \parallel sep \parallel data)$, $V \leftarrow HMAC(K, V)$, repeated with the second
+    /// separator unless `data` is empty
+    fn update(&mut self, data: &[&[u8]]) {
+        for separator in [0x00u8, 0x01u8] {
+            let mut mac = self.mac();
+            mac.update(&self.v);
+            mac.update(&[separator]);
+            for part in data {
+                mac.update(part);
+            }
+            self.k = mac.finalize().into_bytes().into();
+
+            let mut mac = self.mac();
+            mac.update(&self.v);
+            self.v = mac.finalize().into_bytes().into();
+
+            if data.iter().all(|part| part.is_empty()) {
+                break;
+            }
+        }
+    }
+
+    /// Draw a nonzero [Scalar] from 64 bytes of output
+    pub(super) fn scalar(&mut self) -> Scalar {
+        loop {
+            let mut wide = [0u8; 64];
+            self.fill_bytes(&mut wide);
+            let scalar = Scalar::from_bytes_wide(&wide);
+            if !bool::from(scalar.is_zero()) {
+                return scalar;
+            }
+        }
+    }
+}
+
+impl RngCore for HmacDrbg {
+    fn next_u32(&mut self) -> u32 {
+        rand_core::impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        rand_core::impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(32) {
+            let mut mac = self.mac();
+            mac.update(&self.v);
+            self.v = mac.finalize().into_bytes().into();
+            chunk.copy_from_slice(&self.v[..chunk.len()]);
+        }
+        self.update(&[]);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for HmacDrbg {}
@@ -0,0 +1,221 @@
+//! Hex, base64 and PEM text encodings of [PublicKey], [Signature] and [KeyPair]
+//!
+//! All wrap the canonical `to_bytes`/`from_bytes` encodings so the byte order is the same everywhere. Hex is
+//! lowercase when encoding and accepts either case when decoding. Base64 uses the standard alphabet with padding and
+//! requires the `base64` feature, as does PEM: the base64 of the bytes in lines of 64 characters between
+//! `-----BEGIN <label>-----` and `-----END <label>-----`, with the labels [PUBLIC_KEY_PEM_LABEL] and
+//! [SECRET_KEY_PEM_LABEL].
+
+#[cfg(feature = "base64")]
+use super::KeyPair;
+use super::{Error, PublicKey, Signature, PUBLIC_KEY_BYTES, SIGNATURE_BYTES};
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "base64")]
+use base64::{engine::general_purpose::STANDARD, Engine};
+#[cfg(feature = "base64")]
+use zeroize::Zeroize;
+
+/// Label of the PEM block produced by [PublicKey::to_pem]
+pub const PUBLIC_KEY_PEM_LABEL: &str = "OKAMOTO PUBLIC KEY";
+
+/// Label of the PEM block produced by [KeyPair::to_pem]
+pub const SECRET_KEY_PEM_LABEL: &str = "OKAMOTO SECRET KEY";
+
+#[cfg(feature = "alloc")]
+fn encode_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        hex.push(DIGITS[(byte >> 4) as usize] as char);
+        hex.push(DIGITS[(byte & 0x0f) as usize] as char);
+    }
+    hex
+}
+
+fn decode_hex<const N: usize>(hex: &str) -> Result<[u8; N], Error> {
+    fn nibble(digit: u8) -> Result<u8, Error> {
+        match digit {
+            b'0'..=b'9' => Ok(digit - b'0'),
+            b'a'..=b'f' => Ok(digit - b'a' + 10),
+            b'A'..=b'F' => Ok(digit - b'A' + 10),
+            _ => Err(Error::DecodingFailed),
+        }
+    }
+
+    let hex = hex.as_bytes();
+    if hex.len() != N * 2 {
+        return Err(Error::DecodingFailed);
+    }
+
+    let mut bytes = [0u8; N];
+    for (byte, pair) in bytes.iter_mut().zip(hex.chunks_exact(2)) {
+        *byte = (nibble(pair[0])? << 4) | nibble(pair[1])?;
+    }
+    Ok(bytes)
+}
+
+#[cfg(feature = "base64")]
+fn decode_base64<const N: usize>(text: &str) -> Result<[u8; N], Error> {
+    let mut decoded = STANDARD.decode(text).map_err(|_| Error::DecodingFailed)?;
+    let bytes = <[u8; N]>::try_from(decoded.as_slice()).map_err(|_| Error::DecodingFailed);
+    decoded.zeroize();
+    bytes
+}
+
+#[cfg(feature = "base64")]
+fn encode_pem(label: &str, bytes: &[u8]) -> String {
+    let mut body = STANDARD.encode(bytes);
+    let mut pem = String::with_capacity(body.len() + body.len() / 64 + 2 * label.len() + 40);
+    pem.push_str("-----BEGIN ");
+    pem.push_str(label);
+    pem.push_str("-----\n");
+    for line in body.as_bytes().chunks(64) {
+        pem.push_str(core::str::from_utf8(line).expect("base64 is ASCII"));
+        pem.push('\n');
+    }
+    pem.push_str("-----END ");
+    pem.push_str(label);
+    pem.push_str("-----\n");
+    body.zeroize();
+    pem
+}
+
+/// Fails with [Error::DecodingFailed] unless `text` is a single PEM block labeled `label`, ignoring surrounding
+/// whitespace, whose body is base64 of `N` bytes
+#[cfg(feature = "base64")]
+fn decode_pem<const N: usize>(label: &str, text: &str) -> Result<[u8; N], Error> {
+    let body = text
+        .trim()
+        .strip_prefix("-----BEGIN ")
+        .and_then(|text| text.strip_prefix(label))
+        .and_then(|text| text.strip_prefix("-----"))
+        .and_then(|text| text.strip_suffix("-----"))
+        .and_then(|text| text.strip_suffix(label))
+        .and_then(|text| text.strip_suffix("-----END "))
+        .ok_or(Error::DecodingFailed)?;
+
+    let mut joined: String = body.split_ascii_whitespace().collect();
+    let bytes = decode_base64::<N>(&joined);
+    joined.zeroize();
+    bytes
+}
+
+impl PublicKey {
+    /// Encode [PublicKey::to_bytes] as lowercase hex
+    #[cfg(feature = "alloc")]
+    pub fn to_hex(&self) -> String {
+        encode_hex(&self.to_bytes())
+    }
+
+    /// Decode the encoding produced by [PublicKey::to_hex]
+    ///
+    /// Fails with [Error::DecodingFailed] if the text is not 1344 hex digits and otherwise as
+    /// [PublicKey::from_bytes]
+    pub fn from_hex(hex: &str) -> Result<Self, Error> {
+        Self::from_bytes(&decode_hex::<PUBLIC_KEY_BYTES>(hex)?)
+    }
+
+    /// Encode [PublicKey::to_bytes] as padded standard base64
+    #[cfg(feature = "base64")]
+    pub fn to_base64(&self) -> String {
+        STANDARD.encode(self.to_bytes())
+    }
+
+    /// Decode the encoding produced by [PublicKey::to_base64]
+    ///
+    /// Fails with [Error::DecodingFailed] if the text is not base64 of [PUBLIC_KEY_BYTES] and otherwise as
+    /// [PublicKey::from_bytes]
+    #[cfg(feature = "base64")]
+    pub fn from_base64(text: &str) -> Result<Self, Error> {
+        Self::from_bytes(&decode_base64::<PUBLIC_KEY_BYTES>(text)?)
+    }
+
+    /// Encode [PublicKey::to_bytes] as a PEM block labeled [PUBLIC_KEY_PEM_LABEL]
+    #[cfg(feature = "base64")]
+    pub fn to_pem(&self) -> String {
+        encode_pem(PUBLIC_KEY_PEM_LABEL, &self.to_bytes())
+    }
+
+    /// Decode the encoding produced by [PublicKey::to_pem]
+    ///
+    /// Fails with [Error::DecodingFailed] if the text is not a PEM block labeled [PUBLIC_KEY_PEM_LABEL] holding
+    /// [PUBLIC_KEY_BYTES] and otherwise as [PublicKey::from_bytes]
+    #[cfg(feature = "base64")]
+    pub fn from_pem(text: &str) -> Result<Self, Error> {
+        Self::from_bytes(&decode_pem::<PUBLIC_KEY_BYTES>(PUBLIC_KEY_PEM_LABEL, text)?)
+    }
+}
+
+/// A [KeyPair] encoded by [KeyPair::to_pem]
+///
+/// Like [super::SecretKeyBytes] the text is overwritten with zeros when the value is dropped and implements neither
+/// `Debug` nor `Clone`.
+#[cfg(feature = "base64")]
+pub struct SecretKeyPem(String);
+
+#[cfg(feature = "base64")]
+impl SecretKeyPem {
+    /// Borrow the PEM text, e.g. to write it to a protected file
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(feature = "base64")]
+impl Drop for SecretKeyPem {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(feature = "base64")]
+impl KeyPair {
+    /// Encode [KeyPair::export_secret] as a PEM block labeled [SECRET_KEY_PEM_LABEL]
+    pub fn to_pem(&self) -> SecretKeyPem {
+        SecretKeyPem(encode_pem(SECRET_KEY_PEM_LABEL, self.export_secret().as_bytes()))
+    }
+
+    /// Decode the encoding produced by [KeyPair::to_pem]
+    ///
+    /// Fails with [Error::DecodingFailed] if the text is not a PEM block labeled [SECRET_KEY_PEM_LABEL] holding 32
+    /// bytes and otherwise as [KeyPair::from_secret_bytes]
+    pub fn from_pem(text: &str) -> Result<Self, Error> {
+        let mut bytes = decode_pem::<32>(SECRET_KEY_PEM_LABEL, text)?;
+        let key_pair = Self::from_secret_bytes(&bytes);
+        bytes.zeroize();
+        key_pair
+    }
+}
+
+impl Signature {
+    /// Encode [Signature::to_bytes] as lowercase hex
+    #[cfg(feature = "alloc")]
+    pub fn to_hex(&self) -> String {
+        encode_hex(&self.to_bytes())
+    }
+
+    /// Decode the encoding produced by [Signature::to_hex]
+    ///
+    /// Fails with [Error::DecodingFailed] if the text is not 352 hex digits and otherwise as
+    /// [Signature::from_bytes]
+    pub fn from_hex(hex: &str) -> Result<Self, Error> {
+        Self::from_bytes(&decode_hex::<SIGNATURE_BYTES>(hex)?)
+    }
+
+    /// Encode [Signature::to_bytes] as padded standard base64
+    #[cfg(feature = "base64")]
+    pub fn to_base64(&self) -> String {
+        STANDARD.encode(self.to_bytes())
+    }
+
+    /// Decode the encoding produced by [Signature::to_base64]
+    ///
+    /// Fails with [Error::DecodingFailed] if the text is not base64 of [SIGNATURE_BYTES] and otherwise as
+    /// [Signature::from_bytes]
+    #[cfg(feature = "base64")]
+    pub fn from_base64(text: &str) -> Result<Self, Error> {
+        Self::from_bytes(&decode_base64::<SIGNATURE_BYTES>(text)?)
+    }
+}
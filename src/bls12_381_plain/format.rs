@@ -0,0 +1,139 @@
+//! Choosing between the compressed and uncompressed point encodings of [PublicKey] and [Signature]
+
+use super::{decode_g1, decode_g2, decode_scalar, Error, PublicKey, Signature};
+#[cfg(doc)]
+use super::{PUBLIC_KEY_BYTES, SIGNATURE_BYTES};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use bls12_381::{G1Affine, G2Affine};
+
+/// Length of [PublicKey::encode] with [Encoding::Uncompressed]: four uncompressed points in $\mathbb{G_1}$ and five
+/// in $\mathbb{G_2}$
+pub const PUBLIC_KEY_UNCOMPRESSED_BYTES: usize = 4 * 96 + 5 * 192;
+
+/// Length of [Signature::encode] with [Encoding::Uncompressed]: uncompressed points in $\mathbb{G_1}$ and
+/// $\mathbb{G_2}$ and a scalar
+pub const SIGNATURE_UNCOMPRESSED_BYTES: usize = 96 + 192 + 32;
+
+/// The point encoding of [PublicKey::encode] and [Signature::encode]
+///
+/// Both encode the points in the same order as `to_bytes` and scalars as 32 little-endian bytes. The encodings
+/// differ in length and in the compression flag of every point, so bytes of one are rejected by the decoder of the
+/// other rather than misread.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// The $x$ coordinate of each point, the `to_bytes` encoding. Decoding recovers $y$ with a square root.
+    #[default]
+    CompressedCanonical,
+    /// Both coordinates of each point, about twice the size but decoded without a square root, e.g. for a
+    /// constrained verifier
+    Uncompressed,
+}
+
+#[cfg(feature = "alloc")]
+fn encode_points(format: Encoding, g1: &[G1Affine], g2: &[G2Affine]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for point in g1 {
+        match format {
+            Encoding::CompressedCanonical => bytes.extend_from_slice(&point.to_compressed()),
+            Encoding::Uncompressed => bytes.extend_from_slice(&point.to_uncompressed()),
+        }
+    }
+    for point in g2 {
+        match format {
+            Encoding::CompressedCanonical => bytes.extend_from_slice(&point.to_compressed()),
+            Encoding::Uncompressed => bytes.extend_from_slice(&point.to_uncompressed()),
+        }
+    }
+    bytes
+}
+
+/// The points decoded by [decode_points] and the bytes after them
+type DecodedPoints<'a, const G1: usize, const G2: usize> = ([G1Affine; G1], [G2Affine; G2], &'a [u8]);
+
+/// Decode `G1` points in $\mathbb{G_1}$ and `G2` in $\mathbb{G_2}$ followed by exactly `trailing` more bytes, which
+/// are returned
+fn decode_points<const G1: usize, const G2: usize>(
+    format: Encoding,
+    bytes: &[u8],
+    trailing: usize,
+) -> Result<DecodedPoints<'_, G1, G2>, Error> {
+    let (g1_len, g2_len) = match format {
+        Encoding::CompressedCanonical => (48, 96),
+        Encoding::Uncompressed => (96, 192),
+    };
+    if bytes.len() != G1 * g1_len + G2 * g2_len + trailing {
+        return Err(Error::DecodingFailed);
+    }
+
+    let (g1_bytes, rest) = bytes.split_at(G1 * g1_len);
+    let (g2_bytes, rest) = rest.split_at(G2 * g2_len);
+    let mut g1 = [G1Affine::identity(); G1];
+    let mut g2 = [G2Affine::identity(); G2];
+    for (point, encoded) in g1.iter_mut().zip(g1_bytes.chunks_exact(g1_len)) {
+        *point = decode_g1(encoded)?;
+    }
+    for (point, encoded) in g2.iter_mut().zip(g2_bytes.chunks_exact(g2_len)) {
+        *point = decode_g2(encoded)?;
+    }
+
+    Ok((g1, g2, rest))
+}
+
+impl PublicKey {
+    /// Serialize with the points in `format`; [Encoding::CompressedCanonical] is [PublicKey::to_bytes]
+    #[cfg(feature = "alloc")]
+    pub fn encode(&self, format: Encoding) -> Vec<u8> {
+        encode_points(
+            format,
+            &[self.g1, self.h1, self.u1, self.v1],
+            &[self.g2, self.h2, self.u2, self.v2, self.w2],
+        )
+    }
+
+    /// Deserialize from the encoding produced by [PublicKey::encode] with the same `format`
+    ///
+    /// Fails with [Error::DecodingFailed] unless `bytes` has the length of `format`, i.e. [PUBLIC_KEY_BYTES] or
+    /// [PUBLIC_KEY_UNCOMPRESSED_BYTES], with [Error::PointNotOnCurve] if any point is not on its curve or is not
+    /// encoded in `format`, with [Error::NotInSubgroup] if any point is outside its prime-order subgroup and with
+    /// [Error::InvalidPublicKey] if any point is the identity
+    pub fn decode(format: Encoding, bytes: &[u8]) -> Result<Self, Error> {
+        let ([g1, h1, u1, v1], [g2, h2, u2, v2, w2], _) = decode_points(format, bytes, 0)?;
+
+        Self {
+            g1,
+            h1,
+            u1,
+            v1,
+            g2,
+            h2,
+            u2,
+            v2,
+            w2,
+        }
+        .reject_identity()
+    }
+}
+
+impl Signature {
+    /// Serialize with the points in `format`; [Encoding::CompressedCanonical] is [Signature::to_bytes]
+    #[cfg(feature = "alloc")]
+    pub fn encode(&self, format: Encoding) -> Vec<u8> {
+        let mut bytes = encode_points(format, &[self.sigma], &[self.alpha]);
+        bytes.extend_from_slice(&self.beta.to_bytes());
+        bytes
+    }
+
+    /// Deserialize from the encoding produced by [Signature::encode] with the same `format`
+    ///
+    /// Fails with [Error::DecodingFailed] unless `bytes` has the length of `format`, i.e. [SIGNATURE_BYTES] or
+    /// [SIGNATURE_UNCOMPRESSED_BYTES], with [Error::PointNotOnCurve] if $\sigma$ or $\alpha$ is not on its curve or
+    /// is not encoded in `format`, with [Error::NotInSubgroup] if either is outside its prime-order subgroup and with
+    /// [Error::NonCanonicalScalar] if $\beta$ is not canonical
+    pub fn decode(format: Encoding, bytes: &[u8]) -> Result<Self, Error> {
+        let ([sigma], [alpha], beta) = decode_points(format, bytes, 32)?;
+        let beta = decode_scalar(beta)?;
+
+        Ok(Self { sigma, alpha, beta })
+    }
+}
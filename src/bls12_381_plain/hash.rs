@@ -0,0 +1,125 @@
+//! Hashing application byte strings to messages $m \in \mathbb{Z}_p$
+
+use super::{Commitment, PublicKey, COMMITMENT_BYTES, PUBLIC_KEY_BYTES};
+use bls12_381::Scalar;
+use sha2::{Digest, Sha256, Sha512};
+
+/// Domain separation tag for hashing $m_0$ (aka `info`) in [super::User::set_message_bytes] and
+/// [super::Signer::set_message_bytes]
+pub const INFO_DST: &[u8] = b"OKAMOTO-BLS12381-PLAIN-INFO";
+
+/// Domain separation tag for hashing $m_1$ (aka `message`) in [super::User::set_message_bytes]
+pub const MESSAGE_DST: &[u8] = b"OKAMOTO-BLS12381-PLAIN-MESSAGE";
+
+/// Domain separation tag for [fiat_shamir_challenge]
+pub const CHALLENGE_DST: &[u8] = b"OKAMOTO-BLS12381-PLAIN-CHALLENGE";
+
+/// Domain separation tag for the coefficients of [super::AggregateSignature::verify]
+pub const AGGREGATE_DST: &[u8] = b"OKAMOTO-BLS12381-PLAIN-AGGREGATE";
+
+/// Domain separation tag for the coefficients of [super::batch_verify_deterministic]
+pub const BATCH_DST: &[u8] = b"OKAMOTO-BLS12381-PLAIN-BATCH";
+
+/// Domain separation tag for [info_commitment]
+pub const INFO_COMMITMENT_DST: &[u8] = b"OKAMOTO-BLS12381-PLAIN-INFO-COMMITMENT";
+
+/// Domain separation tag for [super::verification_receipt]
+pub const RECEIPT_DST: &[u8] = b"OKAMOTO-BLS12381-PLAIN-RECEIPT";
+
+/// Hash a byte string to a [Scalar] under the domain separation tag `dst`
+///
+/// $H(dst, msg) \leftarrow \mathrm{SHA512}(|dst| \parallel dst \parallel msg) \mod p$
+///
+/// where $|dst|$ is the length of the tag as a big-endian `u64`. The 512-bit digest is reduced modulo $p$ so the
+/// result is statistically close to uniform. Different tags yield independent functions, so the [super::User] and
+/// the [super::Signer] must use the same tag to agree on a message.
+pub fn hash_to_scalar(dst: &[u8], msg: &[u8]) -> Scalar {
+    let mut hasher = ScalarHasher::new(dst);
+    hasher.update(msg);
+    hasher.finalize()
+}
+
+/// Derive $(m_0, m_1)$ from the two parts of one logical message under a single domain separation tag
+///
+/// $m_0 \leftarrow H(domain \parallel \mathtt{0x00}, info)$ and $m_1 \leftarrow H(domain \parallel \mathtt{0x01},
+/// message)$
+///
+/// The sub-domain tags differ, so equal `info_bytes` and `message_bytes` still give independent $m_0$ and $m_1$. Both
+/// parties derive $m_0$ the same way, the [super::Signer] discarding $m_1$.
+pub fn derive_messages(domain: &[u8], info_bytes: &[u8], message_bytes: &[u8]) -> (Scalar, Scalar) {
+    let mut info = ScalarHasher::with_sub_domain(domain, 0x00);
+    info.update(info_bytes);
+    let mut message = ScalarHasher::with_sub_domain(domain, 0x01);
+    message.update(message_bytes);
+
+    (info.finalize(), message.finalize())
+}
+
+/// [hash_to_scalar] over a message supplied in chunks, e.g. an `info` payload too large to buffer
+///
+/// Feeding the same bytes in any split yields the same [Scalar] as [hash_to_scalar] over their concatenation, so the
+/// [super::User] and the [super::Signer] can each stream the payload and agree on $m_0$.
+#[derive(Clone)]
+pub struct ScalarHasher {
+    hasher: Sha512,
+}
+
+impl ScalarHasher {
+    /// Start hashing under the domain separation tag `dst`
+    pub fn new(dst: &[u8]) -> Self {
+        let mut hasher = Sha512::new();
+        hasher.update((dst.len() as u64).to_be_bytes());
+        hasher.update(dst);
+        Self { hasher }
+    }
+
+    /// Start hashing under the tag `dst || sub_domain` without having to allocate it
+    fn with_sub_domain(dst: &[u8], sub_domain: u8) -> Self {
+        let mut hasher = Sha512::new();
+        hasher.update((dst.len() as u64 + 1).to_be_bytes());
+        hasher.update(dst);
+        hasher.update([sub_domain]);
+        Self { hasher }
+    }
+
+    /// Append the next `chunk` of the message
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.hasher.update(chunk);
+    }
+
+    /// Reduce the digest of everything passed to [ScalarHasher::update] to a [Scalar]
+    pub fn finalize(self) -> Scalar {
+        let mut wide = [0u8; 64];
+        wide.copy_from_slice(&self.hasher.finalize());
+
+        Scalar::from_bytes_wide(&wide)
+    }
+}
+
+/// Derive the challenge $\eta$ from the [Commitment] rather than sampling it
+///
+/// $\eta \leftarrow H(pk \parallel m_0 \parallel W \parallel X)$
+///
+/// Binds $\eta$ to the exact $(W, X)$ received, the [PublicKey] and $m_0$ so a proof cannot be replayed against a
+/// different commitment. Used by [super::Signer::new_fiat_shamir].
+pub fn fiat_shamir_challenge(public_key: &PublicKey, m0: &Scalar, commitment: &Commitment) -> Scalar {
+    let mut transcript = [0u8; PUBLIC_KEY_BYTES + 32 + COMMITMENT_BYTES];
+    transcript[..PUBLIC_KEY_BYTES].copy_from_slice(&public_key.to_bytes());
+    transcript[PUBLIC_KEY_BYTES..PUBLIC_KEY_BYTES + 32].copy_from_slice(&m0.to_bytes());
+    transcript[PUBLIC_KEY_BYTES + 32..].copy_from_slice(&commitment.to_bytes());
+
+    hash_to_scalar(CHALLENGE_DST, &transcript)
+}
+
+/// Commit to $m_0$ under a [PublicKey] for [super::User::info_commitment] and [super::Signer::check_info_commitment]
+///
+/// $\mathrm{SHA256}(|dst| \parallel dst \parallel \mathrm{fingerprint}(pk) \parallel m_0)$ under
+/// [INFO_COMMITMENT_DST], with $|dst|$ as in [hash_to_scalar]
+pub fn info_commitment(public_key: &PublicKey, m0: &Scalar) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update((INFO_COMMITMENT_DST.len() as u64).to_be_bytes());
+    hasher.update(INFO_COMMITMENT_DST);
+    hasher.update(public_key.fingerprint());
+    hasher.update(m0.to_bytes());
+    hasher.finalize().into()
+}
@@ -0,0 +1,49 @@
+//! Counting the state transitions of [Signer](super::Signer) and [User](super::User) with the `metrics` crate
+//!
+//! Every transition increments `okamoto_signer_state_transitions_total` or `okamoto_user_state_transitions_total`
+//! with the new state in the `state` label. A transition to `Aborted` also increments `okamoto_signer_aborts_total`
+//! or `okamoto_user_aborts_total` with the [AbortReason] in the `reason` label. Labels are fixed strings, so no
+//! protocol value reaches the recorder.
+
+use super::{AbortReason, Error, SignerState, UserState};
+
+pub(super) fn record_signer_transition(state: &SignerState) {
+    if let SignerState::Aborted(reason) = state {
+        ::metrics::counter!("okamoto_signer_aborts_total", "reason" => reason_label(reason)).increment(1);
+    }
+    ::metrics::counter!("okamoto_signer_state_transitions_total", "state" => state.name()).increment(1);
+}
+
+pub(super) fn record_user_transition(state: &UserState) {
+    if let UserState::Aborted(reason) = state {
+        ::metrics::counter!("okamoto_user_aborts_total", "reason" => reason_label(reason)).increment(1);
+    }
+    ::metrics::counter!("okamoto_user_state_transitions_total", "state" => state.name()).increment(1);
+}
+
+/// `UserRequested` or the name of the [Error] variant
+fn reason_label(reason: &AbortReason) -> &'static str {
+    match reason {
+        AbortReason::UserRequested => "UserRequested",
+        AbortReason::Error(error) => match error {
+            Error::WrongState { .. } => "WrongState",
+            Error::Unsupported => "Unsupported",
+            Error::InvalidSignature => "InvalidSignature",
+            Error::PointNotOnCurve => "PointNotOnCurve",
+            Error::NotInSubgroup => "NotInSubgroup",
+            Error::WitnessMismatch { .. } => "WitnessMismatch",
+            Error::ScalarIsZero => "ScalarIsZero",
+            Error::DecodingFailed => "DecodingFailed",
+            Error::NonCanonicalScalar => "NonCanonicalScalar",
+            Error::InvalidPublicKey => "InvalidPublicKey",
+            Error::MessageCountMismatch => "MessageCountMismatch",
+            Error::InconsistentKeyPair => "InconsistentKeyPair",
+            Error::ReplayDetected => "ReplayDetected",
+            Error::TransportFailed => "TransportFailed",
+            Error::DuplicateMessages => "DuplicateMessages",
+            Error::InfoMismatch => "InfoMismatch",
+            Error::SessionMismatch => "SessionMismatch",
+            Error::StaleCheckpoint => "StaleCheckpoint",
+        },
+    }
+}
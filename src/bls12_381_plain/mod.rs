@@ -2,17 +2,16 @@
 //!
 //! # Example
 //! ```rust
-//! #![allow(non_snake_case)]
 //! use bls12_381::Scalar;
 //! use ff::Field;
-//! use ziglet_okamoto::bls12_381_plain::{Error, KeyPair, Signer, User};
+//! use ziglet_okamoto::bls12_381_plain::{verify_signature, Error, KeyPair, Signature, Signer, User};
 //!
 //! fn happy_path() -> Result<(), Error> {
 //!     // Setup
 //!     let mut rng = rand_core::OsRng;
-//!     let key_pair = KeyPair::generate(rng.clone());
-//!     let mut user = User::new(&key_pair.public_key, rng.clone());
-//!     let mut signer = Signer::new(&key_pair, rng.clone());
+//!     let key_pair = KeyPair::generate(rng);
+//!     let mut user = User::new(&key_pair.public_key, rng);
+//!     let mut signer = Signer::new(&key_pair, rng);
 //!
 //!     // Step 0: out-of-band, the [User] and the [Signer] perform application logic necessary to agree on $m_0$
 //!     let m0 = Scalar::random(&mut rng);
@@ -24,17 +23,25 @@
 //!     signer.set_message(m0)?;
 //!
 //!     // Step 2: User generates a proof of commitment that is verified by Signer
-//!     let (W,X) = user.commit()?;
-//!     let eta = signer.commit(W,X)?;
+//!     let commitment = user.commit()?;
+//!     let eta = signer.commit(commitment)?;
 //!
 //!     // Step 3: User generates proof of knowledge of variables $s,t \in \mathbb{Z}_p^{*}$.
 //!     // Signer verifies the proof.
-//!     let (b1, b2, b3) = user.compute_witness(&eta)?;
-//!     signer.verify_witness(b1,b2,b3)?;
+//!     let witness = user.compute_witness(&eta)?;
+//!     signer.verify_witness(witness)?;
 //!
 //!     // Step 4: Signer send a partial signature to the User. User generates a completed signature.
-//!     let (Y,R,l) = signer.sign()?;
-//!     let (sigma, alpha, beta) = user.sign(&Y,&R,&l)?;
+//!     let partial = signer.sign()?;
+//!     let signature = user.sign(&partial)?;
+//!
+//!     // Verify: anyone holding the public key can check the signature on $(m_0, m_1)$
+//!     let Signature { sigma, alpha, beta } = signature;
+//!     verify_signature(&key_pair.public_key, &m0, &m1, &sigma, &alpha, &beta)?;
+//!
+//!     // A signature on a different message is rejected
+//!     let result = verify_signature(&key_pair.public_key, &m0, &(m1 + Scalar::one()), &sigma, &alpha, &beta);
+//!     assert!(matches!(result, Err(Error::InvalidSignature)));
 //!
 //!     Ok(())
 //! }
@@ -42,15 +49,211 @@
 //! happy_path().expect("successful completion");
 //! ```
 
+#[cfg(feature = "alloc")]
+use bls12_381::{multi_miller_loop, G2Prepared, Gt};
 use bls12_381::{G1Affine, G1Projective, G2Affine, G2Projective, Scalar};
+use core::fmt;
+pub use deterministic::{HmacDrbg, DETERMINISTIC_DST, KEYGEN_DST};
 use ff::Field;
-use rand_core::RngCore;
+use msm::msm4;
+use rand_core::{CryptoRng, RngCore};
+use sha2::{Digest, Sha256};
+pub use subtle::{Choice, ConstantTimeEq};
+pub use transcript::Transcript;
+use zeroize::Zeroize;
+
+#[cfg(feature = "alloc")]
+mod aggregate;
+#[cfg(feature = "std")]
+pub mod anonymous_token;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "alloc")]
+mod batch;
+mod blinding;
+mod builder;
+#[cfg(feature = "cache")]
+mod cache;
+#[cfg(feature = "alloc")]
+mod checkpoint;
+mod deterministic;
+mod encoding;
+mod format;
+mod hash;
+#[cfg(feature = "metrics")]
+mod metrics;
+mod msm;
+#[cfg(feature = "alloc")]
+mod multi;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod precompute;
+mod randomness;
+#[cfg(feature = "std")]
+mod replay;
+#[cfg(feature = "serde")]
+pub mod serialization;
+#[cfg(any(test, feature = "test-internals"))]
+pub mod test_util;
+mod transcript;
+#[cfg(feature = "async")]
+mod transport;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "alloc")]
+pub use aggregate::{aggregate, AggregateSignature};
+#[cfg(feature = "alloc")]
+pub use batch::{batch_verify, batch_verify_deterministic, BatchVerifier};
+pub use blinding::{BlindedMessage, BLINDED_MESSAGE_BYTES};
+pub use builder::PublicKeyBuilder;
+#[cfg(feature = "cache")]
+pub use cache::VerificationCache;
+#[cfg(feature = "base64")]
+pub use encoding::SecretKeyPem;
+pub use encoding::{PUBLIC_KEY_PEM_LABEL, SECRET_KEY_PEM_LABEL};
+pub use format::{Encoding, PUBLIC_KEY_UNCOMPRESSED_BYTES, SIGNATURE_UNCOMPRESSED_BYTES};
+pub use hash::{
+    derive_messages, fiat_shamir_challenge, hash_to_scalar, info_commitment, ScalarHasher, AGGREGATE_DST, BATCH_DST,
+    CHALLENGE_DST, INFO_COMMITMENT_DST, INFO_DST, MESSAGE_DST, RECEIPT_DST,
+};
+#[cfg(feature = "alloc")]
+pub use multi::{
+    verify_multi_signature, MultiMessageKeyPair, MultiMessagePublicKey, MultiMessageSigner, MultiMessageUser,
+    MultiMessageWitness,
+};
+#[cfg(feature = "rayon")]
+pub use parallel::verify_many;
+pub use precompute::PrecomputedCommitment;
+pub use randomness::RandomnessSource;
+#[cfg(feature = "std")]
+pub use replay::ReplayGuard;
+#[cfg(feature = "async")]
+pub use transport::{run_signer, run_user, Transport};
 
 pub type SecretKey = Scalar;
 
+/// Length of [PublicKey::to_bytes]: four compressed points in $\mathbb{G_1}$ and five in $\mathbb{G_2}$
+pub const PUBLIC_KEY_BYTES: usize = 4 * 48 + 5 * 96;
+
+/// Length of [VerifierKey::to_bytes]: one compressed point in $\mathbb{G_1}$ and five in $\mathbb{G_2}$
+pub const VERIFIER_KEY_BYTES: usize = 48 + 5 * 96;
+
+/// Length of the session id of [Signer::new_with_session]
+pub const SESSION_ID_BYTES: usize = 16;
+
+/// Length of [Commitment::to_bytes]: two compressed points in $\mathbb{G_1}$
+pub const COMMITMENT_BYTES: usize = 2 * 48;
+
+/// Length of [Challenge::to_bytes]: one scalar
+pub const CHALLENGE_BYTES: usize = 32;
+
+/// Length of [Witness::to_bytes]: three scalars
+pub const WITNESS_BYTES: usize = 3 * 32;
+
+/// Length of [PartialSignature::to_bytes]: compressed points in $\mathbb{G_1}$ and $\mathbb{G_2}$ and a scalar
+pub const PARTIAL_SIGNATURE_BYTES: usize = 48 + 96 + 32;
+
+/// Length of [Signature::to_bytes]: compressed points in $\mathbb{G_1}$ and $\mathbb{G_2}$ and a scalar
+pub const SIGNATURE_BYTES: usize = 48 + 96 + 32;
+
+/// Length of [Transcript::to_bytes]: the presence byte, a [Commitment], a [Challenge], a [Witness] and a
+/// [PartialSignature]
+pub const TRANSCRIPT_BYTES: usize = 1 + COMMITMENT_BYTES + CHALLENGE_BYTES + WITNESS_BYTES + PARTIAL_SIGNATURE_BYTES;
+
+/// The version byte that starts the envelope of [serialize_for_verification]
+pub const VERIFICATION_ENVELOPE_VERSION: u8 = 1;
+
+/// Length of [serialize_for_verification]: the version byte, a [PublicKey], $m_0$, $m_1$ and a [Signature]
+pub const VERIFICATION_ENVELOPE_BYTES: usize = 1 + PUBLIC_KEY_BYTES + 2 * 32 + SIGNATURE_BYTES;
+
+/// Decode a point in $\mathbb{G_1}$ from its 48-byte compressed or 96-byte uncompressed encoding
+///
+/// Fails with [Error::DecodingFailed] for any other length, with [Error::PointNotOnCurve] if the encoding is not that
+/// of a point on the curve and with [Error::NotInSubgroup] if the point is outside the prime-order subgroup. Every
+/// decoder of the crate maps its points through here.
+pub(crate) fn decode_g1(bytes: &[u8]) -> Result<G1Affine, Error> {
+    let decoded = if let Ok(compressed) = <&[u8; 48]>::try_from(bytes) {
+        G1Affine::from_compressed_unchecked(compressed)
+    } else if let Ok(uncompressed) = <&[u8; 96]>::try_from(bytes) {
+        G1Affine::from_uncompressed_unchecked(uncompressed)
+    } else {
+        return Err(Error::DecodingFailed);
+    };
+
+    let point: G1Affine = Option::from(decoded).ok_or(Error::PointNotOnCurve)?;
+    if !bool::from(point.is_on_curve()) {
+        return Err(Error::PointNotOnCurve);
+    }
+    if !bool::from(point.is_torsion_free()) {
+        return Err(Error::NotInSubgroup);
+    }
+
+    Ok(point)
+}
+
+/// Decode a point in $\mathbb{G_2}$ from its 96-byte compressed or 192-byte uncompressed encoding, as [decode_g1]
+pub(crate) fn decode_g2(bytes: &[u8]) -> Result<G2Affine, Error> {
+    let decoded = if let Ok(compressed) = <&[u8; 96]>::try_from(bytes) {
+        G2Affine::from_compressed_unchecked(compressed)
+    } else if let Ok(uncompressed) = <&[u8; 192]>::try_from(bytes) {
+        G2Affine::from_uncompressed_unchecked(uncompressed)
+    } else {
+        return Err(Error::DecodingFailed);
+    };
+
+    let point: G2Affine = Option::from(decoded).ok_or(Error::PointNotOnCurve)?;
+    if !bool::from(point.is_on_curve()) {
+        return Err(Error::PointNotOnCurve);
+    }
+    if !bool::from(point.is_torsion_free()) {
+        return Err(Error::NotInSubgroup);
+    }
+
+    Ok(point)
+}
+
+/// Decode a [Scalar] from its 32-byte little-endian encoding
+///
+/// Fails with [Error::DecodingFailed] for any other length and with [Error::NonCanonicalScalar] unless the value is
+/// below $p$.
+pub(crate) fn decode_scalar(bytes: &[u8]) -> Result<Scalar, Error> {
+    let bytes = <&[u8; 32]>::try_from(bytes).map_err(|_| Error::DecodingFailed)?;
+
+    Option::from(Scalar::from_bytes(bytes)).ok_or(Error::NonCanonicalScalar)
+}
+
+/// Stands in for secret values in the `Debug` output of [KeyPair], [Signer] and [User]
+struct Redacted;
+
+impl fmt::Debug for Redacted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<redacted>")
+    }
+}
+
+/// The 32-byte little-endian encoding of a [SecretKey] from [KeyPair::export_secret]
+///
+/// The bytes are overwritten with zeros when the value is dropped. It deliberately implements neither `Debug` nor
+/// `Clone` so the secret does not end up in logs or in stray copies.
+pub struct SecretKeyBytes([u8; 32]);
+
+impl SecretKeyBytes {
+    /// Borrow the encoding, e.g. to encrypt it for backup
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl Drop for SecretKeyBytes {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
 /// The public key for this signing protocol consists of several generators in $\mathbb{G_1}$ and
 /// matching generators for the pairing operation in $\mathbb{G_2}$.
-#[derive(Copy, Clone, Debug, PartialEq, Default)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
 pub struct PublicKey {
     pub g1: G1Affine,
     pub h1: G1Affine,
@@ -64,6 +267,270 @@ pub struct PublicKey {
     pub w2: G2Affine,
 }
 
+impl PublicKey {
+    /// Serialize as the compressed encodings of $g_1, h_1, u_1, v_1, g_2, h_2, u_2, v_2, w_2$ in that order
+    pub fn to_bytes(&self) -> [u8; PUBLIC_KEY_BYTES] {
+        let mut bytes = [0u8; PUBLIC_KEY_BYTES];
+        for (i, point) in [&self.g1, &self.h1, &self.u1, &self.v1].into_iter().enumerate() {
+            bytes[i * 48..(i + 1) * 48].copy_from_slice(&point.to_compressed());
+        }
+        for (i, point) in [&self.g2, &self.h2, &self.u2, &self.v2, &self.w2]
+            .into_iter()
+            .enumerate()
+        {
+            bytes[192 + i * 96..192 + (i + 1) * 96].copy_from_slice(&point.to_compressed());
+        }
+        bytes
+    }
+
+    /// Deserialize from the encoding produced by [PublicKey::to_bytes]
+    ///
+    /// Fails with [Error::DecodingFailed] unless `bytes` is [PUBLIC_KEY_BYTES] long, with [Error::PointNotOnCurve] or
+    /// [Error::NotInSubgroup] if any point is not a valid element of its group and with [Error::InvalidPublicKey] if
+    /// any point is the identity
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != PUBLIC_KEY_BYTES {
+            return Err(Error::DecodingFailed);
+        }
+
+        let mut g1 = [G1Affine::identity(); 4];
+        let mut g2 = [G2Affine::identity(); 5];
+
+        for (i, point) in g1.iter_mut().enumerate() {
+            *point = decode_g1(&bytes[i * 48..(i + 1) * 48])?;
+        }
+        for (i, point) in g2.iter_mut().enumerate() {
+            *point = decode_g2(&bytes[192 + i * 96..192 + (i + 1) * 96])?;
+        }
+
+        let [g1, h1, u1, v1] = g1;
+        let [g2, h2, u2, v2, w2] = g2;
+
+        Self {
+            g1,
+            h1,
+            u1,
+            v1,
+            g2,
+            h2,
+            u2,
+            v2,
+            w2,
+        }
+        .reject_identity()
+    }
+
+    /// Fail with [Error::InvalidPublicKey] if any point is the identity, which no generated key contains: an identity
+    /// generator removes a message from the signature equation and $w_2 = 1$ means the secret key $x = 0$
+    fn reject_identity(self) -> Result<Self, Error> {
+        let any_g1 = [&self.g1, &self.h1, &self.u1, &self.v1]
+            .iter()
+            .fold(Choice::from(0), |any, point| any | point.is_identity());
+        let any_g2 = [&self.g2, &self.h2, &self.u2, &self.v2, &self.w2]
+            .iter()
+            .fold(Choice::from(0), |any, point| any | point.is_identity());
+        if bool::from(any_g1 | any_g2) {
+            return Err(Error::InvalidPublicKey);
+        }
+
+        Ok(self)
+    }
+
+    /// Prepare the key for repeated use with [verify_signature_prepared]
+    ///
+    /// Checks that each generator in $\mathbb{G_1}$ and its counterpart in $\mathbb{G_2}$ share the same discrete
+    /// logarithm relative to $g_1$ and $g_2$, i.e. $e(h_1, g_2) = e(g_1, h_2)$ and likewise for $u$ and $v$. This
+    /// holds for every key produced by [KeyPair::generate] and lets the pairing equation be checked with the
+    /// message terms in $\mathbb{G_1}$ against the fixed, prepared $g_2$.
+    #[cfg(feature = "alloc")]
+    pub fn prepare(&self) -> Result<PreparedPublicKey, Error> {
+        let g2 = G2Prepared::from(self.g2);
+        let neg_g1 = -self.g1;
+
+        for (a1, a2) in [(&self.h1, self.h2), (&self.u1, self.u2), (&self.v1, self.v2)] {
+            let a2 = G2Prepared::from(a2);
+            if multi_miller_loop(&[(a1, &g2), (&neg_g1, &a2)]).final_exponentiation() != Gt::identity() {
+                return Err(Error::InvalidPublicKey);
+            }
+        }
+
+        Ok(PreparedPublicKey { public_key: *self, g2 })
+    }
+
+    /// Check that the key is usable for signing and verification
+    ///
+    /// Every point must be a valid element of its prime-order group other than the identity and each of $h_2, u_2,
+    /// v_2$ must have the same exponent relative to $g_2$ as its counterpart $h_1, u_1, v_1$ relative to $g_1$, i.e.
+    /// $e(a_1, g_2) = e(g_1, a_2)$. Fails with [Error::NotInSubgroup] if a point on the curve is outside the
+    /// prime-order subgroup and with [Error::InvalidPublicKey] otherwise.
+    pub fn validate(&self) -> Result<(), Error> {
+        let g1 = [&self.g1, &self.h1, &self.u1, &self.v1];
+        let g2 = [&self.g2, &self.h2, &self.u2, &self.v2, &self.w2];
+        let valid_g1 = g1
+            .iter()
+            .all(|point| bool::from(point.is_on_curve() & !point.is_identity()));
+        let valid_g2 = g2
+            .iter()
+            .all(|point| bool::from(point.is_on_curve() & !point.is_identity()));
+        if !valid_g1 || !valid_g2 {
+            return Err(Error::InvalidPublicKey);
+        }
+        let torsion_free = g1.iter().all(|point| bool::from(point.is_torsion_free()))
+            && g2.iter().all(|point| bool::from(point.is_torsion_free()));
+        if !torsion_free {
+            return Err(Error::NotInSubgroup);
+        }
+
+        for (a1, a2) in [(&self.h1, &self.h2), (&self.u1, &self.u2), (&self.v1, &self.v2)] {
+            if bls12_381::pairing(a1, &self.g2) != bls12_381::pairing(&self.g1, a2) {
+                return Err(Error::InvalidPublicKey);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// A short identifier of the key to compare against one obtained out of band, see [User::new_checked]
+    ///
+    /// The SHA-256 digest of [PublicKey::to_bytes].
+    pub fn fingerprint(&self) -> [u8; 32] {
+        Sha256::digest(self.to_bytes()).into()
+    }
+
+    /// The part of the key read by [verify_signature], for distribution to verifiers
+    pub fn verifier_key(&self) -> VerifierKey {
+        VerifierKey {
+            g1: self.g1,
+            g2: self.g2,
+            h2: self.h2,
+            u2: self.u2,
+            v2: self.v2,
+            w2: self.w2,
+        }
+    }
+
+    /// Combine with the key of another signer holding $x'$ over the same generators into the key for $x + x'$
+    ///
+    /// $w_2 \leftarrow w_2 \cdot w_2'$
+    ///
+    /// Fails with [Error::InvalidPublicKey] unless every generator other than $w_2$ matches. [KeyPair::generate]
+    /// derives the generators from the secret key, so the keys to combine must be set up over shared generators.
+    pub fn combine(&self, other: &PublicKey) -> Result<PublicKey, Error> {
+        let generators = |pk: &PublicKey| (pk.g1, pk.h1, pk.u1, pk.v1, pk.g2, pk.h2, pk.u2, pk.v2);
+        if generators(self) != generators(other) {
+            return Err(Error::InvalidPublicKey);
+        }
+
+        PublicKey {
+            w2: G2Affine::from(G2Projective::from(self.w2) + other.w2),
+            ..*self
+        }
+        .reject_identity()
+    }
+}
+
+/// Hashes the compressed encoding from [PublicKey::to_bytes] so that the hash is consistent with [PartialEq]
+impl core::hash::Hash for PublicKey {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.to_bytes().hash(state);
+    }
+}
+
+/// Compares the compressed encodings from [PublicKey::to_bytes] in constant time
+impl ConstantTimeEq for PublicKey {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.to_bytes().ct_eq(&other.to_bytes())
+    }
+}
+
+/// The generators of a [PublicKey] needed to verify signatures, created by [PublicKey::verifier_key]
+///
+/// Verification only pairs with $g_1$ in $\mathbb{G_1}$, so $h_1$, $u_1$ and $v_1$ are left out. Signatures are checked
+/// with [verify_signature_with_verifier_key].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct VerifierKey {
+    pub g1: G1Affine,
+    pub g2: G2Affine,
+    pub h2: G2Affine,
+    pub u2: G2Affine,
+    pub v2: G2Affine,
+    /// ${g_2}^{x}$
+    pub w2: G2Affine,
+}
+
+impl VerifierKey {
+    /// Serialize as the compressed encodings of $g_1, g_2, h_2, u_2, v_2, w_2$ in that order
+    pub fn to_bytes(&self) -> [u8; VERIFIER_KEY_BYTES] {
+        let mut bytes = [0u8; VERIFIER_KEY_BYTES];
+        bytes[..48].copy_from_slice(&self.g1.to_compressed());
+        for (i, point) in [&self.g2, &self.h2, &self.u2, &self.v2, &self.w2]
+            .into_iter()
+            .enumerate()
+        {
+            bytes[48 + i * 96..48 + (i + 1) * 96].copy_from_slice(&point.to_compressed());
+        }
+        bytes
+    }
+
+    /// Deserialize from the encoding produced by [VerifierKey::to_bytes]
+    ///
+    /// Fails with [Error::DecodingFailed] unless `bytes` is [VERIFIER_KEY_BYTES] long, with [Error::PointNotOnCurve]
+    /// or [Error::NotInSubgroup] if any point is not a valid element of its group and with [Error::InvalidPublicKey]
+    /// if any point is the identity
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != VERIFIER_KEY_BYTES {
+            return Err(Error::DecodingFailed);
+        }
+
+        let g1 = decode_g1(&bytes[..48])?;
+
+        let mut g2 = [G2Affine::identity(); 5];
+        for (i, point) in g2.iter_mut().enumerate() {
+            *point = decode_g2(&bytes[48 + i * 96..48 + (i + 1) * 96])?;
+        }
+        let [g2, h2, u2, v2, w2] = g2;
+
+        let any_g2 = [&g2, &h2, &u2, &v2, &w2]
+            .iter()
+            .fold(Choice::from(0), |any, point| any | point.is_identity());
+        if bool::from(g1.is_identity() | any_g2) {
+            return Err(Error::InvalidPublicKey);
+        }
+
+        Ok(Self { g1, g2, h2, u2, v2, w2 })
+    }
+}
+
+impl From<&PublicKey> for VerifierKey {
+    fn from(public_key: &PublicKey) -> Self {
+        public_key.verifier_key()
+    }
+}
+
+/// A [PublicKey] with $g_2$ prepared for the Miller loop, created by [PublicKey::prepare]
+///
+/// Amortizes the fixed part of [verify_signature_prepared] across many signatures verified against the same key.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct PreparedPublicKey {
+    public_key: PublicKey,
+    g2: G2Prepared,
+}
+
+#[cfg(feature = "alloc")]
+impl PreparedPublicKey {
+    /// The [PublicKey] this was prepared from
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+}
+
+/// How many secret keys [KeyPair::try_generate] draws before concluding the RNG is broken
+///
+/// A working RNG draws a zero [Scalar] with probability about $2^{-255}$, so a second zero in a row already points to a
+/// broken source.
+pub const KEY_GENERATION_ATTEMPTS: usize = 8;
+
 /// A pair of secret and public keys for the signing protocol
 pub struct KeyPair {
     pub public_key: PublicKey,
@@ -72,8 +539,153 @@ pub struct KeyPair {
 }
 
 impl KeyPair {
-    pub fn generate(mut rng: impl RngCore) -> KeyPair {
-        let secret_key: SecretKey = Scalar::random(&mut rng);
+    /// Generate a fresh [KeyPair]
+    ///
+    /// The secret key is drawn from `rng`, which must therefore be a [CryptoRng], redrawing a zero as for the
+    /// exponents of the generators, and the generators are derived from it (see [KeyPair::from_secret_bytes]). [Signer]
+    /// and [User] draw their nonces and blinding factors from a [RandomnessSource], which every [CryptoRng] is.
+    ///
+    /// ```compile_fail
+    /// use rand_core::{impls, RngCore};
+    /// use ziglet_okamoto::bls12_381_plain::KeyPair;
+    ///
+    /// // A counter is an RngCore but not a CryptoRng
+    /// struct Counter(u64);
+    ///
+    /// impl RngCore for Counter {
+    ///     fn next_u32(&mut self) -> u32 { self.next_u64() as u32 }
+    ///     fn next_u64(&mut self) -> u64 { self.0 += 1; self.0 }
+    ///     fn fill_bytes(&mut self, dest: &mut [u8]) { impls::fill_bytes_via_next(self, dest) }
+    ///     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+    ///         Ok(self.fill_bytes(dest))
+    ///     }
+    /// }
+    ///
+    /// KeyPair::generate(Counter(0));
+    /// ```
+    ///
+    /// # Panics
+    /// If `rng` yields a zero secret key [KEY_GENERATION_ATTEMPTS] times in a row (see [KeyPair::try_generate])
+    pub fn generate(rng: impl RngCore + CryptoRng) -> KeyPair {
+        Self::try_generate(rng).expect("the RNG yielded only zero secret keys")
+    }
+
+    /// Generate a fresh [KeyPair] as in [KeyPair::generate], surfacing a broken `rng` instead of panicking
+    ///
+    /// # Errors
+    /// - [Error::ScalarIsZero] if `rng` yields a zero secret key [KEY_GENERATION_ATTEMPTS] times in a row, which a
+    ///   working [CryptoRng] does with negligible probability
+    pub fn try_generate(rng: impl RngCore + CryptoRng) -> Result<KeyPair, Error> {
+        Ok(Self::from_secret_key(Self::draw_secret_key(rng)?, None))
+    }
+
+    /// Generate a fresh [KeyPair] whose generators are domain separated by `dst`
+    ///
+    /// The secret key is drawn from `rng` as in [KeyPair::generate], and the generators are derived from `dst` and
+    /// the secret key. Deployments with different tags therefore have disjoint generator sets: a [Signature] issued
+    /// under one tag does not verify under a key generated with another, even for the same secret key.
+    ///
+    /// The generators are not hashed to the curve with `dst` alone. Each of $h, u, v$ needs the same exponent in
+    /// $\mathbb{G_1}$ and $\mathbb{G_2}$ (see [PublicKeyBuilder]), and those exponents must remain unknown to
+    /// users: with them a single signature would yield signatures on any other message. Restore the key with
+    /// [KeyPair::from_secret_bytes_with_dst] and the same tag.
+    ///
+    /// # Panics
+    /// As [KeyPair::generate]
+    pub fn generate_with_dst(rng: impl RngCore + CryptoRng, dst: &[u8]) -> KeyPair {
+        let secret_key = Self::draw_secret_key(rng).expect("the RNG yielded only zero secret keys");
+        Self::from_secret_key(secret_key, Some(dst))
+    }
+
+    /// A uniformly random nonzero secret key; $x = 0$ would make $w_2$ the identity
+    ///
+    /// Gives up with [Error::ScalarIsZero] after [KEY_GENERATION_ATTEMPTS] zero draws.
+    pub(crate) fn draw_secret_key(mut rng: impl RngCore + CryptoRng) -> Result<SecretKey, Error> {
+        for _ in 0..KEY_GENERATION_ATTEMPTS {
+            let secret_key = Scalar::random(&mut rng);
+            if !bool::from(secret_key.is_zero()) {
+                return Ok(secret_key);
+            }
+        }
+        Err(Error::ScalarIsZero)
+    }
+
+    /// Restore a [KeyPair] from the encoding returned by [KeyPair::export_secret]
+    ///
+    /// Only the secret key is stored; the generators of the [PublicKey] are derived from it again.
+    ///
+    /// # Errors
+    /// - [Error::NonCanonicalScalar] if `bytes` is not a canonical [Scalar]
+    /// - [Error::ScalarIsZero] if the secret key is zero
+    pub fn from_secret_bytes(bytes: &[u8; 32]) -> Result<KeyPair, Error> {
+        let secret_key = decode_scalar(bytes)?;
+        if secret_key.is_zero().into() {
+            return Err(Error::ScalarIsZero);
+        }
+        Ok(Self::from_secret_key(secret_key, None))
+    }
+
+    /// Restore a [KeyPair] created by [KeyPair::generate_with_dst] from [KeyPair::export_secret] and its tag `dst`
+    ///
+    /// # Errors
+    /// As [KeyPair::from_secret_bytes]
+    pub fn from_secret_bytes_with_dst(bytes: &[u8; 32], dst: &[u8]) -> Result<KeyPair, Error> {
+        let secret_key = decode_scalar(bytes)?;
+        if secret_key.is_zero().into() {
+            return Err(Error::ScalarIsZero);
+        }
+        Ok(Self::from_secret_key(secret_key, Some(dst)))
+    }
+
+    /// Pair `secret_key` with a [PublicKey] over generators chosen elsewhere, whose $w_2$ must be ${g_2}^x$
+    #[cfg(feature = "bls12_381_crs")]
+    pub(crate) fn from_parts(secret_key: SecretKey, public_key: PublicKey) -> KeyPair {
+        KeyPair { public_key, secret_key }
+    }
+
+    /// Export the secret key, e.g. for an encrypted backup restored with [KeyPair::from_secret_bytes]
+    pub fn export_secret(&self) -> SecretKeyBytes {
+        SecretKeyBytes(self.secret_key.to_bytes())
+    }
+
+    /// Get the [PublicKey]
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    /// Check that the [PublicKey] belongs to the secret key, e.g. after restoring key material from a backup
+    ///
+    /// Confirms $w_2 = {g_2}^x$ and that each of $g_2, h_2, u_2, v_2$ has the same exponent as its counterpart
+    /// $g_1, h_1, u_1, v_1$, i.e. $e(a_1, g_2) = e(g_1, a_2)$. A [Signer] with an inconsistent key produces signatures
+    /// that never verify.
+    ///
+    /// # Errors
+    /// [Error::InconsistentKeyPair] if any relation does not hold
+    pub fn verify_consistency(&self) -> Result<(), Error> {
+        let pk = &self.public_key;
+
+        if pk.w2 != G2Affine::from(pk.g2 * self.secret_key) {
+            return Err(Error::InconsistentKeyPair);
+        }
+
+        for (a1, a2) in [(&pk.h1, &pk.h2), (&pk.u1, &pk.u2), (&pk.v1, &pk.v2)] {
+            if bls12_381::pairing(a1, &pk.g2) != bls12_381::pairing(&pk.g1, a2) {
+                return Err(Error::InconsistentKeyPair);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The exponents of the generators are drawn from an [HmacDrbg] seeded with [KEYGEN_DST], the length-prefixed
+    /// `dst` if any and the secret key, so a [KeyPair] is fully determined by its secret key and `dst`
+    fn from_secret_key(secret_key: SecretKey, dst: Option<&[u8]>) -> KeyPair {
+        let mut secret_bytes = secret_key.to_bytes();
+        let mut rng = match dst {
+            None => HmacDrbg::new(&[KEYGEN_DST, &secret_bytes]),
+            Some(dst) => HmacDrbg::new(&[KEYGEN_DST, &(dst.len() as u64).to_be_bytes(), dst, &secret_bytes]),
+        };
+        secret_bytes.zeroize();
 
         let mut public_key = PublicKey::default();
 
@@ -142,26 +754,313 @@ impl KeyPair {
         public_key.v2 = G2Affine::from(G2Projective::generator() * v1_r);
         public_key.w2 = G2Affine::from(public_key.g2 * secret_key);
 
-        let key_pair = KeyPair { secret_key, public_key };
+        KeyPair { secret_key, public_key }
+    }
+}
+
+/// Prints the [PublicKey] only, the secret key is redacted
+impl fmt::Debug for KeyPair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyPair")
+            .field("public_key", &self.public_key)
+            .field("secret_key", &Redacted)
+            .finish()
+    }
+}
+
+/// The [User]'s commitment $(W, X)$ sent to the [Signer] in Step 2
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Commitment {
+    /// $W \in \mathbb{G_1}$
+    pub w: G1Affine,
+    /// $X \in \mathbb{G_1}$
+    pub x: G1Affine,
+}
+
+impl Commitment {
+    /// Serialize as the compressed encodings of $W$ and $X$
+    pub fn to_bytes(&self) -> [u8; COMMITMENT_BYTES] {
+        let mut bytes = [0u8; COMMITMENT_BYTES];
+        bytes[0..48].copy_from_slice(&self.w.to_compressed());
+        bytes[48..96].copy_from_slice(&self.x.to_compressed());
+        bytes
+    }
+
+    /// Deserialize from the encoding produced by [Commitment::to_bytes]
+    ///
+    /// Fails with [Error::DecodingFailed] unless `bytes` is [COMMITMENT_BYTES] long and with
+    /// [Error::PointNotOnCurve] or [Error::NotInSubgroup] if either point is not a valid element of $\mathbb{G_1}$
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != COMMITMENT_BYTES {
+            return Err(Error::DecodingFailed);
+        }
+
+        let w = decode_g1(&bytes[0..48])?;
+        let x = decode_g1(&bytes[48..96])?;
+
+        Ok(Self { w, x })
+    }
+}
+
+/// The [Signer]'s challenge $\eta$ returned by [Signer::commit] in Step 2 and consumed by [User::compute_witness]
+///
+/// A distinct type from the [Scalar] messages so the two cannot be passed in place of each other.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Challenge(Scalar);
+
+impl Challenge {
+    /// The challenge $\eta$
+    pub fn eta(&self) -> &Scalar {
+        &self.0
+    }
+
+    /// Serialize as the little-endian encoding of $\eta$
+    pub fn to_bytes(&self) -> [u8; CHALLENGE_BYTES] {
+        self.0.to_bytes()
+    }
+
+    /// Deserialize from the encoding produced by [Challenge::to_bytes]
+    ///
+    /// Fails with [Error::DecodingFailed] unless `bytes` is [CHALLENGE_BYTES] long, with [Error::NonCanonicalScalar]
+    /// unless it is a canonical encoding and with [Error::ScalarIsZero] if $\eta = 0$, which is not in
+    /// $\mathbb{Z}_p^{*}$
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let eta = decode_scalar(bytes)?;
+        if eta.is_zero().into() {
+            return Err(Error::ScalarIsZero);
+        }
+
+        Ok(Self(eta))
+    }
+}
+
+impl From<Scalar> for Challenge {
+    fn from(eta: Scalar) -> Self {
+        Self(eta)
+    }
+}
+
+impl From<Challenge> for Scalar {
+    fn from(challenge: Challenge) -> Self {
+        challenge.0
+    }
+}
+
+/// The [User]'s witness $(b_1, b_2, b_3)$ sent to the [Signer] in Step 3
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Witness {
+    pub b1: Scalar,
+    pub b2: Scalar,
+    pub b3: Scalar,
+}
+
+impl Witness {
+    /// Serialize as the little-endian encodings of $b_1$, $b_2$ and $b_3$
+    pub fn to_bytes(&self) -> [u8; WITNESS_BYTES] {
+        let mut bytes = [0u8; WITNESS_BYTES];
+        bytes[0..32].copy_from_slice(&self.b1.to_bytes());
+        bytes[32..64].copy_from_slice(&self.b2.to_bytes());
+        bytes[64..96].copy_from_slice(&self.b3.to_bytes());
+        bytes
+    }
+
+    /// Deserialize from the encoding produced by [Witness::to_bytes]
+    ///
+    /// Fails with [Error::DecodingFailed] unless `bytes` is [WITNESS_BYTES] long and with [Error::NonCanonicalScalar]
+    /// unless every scalar is a canonical encoding
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != WITNESS_BYTES {
+            return Err(Error::DecodingFailed);
+        }
+
+        let b1 = decode_scalar(&bytes[0..32])?;
+        let b2 = decode_scalar(&bytes[32..64])?;
+        let b3 = decode_scalar(&bytes[64..96])?;
+
+        Ok(Self { b1, b2, b3 })
+    }
+}
+
+/// The [Signer]'s partial signature $(Y, R, l)$ sent to the [User] in Step 4
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PartialSignature {
+    /// $Y \in \mathbb{G_1}$
+    pub y: G1Affine,
+    /// $R \in \mathbb{G_2}$
+    pub r: G2Affine,
+    /// $l \in \mathbb{Z}_p$
+    pub l: Scalar,
+}
+
+impl PartialSignature {
+    /// Serialize as the compressed encodings of $Y$ and $R$ followed by the little-endian encoding of $l$
+    pub fn to_bytes(&self) -> [u8; PARTIAL_SIGNATURE_BYTES] {
+        let mut bytes = [0u8; PARTIAL_SIGNATURE_BYTES];
+        bytes[0..48].copy_from_slice(&self.y.to_compressed());
+        bytes[48..144].copy_from_slice(&self.r.to_compressed());
+        bytes[144..176].copy_from_slice(&self.l.to_bytes());
+        bytes
+    }
+
+    /// Deserialize from the encoding produced by [PartialSignature::to_bytes]
+    ///
+    /// Fails with [Error::DecodingFailed] unless `bytes` is [PARTIAL_SIGNATURE_BYTES] long, with
+    /// [Error::PointNotOnCurve] or [Error::NotInSubgroup] if $Y$ or $R$ is not a valid group element and with
+    /// [Error::NonCanonicalScalar] if $l$ is not a canonical encoding
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != PARTIAL_SIGNATURE_BYTES {
+            return Err(Error::DecodingFailed);
+        }
+
+        let y = decode_g1(&bytes[0..48])?;
+        let r = decode_g2(&bytes[48..144])?;
+        let l = decode_scalar(&bytes[144..176])?;
+
+        Ok(Self { y, r, l })
+    }
+}
+
+/// A completed signature $(\sigma, \alpha, \beta)$ on $(m_0, m_1)$ produced by [User::sign]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Signature {
+    /// $\sigma \in \mathbb{G_1}$
+    pub sigma: G1Affine,
+    /// $\alpha \in \mathbb{G_2}$
+    pub alpha: G2Affine,
+    /// $\beta \in \mathbb{Z}_p$
+    pub beta: Scalar,
+}
+
+impl Signature {
+    /// Serialize as the compressed encodings of $\sigma$ and $\alpha$ followed by the little-endian encoding of
+    /// $\beta$
+    pub fn to_bytes(&self) -> [u8; SIGNATURE_BYTES] {
+        let mut bytes = [0u8; SIGNATURE_BYTES];
+        bytes[0..48].copy_from_slice(&self.sigma.to_compressed());
+        bytes[48..144].copy_from_slice(&self.alpha.to_compressed());
+        bytes[144..176].copy_from_slice(&self.beta.to_bytes());
+        bytes
+    }
+
+    /// Deserialize from the encoding produced by [Signature::to_bytes]
+    ///
+    /// Fails with [Error::DecodingFailed] unless `bytes` is [SIGNATURE_BYTES] long, with [Error::PointNotOnCurve]
+    /// or [Error::NotInSubgroup] if $\sigma$ or $\alpha$ is not a valid group element and with
+    /// [Error::NonCanonicalScalar] if $\beta$ is not a canonical encoding
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != SIGNATURE_BYTES {
+            return Err(Error::DecodingFailed);
+        }
 
-        key_pair
+        let sigma = decode_g1(&bytes[0..48])?;
+        let alpha = decode_g2(&bytes[48..144])?;
+        let beta = decode_scalar(&bytes[144..176])?;
+
+        Ok(Self { sigma, alpha, beta })
     }
 }
 
-#[derive(Debug)]
+/// Compares the encodings from [Signature::to_bytes] in constant time
+///
+/// Prefer this to `==` when the comparison is on a security-sensitive path, such as checking a received signature
+/// against an expected one.
+impl ConstantTimeEq for Signature {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.to_bytes().ct_eq(&other.to_bytes())
+    }
+}
+
+/// Errors of the protocol steps, of verification and of decoding
+///
+/// A step of a [Signer] or [User] that fails with [Error::WrongState] was called out of order. It leaves the state
+/// unchanged, so the caller can go on with the step that is due. Any other error from a step is a protocol failure:
+/// the party moves to [SignerState::Aborted] or [UserState::Aborted] with the error as its [AbortReason] and has to
+/// be reset before the next flow.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Error {
-    /// A method was called in the incorrect state
-    InvalidState,
+    /// A step of a [Signer] or [User] was called in the `actual` state instead of the `expected` one, e.g.
+    /// [User::sign] before [User::compute_witness]
+    ///
+    /// Every state check of both parties, including the multi-message ones, fails with this error. A step allowed in
+    /// several states, such as [User::reset], names the state given in its documentation as `expected`.
+    WrongState {
+        /// The state the step requires
+        expected: StateKind,
+        /// The state the party was in
+        actual: StateKind,
+    },
+    /// The operation is not available for this party, e.g. [Signer::save_state] of a [Signer::new_deterministic]
+    Unsupported,
     /// A provided signature could not be validated given the [PublicKey]
     InvalidSignature,
     /// Given point is not on the curve
     PointNotOnCurve,
-    /// The given witness was invalid
-    InvalidWitness,
+    /// Given point is on the curve but not in the prime-order subgroup
+    NotInSubgroup,
+    /// The witness did not satisfy the proof equation of [verify_commitment_proof]
+    ///
+    /// With the `debug-errors` feature the compressed encodings of the two sides of the equation are included.
+    /// They are kept compressed so that enabling the feature does not bloat every `Result`.
+    WitnessMismatch {
+        /// $({h_1}^{m_0})^{b_2}{g_1}^{b_1}{u_1}^{b_2}{v_1}^{b_3}$
+        #[cfg(feature = "debug-errors")]
+        lhs: [u8; 48],
+        /// $WX^{\eta}$
+        #[cfg(feature = "debug-errors")]
+        rhs: [u8; 48],
+    },
     /// A given [Scalar] value was zero
     ScalarIsZero,
+    /// A byte encoding could not be decoded
+    DecodingFailed,
+    /// An encoded [Scalar] is not the unique little-endian encoding of a value below the field modulus
+    NonCanonicalScalar,
+    /// The [PublicKey] is malformed
+    InvalidPublicKey,
+    /// The number of messages does not match the number of generators of a multi-message key
+    MessageCountMismatch,
+    /// The [PublicKey] of a [KeyPair] does not match its secret key, see [KeyPair::verify_consistency]
+    InconsistentKeyPair,
+    /// The [Signer] reused $R = {g_2}^r$ from an earlier session, see [User::with_replay_guard]
+    ReplayDetected,
+    /// A `Transport` failed to send or receive a frame, see `run_user` and `run_signer`
+    TransportFailed,
+    /// $m_0 = m_1$ in [User::set_message], which would leave $m_1$ known to the [Signer]
+    DuplicateMessages,
+    /// The [User]'s $m_0$ differs from the [Signer]'s, see [Signer::check_info_commitment]
+    InfoMismatch,
+    /// A message of another session reached a [Signer] bound to a session, see [Signer::new_with_session]
+    SessionMismatch,
+    /// A checkpoint older than the latest [Signer::epoch] was passed to [Signer::restore_state]
+    StaleCheckpoint,
 }
 
+/// [SignerState::step] and [UserState::step] of an aborted flow, distinct from the steps 0 to 4
+pub const STEP_ABORTED: u8 = u8::MAX;
+
+/// A [SignerState] or [UserState] without the [AbortReason], as carried by [Error::WrongState]
+///
+/// An [AbortReason] holds an [Error], so an [Error] cannot hold the states themselves. The reason of an aborted party
+/// is available from its `get_state`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StateKind {
+    /// [SignerState::ReadyToSetMessage] or [UserState::ReadyToSetMessage]
+    ReadyToSetMessage,
+    /// [SignerState::ReadyToCommit] or [UserState::ReadyToCommit]
+    ReadyToCommit,
+    /// [UserState::ReadyToComputeWitness]
+    ReadyToComputeWitness,
+    /// [SignerState::ReadyToVerifyWitness]
+    ReadyToVerifyWitness,
+    /// [SignerState::ReadyToSign] or [UserState::ReadyToSign]
+    ReadyToSign,
+    /// [SignerState::Signed] or [UserState::Signed]
+    Signed,
+    /// [SignerState::Aborted] or [UserState::Aborted] for any [AbortReason]
+    Aborted,
+}
+
+#[derive(Clone, Debug)]
 pub enum SignerState {
     /// Step 1, ready to call [Signer::set_message]
     ReadyToSetMessage,
@@ -173,82 +1072,283 @@ pub enum SignerState {
     ReadyToSign,
     /// End, the message has been signed
     Signed,
-    /// An error occurred during the signing process
-    Aborted,
+    /// End, the signing process was aborted for the [AbortReason]
+    Aborted(AbortReason),
+}
+
+impl SignerState {
+    /// The [StateKind] of this state, as in [Error::WrongState]
+    pub fn kind(&self) -> StateKind {
+        match self {
+            SignerState::ReadyToSetMessage => StateKind::ReadyToSetMessage,
+            SignerState::ReadyToCommit => StateKind::ReadyToCommit,
+            SignerState::ReadyToVerifyWitness => StateKind::ReadyToVerifyWitness,
+            SignerState::ReadyToSign => StateKind::ReadyToSign,
+            SignerState::Signed => StateKind::Signed,
+            SignerState::Aborted(_) => StateKind::Aborted,
+        }
+    }
+
+    /// The name of the variant, e.g. `"ReadyToSign"`
+    pub fn name(&self) -> &'static str {
+        match self {
+            SignerState::ReadyToSetMessage => "ReadyToSetMessage",
+            SignerState::ReadyToCommit => "ReadyToCommit",
+            SignerState::ReadyToVerifyWitness => "ReadyToVerifyWitness",
+            SignerState::ReadyToSign => "ReadyToSign",
+            SignerState::Signed => "Signed",
+            SignerState::Aborted(_) => "Aborted",
+        }
+    }
+
+    /// The number of steps completed, from 0 in [SignerState::ReadyToSetMessage] to 4 in [SignerState::Signed], or
+    /// [STEP_ABORTED] in [SignerState::Aborted], e.g. for a progress bar
+    pub fn step(&self) -> u8 {
+        match self {
+            SignerState::ReadyToSetMessage => 0,
+            SignerState::ReadyToCommit => 1,
+            SignerState::ReadyToVerifyWitness => 2,
+            SignerState::ReadyToSign => 3,
+            SignerState::Signed => 4,
+            SignerState::Aborted(_) => STEP_ABORTED,
+        }
+    }
+
+    /// Whether the flow has ended in [SignerState::Signed] or [SignerState::Aborted]
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, SignerState::Signed | SignerState::Aborted(_))
+    }
+
+    /// Fail with [Error::WrongState] unless in the `expected` state, which is never [SignerState::Aborted]
+    fn expect(&self, expected: SignerState) -> Result<(), Error> {
+        if core::mem::discriminant(self) != core::mem::discriminant(&expected) {
+            return Err(self.unexpected(expected));
+        }
+
+        Ok(())
+    }
+
+    /// [Error::WrongState] for a step that requires `expected`, called in this state
+    fn unexpected(&self, expected: SignerState) -> Error {
+        Error::WrongState {
+            expected: expected.kind(),
+            actual: self.kind(),
+        }
+    }
+}
+
+/// Why a [Signer] or [User] ended in [SignerState::Aborted] or [UserState::Aborted]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AbortReason {
+    /// A step failed with the [Error], e.g. a witness that does not verify
+    Error(Error),
+    /// [Signer::abort] or [User::abort] was called
+    UserRequested,
 }
 
 /// Signer is a single, stateful interaction with a [User] to sign a shared message $m_0$ (aka info)
 /// and a blinded message $m_1$ (aka message).
 ///
-/// A Signer can be used for any number of [Signer::verify_signature] operations but can only be used for a single
-/// signing flow.
+/// A Signer can be used for any number of [Signer::verify_signature] operations but runs a single signing flow at a
+/// time. Once that flow has finished or been aborted, [Signer::reset] prepares it for the next one.
+///
+/// A Signer is [Clone] if `R` is, e.g. to try two continuations of a flow. **The clone duplicates the state of `rng`**,
+/// so both copies draw the same $\eta$, $r$ and $l$; signing with both reuses the nonces and leaks the secret key.
+/// Only clone before any randomness is drawn for the flow and let at most one copy continue.
 #[allow(non_snake_case)]
 #[allow(dead_code)]
-pub struct Signer<'a, R: RngCore> {
+#[derive(Clone)]
+pub struct Signer<'a, R: RandomnessSource> {
     key_pair: &'a KeyPair,
     rng: R,
     state: SignerState,
+    fiat_shamir: bool,
+    nonces: Option<HmacDrbg>,
+    session_id: Option<[u8; SESSION_ID_BYTES]>,
+    epoch: u64,
     m0: Scalar,
     W: G1Projective,
     X: G1Projective,
-    #[cfg(test)]
+    #[cfg(any(test, feature = "test-internals"))]
     l: Scalar,
-    #[cfg(test)]
+    #[cfg(any(test, feature = "test-internals"))]
     r: Scalar,
     eta: Scalar,
-    #[cfg(test)]
+    #[cfg(any(test, feature = "test-internals"))]
     b1: Scalar,
-    #[cfg(test)]
+    #[cfg(any(test, feature = "test-internals"))]
     b2: Scalar,
-    #[cfg(test)]
+    #[cfg(any(test, feature = "test-internals"))]
     b3: Scalar,
 }
 
-impl<'a, R: RngCore> Signer<'a, R> {
+/// Prints the [SignerState] and [PublicKey] only; the secret key and the nonces are redacted
+impl<'a, R: RandomnessSource> fmt::Debug for Signer<'a, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Signer")
+            .field("state", &self.state)
+            .field("public_key", &self.key_pair.public_key)
+            .field("secret_key", &Redacted)
+            .field("eta", &Redacted)
+            .field("r", &Redacted)
+            .field("l", &Redacted)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, R: RandomnessSource> Signer<'a, R> {
     /// Create a fresh [Signer] in the starting state given a [KeyPair]
     pub fn new(key_pair: &'a KeyPair, rng: R) -> Self {
         Self {
             key_pair,
             rng,
             state: SignerState::ReadyToSetMessage,
+            fiat_shamir: false,
+            nonces: None,
+            session_id: None,
+            epoch: 0,
             m0: Scalar::zero(),
             W: Default::default(),
             X: Default::default(),
-            #[cfg(test)]
+            #[cfg(any(test, feature = "test-internals"))]
             l: Default::default(),
-            #[cfg(test)]
+            #[cfg(any(test, feature = "test-internals"))]
             r: Default::default(),
             eta: Default::default(),
-            #[cfg(test)]
+            #[cfg(any(test, feature = "test-internals"))]
             b1: Default::default(),
-            #[cfg(test)]
+            #[cfg(any(test, feature = "test-internals"))]
             b2: Default::default(),
-            #[cfg(test)]
+            #[cfg(any(test, feature = "test-internals"))]
             b3: Default::default(),
         }
     }
 
+    /// Create a fresh [Signer] that derives the challenge $\eta$ with [fiat_shamir_challenge] in [Signer::commit]
+    /// instead of sampling it from `rng`
+    ///
+    /// $\eta$ is then bound to the [PublicKey], $m_0$ and the exact $(W, X)$ committed to, so the [User] can
+    /// recompute it and a witness cannot be replayed against a different commitment.
+    pub fn new_fiat_shamir(key_pair: &'a KeyPair, rng: R) -> Self {
+        Self {
+            fiat_shamir: true,
+            ..Self::new(key_pair, rng)
+        }
+    }
+
+    /// Create a fresh [Signer] bound to the session `session_id` of a multiplexed transport
+    ///
+    /// The [User]'s messages must then be passed to [Signer::commit_in_session] and
+    /// [Signer::verify_witness_in_session], or with the `fiat_shamir` feature to
+    /// `Signer::verify_non_interactive_in_session`, with the session id they arrived under. A message of another
+    /// session, or one passed to a method without an id, fails with [Error::SessionMismatch]
+    /// and aborts, so the [Signer] never signs a flow assembled from two [User]s' messages. The binding holds across
+    /// [Signer::reset].
+    pub fn new_with_session(key_pair: &'a KeyPair, rng: R, session_id: [u8; SESSION_ID_BYTES]) -> Self {
+        Self {
+            session_id: Some(session_id),
+            ..Self::new(key_pair, rng)
+        }
+    }
+
+    /// The session id given to [Signer::new_with_session], e.g. to stamp the frames sent to the [User]
+    pub fn session_id(&self) -> Option<&[u8; SESSION_ID_BYTES]> {
+        self.session_id.as_ref()
+    }
+
+    /// The number of state changes of this [Signer] so far, carried over by [Signer::save_state] and
+    /// [Signer::restore_state]
+    ///
+    /// It only grows, across [Signer::reset] too. Persist it after every step and pass the latest value to
+    /// [Signer::restore_state], which then rejects any checkpoint saved before it.
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
     /// Get the current [SignerState]
     pub fn get_state(&self) -> &SignerState {
         &self.state
     }
 
+    /// Get the shared message $m_0$ (aka `info`) given to [Signer::set_message]
+    ///
+    /// # Errors
+    /// [Error::WrongState] with `expected` [StateKind::ReadyToCommit] until [Signer::set_message]
+    pub fn info(&self) -> Result<Scalar, Error> {
+        match self.state {
+            SignerState::ReadyToSetMessage => Err(self.state.unexpected(SignerState::ReadyToCommit)),
+            _ => Ok(self.m0),
+        }
+    }
+
     /// Step 1. In the first stage of the negotiation, Signer and User agree on $m_0$ (aka `info`).
     /// The rules for agreement are up to the application.
     ///
-    /// $m_0 \in \mathbb{Z}_p^{*}$.
+    /// $m_0 \in \mathbb{Z}_p^{*}$: a zero $m_0$ fails with [Error::ScalarIsZero] and aborts, as in [User::set_message].
     ///
     /// It is up to the application to hash the byte array of the message to the finite field:
     ///
     /// $H: {0..1}^* \rightarrow \mathbb{Z}_p^{*}$
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Signer::set_message", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
     pub fn set_message(&mut self, m0: Scalar) -> Result<(), Error> {
-        match self.state {
-            SignerState::ReadyToSetMessage => {}
-            _ => return Err(Error::InvalidState),
+        self.state.expect(SignerState::ReadyToSetMessage)?;
+
+        if m0.is_zero().into() {
+            return Err(self.abort_with(Error::ScalarIsZero));
+        }
+
+        self.m0 = m0;
+        self.set_state(SignerState::ReadyToCommit);
+
+        Ok(())
+    }
+
+    /// Step 1. As [Signer::set_message] but derives $m_0$ from the `info` bytes with [hash_to_scalar] under
+    /// [INFO_DST], matching [User::set_message_bytes]
+    pub fn set_message_bytes(&mut self, info: &[u8]) -> Result<(), Error> {
+        self.set_message(hash_to_scalar(INFO_DST, info))
+    }
+
+    /// Check that the [User] set the same $m_0$, given their [User::info_commitment], before the expensive steps
+    ///
+    /// A different $m_0$ would otherwise only surface as [Error::WitnessMismatch] in [Signer::verify_witness]. The
+    /// [Signer] stays in [SignerState::ReadyToCommit] either way, so after a mismatch both sides can agree on $m_0$
+    /// again and apply it with [Signer::update_info] and [User::update_info], or abort.
+    ///
+    /// # Errors
+    /// - [Error::WrongState] unless in [SignerState::ReadyToCommit]
+    /// - [Error::InfoMismatch] if `commitment` is not [info_commitment] of the [Signer]'s $m_0$
+    pub fn check_info_commitment(&self, commitment: &[u8; 32]) -> Result<(), Error> {
+        self.state.expect(SignerState::ReadyToCommit)?;
+
+        if !bool::from(info_commitment(&self.key_pair.public_key, &self.m0).ct_eq(commitment)) {
+            return Err(Error::InfoMismatch);
+        }
+
+        Ok(())
+    }
+
+    /// Replace $m_0$ (aka `info`) after [Signer::set_message], e.g. when it is renegotiated before the [User] commits
+    ///
+    /// # Errors
+    /// - [Error::WrongState] unless the [Signer] is in [SignerState::ReadyToCommit]. Once the [Commitment] is
+    ///   received $m_0$ is bound into the proof, so a later change requires a new flow.
+    /// - [Error::ScalarIsZero] if `m0` is zero, which aborts as in [Signer::set_message]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Signer::update_info", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
+    pub fn update_info(&mut self, m0: Scalar) -> Result<(), Error> {
+        self.state.expect(SignerState::ReadyToCommit)?;
+
+        if m0.is_zero().into() {
+            return Err(self.abort_with(Error::ScalarIsZero));
         }
 
         self.m0 = m0;
-        self.state = SignerState::ReadyToCommit;
 
         Ok(())
     }
@@ -261,49 +1361,191 @@ impl<'a, R: RngCore> Signer<'a, R> {
     /// * Verify that $a1, a2, a3 \in \mathbb{Z}_p^{*}$
     /// * Store $W$ and $X$
     ///
+    /// $\eta \in \mathbb{Z}_p^{*}$ is resampled until nonzero, or derived with [fiat_shamir_challenge] if the
+    /// [Signer] was created with [Signer::new_fiat_shamir].
+    ///
     /// # Returns
-    /// $\eta$ a value used in the next step to prove to the [Signer] that she
+    /// The [Challenge] $\eta$ used in the next step to prove to the [Signer] that she
     /// knows $s,t \in \mathbb{Z}_p^{*}$
-    #[allow(non_snake_case)]
-    pub fn commit(&mut self, W: G1Affine, X: G1Affine) -> Result<&Scalar, Error> {
-        match self.state {
-            SignerState::ReadyToCommit => {}
-            _ => return Err(Error::InvalidState),
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Signer::commit", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
+    pub fn commit(&mut self, commitment: Commitment) -> Result<Challenge, Error> {
+        self.receive_commitment(None, &commitment, self.fiat_shamir)?;
+
+        Ok(Challenge(self.eta))
+    }
+
+    /// [Signer::commit] for a [Commitment] received under `session_id`, see [Signer::new_with_session]
+    ///
+    /// # Errors
+    /// [Error::SessionMismatch] unless `session_id` is the one of [Signer::new_with_session], which aborts, and the
+    /// errors of [Signer::commit]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Signer::commit_in_session", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
+    pub fn commit_in_session(
+        &mut self,
+        session_id: &[u8; SESSION_ID_BYTES],
+        commitment: Commitment,
+    ) -> Result<Challenge, Error> {
+        self.receive_commitment(Some(session_id), &commitment, self.fiat_shamir)?;
+
+        Ok(Challenge(self.eta))
+    }
+
+    /// Steps 2 and 3 at once for a [User] that derived $\eta$ itself in [User::commit_non_interactive]
+    ///
+    /// $\eta$ is recomputed with [fiat_shamir_challenge] from the received [Commitment], whatever the mode of this
+    /// [Signer], and the [Witness] is checked against it. On success the [Signer] is ready to [Signer::sign].
+    ///
+    /// A [Signer::new_with_session] takes the pair through [Signer::verify_non_interactive_in_session] instead.
+    #[cfg(feature = "fiat_shamir")]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Signer::verify_non_interactive", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
+    pub fn verify_non_interactive(&mut self, commitment: Commitment, witness: Witness) -> Result<(), Error> {
+        self.receive_commitment(None, &commitment, true)?;
+        self.receive_witness(None, witness)
+    }
+
+    /// [Signer::verify_non_interactive] for a [Commitment] and [Witness] received under `session_id`, see
+    /// [Signer::new_with_session]
+    ///
+    /// # Errors
+    /// [Error::SessionMismatch] unless `session_id` is the one of [Signer::new_with_session], which aborts, and the
+    /// errors of [Signer::verify_non_interactive]
+    #[cfg(feature = "fiat_shamir")]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Signer::verify_non_interactive_in_session", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
+    pub fn verify_non_interactive_in_session(
+        &mut self,
+        session_id: &[u8; SESSION_ID_BYTES],
+        commitment: Commitment,
+        witness: Witness,
+    ) -> Result<(), Error> {
+        self.receive_commitment(Some(session_id), &commitment, true)?;
+        self.receive_witness(Some(session_id), witness)
+    }
+
+    /// Check and store the [Commitment] received under `session_id` and set $\eta$, deriving it with
+    /// [fiat_shamir_challenge] if `fiat_shamir`
+    fn receive_commitment(
+        &mut self,
+        session_id: Option<&[u8; SESSION_ID_BYTES]>,
+        commitment: &Commitment,
+        fiat_shamir: bool,
+    ) -> Result<(), Error> {
+        self.state.expect(SignerState::ReadyToCommit)?;
+        self.check_session(session_id)?;
+
+        if !bool::from(commitment.w.is_on_curve()) || !bool::from(commitment.x.is_on_curve()) {
+            return Err(self.abort_with(Error::PointNotOnCurve));
         }
 
-        if !bool::from(W.is_on_curve()) || !bool::from(X.is_on_curve()) {
-            self.state = SignerState::Aborted;
-            return Err(Error::PointNotOnCurve);
+        if let Some(nonces) = &mut self.nonces {
+            nonces.reseed(&[
+                &self.m0.to_bytes(),
+                &commitment.w.to_compressed(),
+                &commitment.x.to_compressed(),
+            ]);
         }
 
-        self.eta = Scalar::random(&mut self.rng);
-        self.W = G1Projective::from(W);
-        self.X = G1Projective::from(X);
-        self.state = SignerState::ReadyToVerifyWitness;
+        self.eta = if fiat_shamir {
+            fiat_shamir_challenge(&self.key_pair.public_key, &self.m0, commitment)
+        } else if let Some(nonces) = &mut self.nonces {
+            nonces.scalar()
+        } else {
+            loop {
+                let eta = self.rng.next_scalar();
+                if !bool::from(eta.is_zero()) {
+                    break eta;
+                }
+            }
+        };
+
+        if self.eta.is_zero().into() {
+            return Err(self.abort_with(Error::ScalarIsZero));
+        }
+
+        self.W = G1Projective::from(commitment.w);
+        self.X = G1Projective::from(commitment.x);
+        self.set_state(SignerState::ReadyToVerifyWitness);
+
+        Ok(())
+    }
+
+    /// Abort with [Error::SessionMismatch] unless a message received under `session_id` belongs to this [Signer]
+    fn check_session(&mut self, session_id: Option<&[u8; SESSION_ID_BYTES]>) -> Result<(), Error> {
+        if self.session_id.as_ref() != session_id {
+            return Err(self.abort_with(Error::SessionMismatch));
+        }
 
-        Ok(&self.eta)
+        Ok(())
     }
 
     /// Step 3. Verify that the [User] has knowledge of $s,t \in \mathbb{Z}_p^{*}$
     ///
-    /// Verify that $({h_1}^{m_0})^{b_2}{g_1}^{b_1}{u_1}^{b_2}{v_1}^{b_3} = WX^{\eta}$
-    pub fn verify_witness(&mut self, b1: Scalar, b2: Scalar, b3: Scalar) -> Result<(), Error> {
-        match self.state {
-            SignerState::ReadyToVerifyWitness => {}
-            _ => return Err(Error::InvalidState),
-        }
+    /// See [verify_commitment_proof]
+    ///
+    /// # Errors
+    /// - [Error::WrongState] unless in [SignerState::ReadyToVerifyWitness]
+    /// - [Error::ScalarIsZero] if any of $b_1, b_2, b_3$ is zero and [Error::WitnessMismatch] if the proof does not
+    ///   hold, which abort
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Signer::verify_witness", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
+    pub fn verify_witness(&mut self, witness: Witness) -> Result<(), Error> {
+        self.receive_witness(None, witness)
+    }
 
-        let pk = &self.key_pair.public_key;
+    /// [Signer::verify_witness] for a [Witness] received under `session_id`, see [Signer::new_with_session]
+    ///
+    /// # Errors
+    /// [Error::SessionMismatch] unless `session_id` is the one of [Signer::new_with_session], which aborts, and the
+    /// errors of [Signer::verify_witness]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Signer::verify_witness_in_session", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
+    pub fn verify_witness_in_session(
+        &mut self,
+        session_id: &[u8; SESSION_ID_BYTES],
+        witness: Witness,
+    ) -> Result<(), Error> {
+        self.receive_witness(Some(session_id), witness)
+    }
 
-        let rhs = self.W + self.X * self.eta;
-        let lhs = pk.h1 * (self.m0 * b2) + pk.g1 * b1 + pk.u1 * b2 + pk.v1 * b3;
+    /// Check the [Witness] received under `session_id` against the stored [Commitment] and $\eta$
+    fn receive_witness(&mut self, session_id: Option<&[u8; SESSION_ID_BYTES]>, witness: Witness) -> Result<(), Error> {
+        self.state.expect(SignerState::ReadyToVerifyWitness)?;
+        self.check_session(session_id)?;
+
+        if let Err(error) = verify_commitment_proof(
+            &self.key_pair.public_key,
+            &self.m0,
+            &G1Affine::from(self.W),
+            &G1Affine::from(self.X),
+            &self.eta,
+            &witness,
+        ) {
+            return Err(self.abort_with(error));
+        }
 
-        if rhs != lhs {
-            self.state = SignerState::Aborted;
-            return Err(Error::InvalidWitness);
+        #[cfg(any(test, feature = "test-internals"))]
+        {
+            self.b1 = witness.b1;
+            self.b2 = witness.b2;
+            self.b3 = witness.b3;
         }
 
-        self.state = SignerState::ReadyToSign;
+        self.set_state(SignerState::ReadyToSign);
 
         Ok(())
     }
@@ -317,54 +1559,287 @@ impl<'a, R: RngCore> Signer<'a, R> {
     /// $l \leftarrow \mathbb{Z}_p^{*}$
     ///
     /// # Returns
-    /// $(Y, R, l)$
-    pub fn sign(&mut self) -> Result<(G1Affine, G2Affine, Scalar), Error> {
-        match self.state {
-            SignerState::ReadyToSign => {}
-            _ => return Err(Error::InvalidState),
-        }
+    /// The [PartialSignature] $(Y, R, l)$
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Signer::sign", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
+    pub fn sign(&mut self) -> Result<PartialSignature, Error> {
+        self.state.expect(SignerState::ReadyToSign)?;
+
+        let (r, l) = loop {
+            let (l, r) = match &mut self.nonces {
+                Some(nonces) => (nonces.scalar(), nonces.scalar()),
+                None => (self.rng.next_scalar(), self.rng.next_scalar()),
+            };
+            if !bool::from(l.is_zero() | r.is_zero() | (self.key_pair.secret_key + r).is_zero()) {
+                break (r, l);
+            }
+        };
 
-        let pk = &self.key_pair.public_key;
+        self.counter_sign(r, l)
+    }
 
-        let l = Scalar::random(&mut self.rng);
-        let r = Scalar::random(&mut self.rng);
+    /// Step 4. As [Signer::sign] but with the caller's $r$ and $l$, e.g. from a hardware module or for a test vector
+    ///
+    /// `r` and `l` must be drawn uniformly from $\mathbb{Z}_p^{*}$ for every flow, kept secret and never reused: a
+    /// [User] who knows or sees a repeated $r$ can compute further signatures under it.
+    ///
+    /// # Errors
+    /// - [Error::WrongState] unless in [SignerState::ReadyToSign]
+    /// - [Error::ScalarIsZero] if `r`, `l` or $x + r$ is zero, which aborts
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Signer::sign_with", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
+    pub fn sign_with(&mut self, r: Scalar, l: Scalar) -> Result<PartialSignature, Error> {
+        self.state.expect(SignerState::ReadyToSign)?;
+
+        self.counter_sign(r, l)
+    }
+
+    /// $(Y, R, l)$ for the given $r$ and $l$, moving to [SignerState::Signed]
+    fn counter_sign(&mut self, r: Scalar, l: Scalar) -> Result<PartialSignature, Error> {
+        if bool::from(r.is_zero() | l.is_zero()) {
+            return Err(self.abort_with(Error::ScalarIsZero));
+        }
+        let pk = &self.key_pair.public_key;
         #[allow(non_snake_case)]
-        let R = pk.g2 * r;
+        let Y = match compute_Y(pk, &self.key_pair.secret_key, &G1Affine::from(self.X), &l, &r) {
+            Ok(Y) => Y,
+            Err(error) => return Err(self.abort_with(error)),
+        };
         #[allow(non_snake_case)]
-        let Y = (self.X + (pk.v1 * l)) * (self.key_pair.secret_key + r).invert().unwrap();
+        let R = pk.g2 * r;
+
+        #[cfg(feature = "strict")]
+        assert_eq!(
+            bls12_381::pairing(&Y, &G2Affine::from(R + pk.w2)),
+            bls12_381::pairing(&G1Affine::from(self.X + pk.v1 * l), &pk.g2),
+            "strict: e(Y, w2 R) != e(X v1^l, g2)"
+        );
 
-        #[cfg(test)]
+        #[cfg(any(test, feature = "test-internals"))]
         {
             self.l = l;
             self.r = r;
         }
 
-        self.state = SignerState::Signed;
+        self.set_state(SignerState::Signed);
 
-        Ok((G1Affine::from(Y), G2Affine::from(R), l))
+        Ok(PartialSignature {
+            y: Y,
+            r: G2Affine::from(R),
+            l,
+        })
     }
 
     /// Abort the protocol preventing further use of the values
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Signer::abort", skip_all, fields(state = tracing::field::Empty))
+    )]
     pub fn abort(&mut self) {
-        self.state = SignerState::Aborted
+        self.set_state(SignerState::Aborted(AbortReason::UserRequested));
+    }
+
+    /// Abort because of `error`, which is returned for the caller to propagate
+    fn abort_with(&mut self, error: Error) -> Error {
+        self.set_state(SignerState::Aborted(AbortReason::Error(error)));
+        error
+    }
+
+    /// Move to `state`, recording it on the current step's span and in the transition counters
+    fn set_state(&mut self, state: SignerState) {
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("state", tracing::field::debug(&state));
+        #[cfg(feature = "metrics")]
+        metrics::record_signer_transition(&state);
+        self.epoch = self.epoch.saturating_add(1);
+        self.state = state;
+    }
+
+    /// Return to [SignerState::ReadyToSetMessage] for a new signing flow, keeping the [KeyPair] and `rng`
+    ///
+    /// # Errors
+    /// [Error::WrongState] with `expected` [StateKind::Signed] unless the current flow is [SignerState::Signed] or
+    /// [SignerState::Aborted] (or has not started). Resetting in the middle of a flow silently discards it, so it requires [Signer::force_reset].
+    pub fn reset(&mut self) -> Result<(), Error> {
+        match self.state {
+            SignerState::ReadyToSetMessage | SignerState::Signed | SignerState::Aborted(_) => {
+                self.force_reset();
+                Ok(())
+            }
+            _ => Err(self.state.unexpected(SignerState::Signed)),
+        }
+    }
+
+    /// Return to [SignerState::ReadyToSetMessage] from any state, discarding the current flow
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Signer::force_reset", skip_all, fields(state = tracing::field::Empty))
+    )]
+    pub fn force_reset(&mut self) {
+        self.set_state(SignerState::ReadyToSetMessage);
+        self.m0 = Scalar::zero();
+        self.W = G1Projective::identity();
+        self.X = G1Projective::identity();
+        self.eta = Scalar::zero();
+        #[cfg(any(test, feature = "test-internals"))]
+        {
+            self.l = Scalar::zero();
+            self.r = Scalar::zero();
+            self.b1 = Scalar::zero();
+            self.b2 = Scalar::zero();
+            self.b3 = Scalar::zero();
+        }
+    }
+}
+
+impl<'a> Signer<'a, HmacDrbg> {
+    /// Create a fresh [Signer] whose $\eta$, $r$ and $l$ are derived deterministically rather than sampled
+    ///
+    /// An [HmacDrbg] is seeded with the secret key and `seed_material` and, in [Signer::commit], reseeded with
+    /// $m_0$ and the received $(W, X)$. $\eta$ is drawn from it in [Signer::commit] and $l, r$ in [Signer::sign],
+    /// in the manner of RFC 6979. Signing is then reproducible and does not depend on a healthy entropy source.
+    ///
+    /// # Security
+    /// * The values are unpredictable only while the secret key is secret; they are as strong as the key and never
+    ///   stronger.
+    /// * The same key, `seed_material`, $m_0$ and $(W, X)$ yield the same $\eta$ and the same [PartialSignature].
+    ///   A [User] replaying a commitment learns nothing new, but `seed_material` should still be unique per session
+    ///   (e.g. a session identifier) so distinct sessions never share nonces.
+    /// * The secret key is used as HMAC key material on every run, which matters for side-channel exposure in
+    ///   environments where the RNG path would not touch it.
+    /// * Deterministic nonces are exposed to fault attacks: a glitch that changes $X$ after derivation yields two
+    ///   different partial signatures under the same $r$. Hardware that can be faulted should prefer [Signer::new].
+    ///
+    /// The `rng` of the returned [Signer] is never drawn from.
+    pub fn new_deterministic(key_pair: &'a KeyPair, seed_material: &[u8]) -> Self {
+        let nonces = HmacDrbg::new(&[DETERMINISTIC_DST, &key_pair.secret_key.to_bytes(), seed_material]);
+
+        Self {
+            nonces: Some(nonces.clone()),
+            ..Self::new(key_pair, nonces)
+        }
     }
 }
 
+/// Intermediate values for conformance and interoperability testing
+///
+/// **Enabling `test-internals` breaks the security of the protocol.** $r$ together with a signature reveals the
+/// secret key, so these accessors must never be compiled into a production signer.
+#[cfg(feature = "test-internals")]
+impl<'a, R: RandomnessSource> Signer<'a, R> {
+    /// $l$ drawn in [Signer::sign], zero before
+    pub fn debug_l(&self) -> Scalar {
+        self.l
+    }
+
+    /// $r$ drawn in [Signer::sign], zero before
+    pub fn debug_r(&self) -> Scalar {
+        self.r
+    }
+
+    /// $b_1$ accepted in [Signer::verify_witness], zero before
+    pub fn debug_b1(&self) -> Scalar {
+        self.b1
+    }
+
+    /// $b_2$ accepted in [Signer::verify_witness], zero before
+    pub fn debug_b2(&self) -> Scalar {
+        self.b2
+    }
+
+    /// $b_3$ accepted in [Signer::verify_witness], zero before
+    pub fn debug_b3(&self) -> Scalar {
+        self.b3
+    }
+}
+
+#[derive(Clone, Debug)]
 pub enum UserState {
     ReadyToSetMessage,
     ReadyToCommit,
     ReadyToComputeWitness,
     ReadyToSign,
     Signed,
-    Aborted,
+    Aborted(AbortReason),
+}
+
+impl UserState {
+    /// The [StateKind] of this state, as in [Error::WrongState]
+    pub fn kind(&self) -> StateKind {
+        match self {
+            UserState::ReadyToSetMessage => StateKind::ReadyToSetMessage,
+            UserState::ReadyToCommit => StateKind::ReadyToCommit,
+            UserState::ReadyToComputeWitness => StateKind::ReadyToComputeWitness,
+            UserState::ReadyToSign => StateKind::ReadyToSign,
+            UserState::Signed => StateKind::Signed,
+            UserState::Aborted(_) => StateKind::Aborted,
+        }
+    }
+
+    /// The name of the variant, e.g. `"ReadyToSign"`
+    pub fn name(&self) -> &'static str {
+        match self {
+            UserState::ReadyToSetMessage => "ReadyToSetMessage",
+            UserState::ReadyToCommit => "ReadyToCommit",
+            UserState::ReadyToComputeWitness => "ReadyToComputeWitness",
+            UserState::ReadyToSign => "ReadyToSign",
+            UserState::Signed => "Signed",
+            UserState::Aborted(_) => "Aborted",
+        }
+    }
+
+    /// The number of steps completed, from 0 in [UserState::ReadyToSetMessage] to 4 in [UserState::Signed], or
+    /// [STEP_ABORTED] in [UserState::Aborted], e.g. for a progress bar
+    pub fn step(&self) -> u8 {
+        match self {
+            UserState::ReadyToSetMessage => 0,
+            UserState::ReadyToCommit => 1,
+            UserState::ReadyToComputeWitness => 2,
+            UserState::ReadyToSign => 3,
+            UserState::Signed => 4,
+            UserState::Aborted(_) => STEP_ABORTED,
+        }
+    }
+
+    /// Whether the flow has ended in [UserState::Signed] or [UserState::Aborted]
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, UserState::Signed | UserState::Aborted(_))
+    }
+
+    /// Fail with [Error::WrongState] unless in the `expected` state, which is never [UserState::Aborted]
+    fn expect(&self, expected: UserState) -> Result<(), Error> {
+        if core::mem::discriminant(self) != core::mem::discriminant(&expected) {
+            return Err(self.unexpected(expected));
+        }
+
+        Ok(())
+    }
+
+    /// [Error::WrongState] for a step that requires `expected`, called in this state
+    fn unexpected(&self, expected: UserState) -> Error {
+        Error::WrongState {
+            expected: expected.kind(),
+            actual: self.kind(),
+        }
+    }
 }
 
 /// User is a single stateful interaction with a [Signer] to sign a shared message $m_0$ (aka `info`)
 /// and a blinded message $m_1$ (aka `message`).
 ///
-/// User can be used to verify any number of signatures but can be used to sign at most on message.
+/// User can be used to verify any number of signatures but signs at most one message per flow. Once that flow has
+/// finished or been aborted, [User::reset] prepares it for the next one.
+///
+/// A User is [Clone] if `R` is. **The clone duplicates the state of `rng`**, so both copies draw the same blinding
+/// factors and the [Signer] could link their signatures. Only clone before any randomness is drawn, i.e. before
+/// [User::commit], and let at most one copy finish.
 #[allow(non_snake_case)]
-pub struct User<'a, R: RngCore> {
+#[derive(Clone)]
+pub struct User<'a, R: RandomnessSource> {
     public_key: &'a PublicKey,
     state: UserState,
     rng: R,
@@ -373,18 +1848,31 @@ pub struct User<'a, R: RngCore> {
     a1: Scalar,
     a2: Scalar,
     a3: Scalar,
-    #[cfg(test)]
+    #[cfg(any(test, feature = "test-internals", feature = "blinding-factors"))]
     f: Scalar,
     s: Scalar,
     t: Scalar,
-    #[cfg(test)]
+    #[cfg(any(test, feature = "test-internals"))]
     W: G1Projective,
-    #[cfg(test)]
     X: G1Projective,
+    #[cfg(feature = "std")]
+    replay_guard: Option<&'a ReplayGuard>,
+}
+
+/// Prints the [UserState] and [PublicKey] only; the messages and blinding factors are redacted
+impl<'a, R: RandomnessSource> fmt::Debug for User<'a, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("User")
+            .field("state", &self.state)
+            .field("public_key", self.public_key)
+            .field("m1", &Redacted)
+            .field("blinding_factors", &Redacted)
+            .finish_non_exhaustive()
+    }
 }
 
 /// User is a stateful single instance of the User side of the (partially) blind signing protocol.
-impl<'a, R: RngCore> User<'a, R> {
+impl<'a, R: RandomnessSource> User<'a, R> {
     pub fn new(public_key: &'a PublicKey, rng: R) -> Self {
         Self {
             public_key,
@@ -395,35 +1883,143 @@ impl<'a, R: RngCore> User<'a, R> {
             a1: Default::default(),
             a2: Default::default(),
             a3: Default::default(),
-            #[cfg(test)]
+            #[cfg(any(test, feature = "test-internals", feature = "blinding-factors"))]
             f: Default::default(),
             s: Default::default(),
             t: Default::default(),
-            #[cfg(test)]
             X: Default::default(),
-            #[cfg(test)]
+            #[cfg(any(test, feature = "test-internals"))]
             W: Default::default(),
+            #[cfg(feature = "std")]
+            replay_guard: None,
+        }
+    }
+
+    /// Reject a [PartialSignature] in [User::sign] with [Error::ReplayDetected] if its $R$ is in `guard`, and record
+    /// it otherwise
+    ///
+    /// Share one [ReplayGuard] between every [User] that interacts with the same [Signer].
+    #[cfg(feature = "std")]
+    pub fn with_replay_guard(self, guard: &'a ReplayGuard) -> Self {
+        Self {
+            replay_guard: Some(guard),
+            ..self
+        }
+    }
+
+    /// Create a fresh [User] after checking that `public_key` has the [PublicKey::fingerprint] the application
+    /// trusts, e.g. one distributed out of band
+    ///
+    /// Fails with [Error::InvalidPublicKey] if the fingerprints differ.
+    pub fn new_checked(public_key: &'a PublicKey, expected_fingerprint: &[u8; 32], rng: R) -> Result<Self, Error> {
+        if &public_key.fingerprint() != expected_fingerprint {
+            return Err(Error::InvalidPublicKey);
         }
+        Ok(Self::new(public_key, rng))
     }
 
     pub fn get_state(&self) -> &UserState {
         &self.state
     }
 
-    /// Step 1. Commit to the values of $m_0$ and $m_1$
-    pub fn set_message(&mut self, m0: Scalar, m1: Scalar) -> Result<(), Error> {
+    /// Get the shared message $m_0$ (aka `info`) given to [User::set_message]
+    ///
+    /// # Errors
+    /// [Error::WrongState] with `expected` [StateKind::ReadyToCommit] until [User::set_message]
+    pub fn info(&self) -> Result<Scalar, Error> {
         match self.state {
-            UserState::ReadyToSetMessage => {}
-            _ => return Err(Error::InvalidState),
+            UserState::ReadyToSetMessage => Err(self.state.unexpected(UserState::ReadyToCommit)),
+            _ => Ok(self.m0),
         }
+    }
 
-        if m0.is_zero().into() || m1.is_zero().into() {
-            return Err(Error::ScalarIsZero);
+    /// Get the blinded message $m_1$ (aka `message`) given to [User::set_message]
+    ///
+    /// # Errors
+    /// As [User::info]
+    pub fn message(&self) -> Result<Scalar, Error> {
+        match self.state {
+            UserState::ReadyToSetMessage => Err(self.state.unexpected(UserState::ReadyToCommit)),
+            _ => Ok(self.m1),
         }
+    }
+
+    /// Step 1. Commit to the values of $m_0$ and $m_1$
+    ///
+    /// Fails with [Error::ScalarIsZero] and aborts if either message is zero, and with [Error::DuplicateMessages] if
+    /// $m_0 = m_1$. The [Signer] knows $m_0$, so an equal $m_1$ is not blind: the [Signer] can test a signature
+    /// against the $m_0$ of each flow it ran and link it to that flow's [User].
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "User::set_message", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
+    pub fn set_message(&mut self, m0: Scalar, m1: Scalar) -> Result<(), Error> {
+        self.state.expect(UserState::ReadyToSetMessage)?;
+
+        self.check_messages(&m0, &m1)?;
 
         self.m0 = m0;
         self.m1 = m1;
-        self.state = UserState::ReadyToCommit;
+        self.set_state(UserState::ReadyToCommit);
+
+        Ok(())
+    }
+
+    /// Abort unless $m_0$ and $m_1$ are nonzero and distinct
+    fn check_messages(&mut self, m0: &Scalar, m1: &Scalar) -> Result<(), Error> {
+        if m0.is_zero().into() || m1.is_zero().into() {
+            return Err(self.abort_with(Error::ScalarIsZero));
+        }
+
+        if m0 == m1 {
+            return Err(self.abort_with(Error::DuplicateMessages));
+        }
+
+        Ok(())
+    }
+
+    /// Step 1. As [User::set_message] but derives $m_0$ and $m_1$ from bytes with [hash_to_scalar] under
+    /// [INFO_DST] and [MESSAGE_DST] respectively, matching [Signer::set_message_bytes]
+    pub fn set_message_bytes(&mut self, info: &[u8], message: &[u8]) -> Result<(), Error> {
+        self.set_message(hash_to_scalar(INFO_DST, info), hash_to_scalar(MESSAGE_DST, message))
+    }
+
+    /// The [info_commitment] to $m_0$ for the [Signer] to compare with [Signer::check_info_commitment] before the
+    /// [Commitment] is sent
+    ///
+    /// The commitment is deterministic, so it only hides $m_0$ as far as $m_0$ cannot be guessed, which is fine
+    /// since the [Signer] learns $m_0$ anyway.
+    ///
+    /// # Errors
+    /// As [User::info]
+    pub fn info_commitment(&self) -> Result<[u8; 32], Error> {
+        Ok(info_commitment(self.public_key, &self.info()?))
+    }
+
+    /// Replace $m_0$ (aka `info`) after [User::set_message], keeping $m_1$, e.g. when it is renegotiated before
+    /// [User::commit]
+    ///
+    /// # Errors
+    /// - [Error::WrongState] unless the [User] is in [UserState::ReadyToCommit], as for [Signer::update_info]. The
+    ///   [Commitment] binds $m_0$, so a later change requires a new flow.
+    /// - [Error::ScalarIsZero] if `m0` is zero and [Error::DuplicateMessages] if it equals $m_1$, which abort as in
+    ///   [User::set_message]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "User::update_info", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
+    pub fn update_info(&mut self, m0: Scalar) -> Result<(), Error> {
+        self.state.expect(UserState::ReadyToCommit)?;
+
+        if m0.is_zero().into() {
+            return Err(self.abort_with(Error::ScalarIsZero));
+        }
+
+        if m0 == self.m1 {
+            return Err(self.abort_with(Error::DuplicateMessages));
+        }
+
+        self.m0 = m0;
 
         Ok(())
     }
@@ -437,39 +2033,64 @@ impl<'a, R: RngCore> User<'a, R> {
     ///
     ///
     /// # Returns
-    /// ($W$,$X$)
-    pub fn commit(&mut self) -> Result<(G1Affine, G1Affine), Error> {
-        match self.state {
-            UserState::ReadyToCommit => {}
-            _ => return Err(Error::InvalidState),
-        }
-
-        let a1 = Scalar::random(&mut self.rng);
-        let a2 = Scalar::random(&mut self.rng);
-        let a3 = Scalar::random(&mut self.rng);
-        let s = Scalar::random(&mut self.rng);
-        let t = Scalar::random(&mut self.rng);
+    /// The [Commitment] ($W$,$X$)
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "User::commit", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
+    pub fn commit(&mut self) -> Result<Commitment, Error> {
+        self.state.expect(UserState::ReadyToCommit)?;
+
+        let a1 = self.rng.next_scalar();
+        let a2 = self.rng.next_scalar();
+        let a3 = self.rng.next_scalar();
+        let s = self.rng.next_scalar();
+        let t = loop {
+            let t = self.rng.next_scalar();
+            if !bool::from(t.is_zero()) {
+                break t;
+            }
+        };
         let pk = &self.public_key;
         #[allow(non_snake_case)]
-        let X = pk.h1 * (self.m0 * t) + pk.g1 * (self.m1 * t) + pk.u1 * t + pk.v1 * (s * t);
+        let X = msm4(
+            [&pk.h1, &pk.g1, &pk.u1, &pk.v1],
+            [&(self.m0 * t), &(self.m1 * t), &t, &(s * t)],
+        );
         #[allow(non_snake_case)]
-        let W = pk.h1 * (self.m0 * a2) + pk.g1 * a1 + pk.u1 * a2 + pk.v1 * a3;
+        let W = msm4([&pk.h1, &pk.g1, &pk.u1, &pk.v1], [&(self.m0 * a2), &a1, &a2, &a3]);
+
+        Ok(self.finish_commit([a1, a2, a3, s, t], W, X))
+    }
 
-        #[cfg(test)]
+    /// Keep the blinding factors $a_1, a_2, a_3, s, t$ and $X$ for the next steps and move on
+    #[allow(non_snake_case)]
+    fn finish_commit(&mut self, [a1, a2, a3, s, t]: [Scalar; 5], W: G1Projective, X: G1Projective) -> Commitment {
+        #[cfg(feature = "strict")]
+        for (name, point) in [("W", W), ("X", X)] {
+            let point = G1Affine::from(point);
+            assert!(bool::from(point.is_on_curve()), "strict: {name} is not on the curve");
+            assert!(!bool::from(point.is_identity()), "strict: {name} is the identity");
+        }
+
+        #[cfg(any(test, feature = "test-internals"))]
         {
-            self.X = X.clone();
-            self.W = W.clone();
+            self.W = W;
         }
 
+        self.X = X;
         self.a1 = a1;
         self.a2 = a2;
         self.a3 = a3;
         self.t = t;
         self.s = s;
 
-        self.state = UserState::ReadyToComputeWitness;
+        self.set_state(UserState::ReadyToComputeWitness);
 
-        Ok((G1Affine::from(W), G1Affine::from(X)))
+        Commitment {
+            w: G1Affine::from(W),
+            x: G1Affine::from(X),
+        }
     }
 
     /// Step 3. Compute a witness that proves that the [User] knows values $s,t \in \mathbb{Z}_p^{*}$ that
@@ -483,55 +2104,320 @@ impl<'a, R: RngCore> User<'a, R> {
     ///
     /// $b_3 \leftarrow a_  + \eta{s}t \mod p$
     ///
+    /// A zero $\eta$ would let the [User] prove knowledge of values she does not know and is rejected.
+    ///
     /// # Returns
-    /// $b_1, b_2, b_3 \in \mathbb{Z}_p^{*}$
-    pub fn compute_witness(&mut self, eta: &Scalar) -> Result<(Scalar, Scalar, Scalar), Error> {
-        match self.state {
-            UserState::ReadyToComputeWitness => {}
-            _ => return Err(Error::InvalidState),
+    /// The [Witness] $b_1, b_2, b_3 \in \mathbb{Z}_p^{*}$
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "User::compute_witness", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
+    pub fn compute_witness(&mut self, challenge: &Challenge) -> Result<Witness, Error> {
+        self.state.expect(UserState::ReadyToComputeWitness)?;
+
+        let eta = challenge.eta();
+
+        if eta.is_zero().into() {
+            return Err(self.abort_with(Error::ScalarIsZero));
         }
 
         let b1 = self.a1 + eta * self.m1 * self.t;
         let b2 = self.a2 + eta * self.t;
         let b3 = self.a3 + eta * self.s * self.t;
 
-        self.state = UserState::ReadyToSign;
+        self.set_state(UserState::ReadyToSign);
+
+        Ok(Witness { b1, b2, b3 })
+    }
+
+    /// Steps 2 and 3 at once: commit and prove knowledge of the committed values without a challenge from the
+    /// [Signer]
+    ///
+    /// $\eta$ is derived from the [Commitment] with [fiat_shamir_challenge] and both messages are sent together to
+    /// [Signer::verify_non_interactive], saving a round trip over [User::commit] and [User::compute_witness].
+    #[cfg(feature = "fiat_shamir")]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "User::commit_non_interactive", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
+    pub fn commit_non_interactive(&mut self) -> Result<(Commitment, Witness), Error> {
+        let commitment = self.commit()?;
+        let eta = fiat_shamir_challenge(self.public_key, &self.m0, &commitment);
+        let witness = self.compute_witness(&Challenge(eta))?;
+
+        Ok((commitment, witness))
+    }
+
+    /// Step 4. Check that the [Signer] computed the [PartialSignature] honestly before finalizing it
+    ///
+    /// $e(Y, w_2R) = e(X{v_1}^{l}, g_2)$
+    ///
+    /// holds exactly when $Y = (X{v_1}^{l})^{1/(x+r)}$ and $R = {g_2}^{r}$, so a cheating [Signer] is detected here
+    /// rather than by a later failure of [verify_signature]. The [User] is left in [UserState::ReadyToSign] either
+    /// way.
+    ///
+    /// Fails with [Error::PointNotOnCurve] if $Y$ or $R$ is off the curve or the identity and with
+    /// [Error::InvalidSignature] if the pairing equation does not hold.
+    pub fn verify_partial(&self, partial: &PartialSignature) -> Result<(), Error> {
+        self.state.expect(UserState::ReadyToSign)?;
+
+        #[allow(non_snake_case)]
+        let PartialSignature { y: Y, r: R, l } = partial;
+
+        if !bool::from(partial_well_formed(partial)) {
+            return Err(Error::PointNotOnCurve);
+        }
+
+        let pk = &self.public_key;
+        let lhs = bls12_381::pairing(Y, &G2Affine::from(G2Projective::from(pk.w2) + R));
+        let rhs = bls12_381::pairing(&G1Affine::from(self.X + pk.v1 * l), &pk.g2);
+
+        if lhs != rhs {
+            return Err(Error::InvalidSignature);
+        }
 
-        Ok((b1, b2, b3))
+        Ok(())
     }
 
-    /// Step 4 (final). Compute the final signature $(\sigma, \alpha, \beta)$
+    /// Step 4 (final). Compute the final signature $(\sigma, \alpha, \beta)$ from the [Signer]'s
+    /// [PartialSignature] $(Y, R, l)$
+    ///
+    /// A malicious or faulty [Signer] could send points off the curve, or the identity which would make
+    /// $\alpha$ degenerate. Either aborts the protocol with [Error::PointNotOnCurve]. With a [ReplayGuard], see
+    /// [User::with_replay_guard], an $R$ seen before aborts it with [Error::ReplayDetected].
     ///
     /// # Returns
-    /// $(\sigma, \alpha, \beta)$
-    #[allow(non_snake_case)]
-    pub fn sign(&mut self, Y: &G1Affine, R: &G2Affine, l: &Scalar) -> Result<(G1Affine, G2Affine, Scalar), Error> {
-        match self.state {
-            UserState::ReadyToSign => {}
-            _ => return Err(Error::InvalidState),
+    /// The [Signature] $(\sigma, \alpha, \beta)$
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "User::sign", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
+    pub fn sign(&mut self, partial: &PartialSignature) -> Result<Signature, Error> {
+        self.state.expect(UserState::ReadyToSign)?;
+
+        self.check_and_finish_sign(partial)
+    }
+
+    /// The checks of [User::sign] on $(Y, R)$ followed by [User::finish_sign]
+    fn check_and_finish_sign(&mut self, partial: &PartialSignature) -> Result<Signature, Error> {
+        if !bool::from(partial_well_formed(partial)) {
+            return Err(self.abort_with(Error::PointNotOnCurve));
+        }
+
+        #[cfg(feature = "std")]
+        if let Some(guard) = self.replay_guard {
+            if !guard.record(&partial.r) {
+                return Err(self.abort_with(Error::ReplayDetected));
+            }
         }
 
+        Ok(self.finish_sign(partial))
+    }
+
+    /// Unblind $(Y, R, l)$ with a fresh $f$ into $(\sigma, \alpha, \beta)$ and move to [UserState::Signed]
+    ///
+    /// $\sigma \leftarrow Y^{1/(ft)}$, $\alpha \leftarrow {w_2}^{f-1}R^f$, $\beta \leftarrow s + l/t$
+    fn finish_sign(&mut self, partial: &PartialSignature) -> Signature {
+        #[allow(non_snake_case)]
+        let PartialSignature { y: Y, r: R, l } = partial;
         let pk = &self.public_key;
-        let f = Scalar::random(&mut self.rng);
-        let tau = (f * self.t).invert().unwrap();
+        // t is nonzero so any nonzero f makes f * t invertible, and 1/t = f * tau
+        let (f, tau) = loop {
+            let f = self.rng.next_scalar();
+            if let Some(tau) = Option::<Scalar>::from((f * self.t).invert()) {
+                break (f, tau);
+            }
+        };
         let sigma = Y * tau;
         let alpha = pk.w2 * (f - Scalar::one()) + (R * f);
-        let beta = self.s + l * self.t.invert().unwrap();
+        let beta = self.s + l * f * tau;
 
-        #[cfg(test)]
+        #[cfg(any(test, feature = "test-internals", feature = "blinding-factors"))]
         {
             self.f = f;
         }
 
-        self.state = UserState::Signed;
+        self.set_state(UserState::Signed);
 
-        Ok((G1Affine::from(sigma), G2Affine::from(alpha), beta))
+        Signature {
+            sigma: G1Affine::from(sigma),
+            alpha: G2Affine::from(alpha),
+            beta,
+        }
     }
 
     /// Abort the instance of the protocol preventing further use of the values
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "User::abort", skip_all, fields(state = tracing::field::Empty))
+    )]
     pub fn abort(&mut self) {
-        self.state = UserState::Aborted;
+        self.set_state(UserState::Aborted(AbortReason::UserRequested));
+    }
+
+    /// Abort because of `error`, which is returned for the caller to propagate
+    fn abort_with(&mut self, error: Error) -> Error {
+        self.set_state(UserState::Aborted(AbortReason::Error(error)));
+        error
+    }
+
+    /// Move to `state`, recording it on the current step's span and in the transition counters
+    fn set_state(&mut self, state: UserState) {
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("state", tracing::field::debug(&state));
+        #[cfg(feature = "metrics")]
+        metrics::record_user_transition(&state);
+        self.state = state;
+    }
+
+    /// Return to [UserState::ReadyToSetMessage] for a new signing flow, keeping the [PublicKey] and `rng`
+    ///
+    /// # Errors
+    /// [Error::WrongState] with `expected` [StateKind::Signed] unless the current flow is [UserState::Signed] or
+    /// [UserState::Aborted] (or has not started). Resetting in the middle of a flow silently discards it, so it requires [User::force_reset].
+    pub fn reset(&mut self) -> Result<(), Error> {
+        match self.state {
+            UserState::ReadyToSetMessage | UserState::Signed | UserState::Aborted(_) => {
+                self.force_reset();
+                Ok(())
+            }
+            _ => Err(self.state.unexpected(UserState::Signed)),
+        }
+    }
+
+    /// Return to [UserState::ReadyToSetMessage] from any state, discarding the current flow and its blinding
+    /// factors
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "User::force_reset", skip_all, fields(state = tracing::field::Empty))
+    )]
+    pub fn force_reset(&mut self) {
+        self.set_state(UserState::ReadyToSetMessage);
+        self.m0 = Scalar::zero();
+        self.m1 = Scalar::zero();
+        self.a1 = Scalar::zero();
+        self.a2 = Scalar::zero();
+        self.a3 = Scalar::zero();
+        self.s = Scalar::zero();
+        self.t = Scalar::zero();
+        self.X = G1Projective::identity();
+        #[cfg(any(test, feature = "test-internals", feature = "blinding-factors"))]
+        {
+            self.f = Scalar::zero();
+        }
+        #[cfg(any(test, feature = "test-internals"))]
+        {
+            self.W = G1Projective::identity();
+        }
+    }
+}
+
+/// The blinding factors of a signed [User] from [User::blinding_factors]
+///
+/// **These values break the blindness of the signature.** Together with the messages the [Signer] saw they link the
+/// [Signature] to its signing flow, so they must stay with the [User].
+#[cfg(feature = "blinding-factors")]
+pub struct BlindingFactors {
+    /// $s$ drawn in [User::commit], $\beta = s + l / t$
+    pub s: Scalar,
+    /// $t$ drawn in [User::commit], $X = (h_1^{m_0} g_1^{m_1} u_1 v_1^s)^t$
+    pub t: Scalar,
+    /// $f$ drawn in [User::sign], $\sigma = Y^{1 / (f t)}$ and $\alpha = w_2^{f - 1} R^f$
+    pub f: Scalar,
+}
+
+#[cfg(feature = "blinding-factors")]
+impl<'a, R: RandomnessSource> User<'a, R> {
+    /// Get the blinding factors $(s, t, f)$ once the [Signature] has been computed, e.g. to unblind auxiliary values
+    /// an application derived alongside it
+    ///
+    /// Requires the `blinding-factors` feature. See [BlindingFactors] for why they must not leave the [User].
+    ///
+    /// # Errors
+    /// [Error::WrongState] unless in [UserState::Signed]
+    pub fn blinding_factors(&self) -> Result<BlindingFactors, Error> {
+        self.state.expect(UserState::Signed)?;
+
+        Ok(BlindingFactors {
+            s: self.s,
+            t: self.t,
+            f: self.f,
+        })
+    }
+}
+
+/// Intermediate values for conformance and interoperability testing
+///
+/// **Enabling `test-internals` breaks the blindness of the protocol.** $f$ links the final signature to the
+/// messages the [Signer] saw, so these accessors must never be compiled into a production user.
+#[cfg(feature = "test-internals")]
+impl<'a, R: RandomnessSource> User<'a, R> {
+    /// $f$ drawn in [User::sign], zero before
+    pub fn debug_f(&self) -> Scalar {
+        self.f
+    }
+
+    /// $W$ computed in [User::commit], the identity before
+    pub fn debug_w(&self) -> G1Affine {
+        G1Affine::from(self.W)
+    }
+
+    /// $X$ computed in [User::commit], the identity before
+    pub fn debug_x(&self) -> G1Affine {
+        G1Affine::from(self.X)
+    }
+}
+
+/// A nonzero [Scalar] from a small integer, e.g. a readable message in a test vector
+///
+/// Messages, challenges and secret keys must all be nonzero, and this applies the same policy up front.
+///
+/// # Errors
+/// [Error::ScalarIsZero] if `v` is zero
+pub fn scalar_from_u64(v: u64) -> Result<Scalar, Error> {
+    match v {
+        0 => Err(Error::ScalarIsZero),
+        v => Ok(Scalar::from(v)),
+    }
+}
+
+/// Verify a recorded proof that the [User] knows the $s,t \in \mathbb{Z}_p^{*}$ committed to in $(W, X)$
+///
+/// This is the check performed by [Signer::verify_witness], exposed so that a transcript can be audited without a
+/// [Signer].
+///
+/// $({h_1}^{m_0})^{b_2}{g_1}^{b_1}{u_1}^{b_2}{v_1}^{b_3} = WX^{\eta}$
+///
+/// Fails with [Error::ScalarIsZero] if $\eta$ is zero, which would make any witness acceptable, or if any of
+/// $b_1, b_2, b_3$ is zero, which an honest [User] produces with negligible probability, and with
+/// [Error::WitnessMismatch] if the equation does not hold.
+pub fn verify_commitment_proof(
+    pk: &PublicKey,
+    m0: &Scalar,
+    w: &G1Affine,
+    x: &G1Affine,
+    eta: &Scalar,
+    witness: &Witness,
+) -> Result<(), Error> {
+    let Witness { b1, b2, b3 } = witness;
+
+    if eta.is_zero().into() || (b1.is_zero() | b2.is_zero() | b3.is_zero()).into() {
+        return Err(Error::ScalarIsZero);
+    }
+
+    let rhs = w + x * eta;
+    let lhs = msm4([&pk.h1, &pk.g1, &pk.u1, &pk.v1], [&(m0 * b2), b1, b2, b3]);
+
+    if rhs != lhs {
+        return Err(Error::WitnessMismatch {
+            #[cfg(feature = "debug-errors")]
+            lhs: G1Affine::from(lhs).to_compressed(),
+            #[cfg(feature = "debug-errors")]
+            rhs: G1Affine::from(rhs).to_compressed(),
+        });
     }
+
+    Ok(())
 }
 
 /// Verify that a signature is valid
@@ -545,7 +2431,13 @@ impl<'a, R: RngCore> User<'a, R> {
 ///
 /// * $\alpha \in \mathbb{G}_2$
 ///
-/// * $\beta \in \mathbb{Z}_p$
+/// * $\alpha \ne 1$ and $w_2\alpha \ne 1$. For $\alpha = {w_2}^{-1}$ the left-hand side below degenerates to
+///   $e(\sigma, 1) = 1$ whatever $\sigma$ is, and an honest $\alpha = {g_2}^{s}$ is neither but with negligible
+///   probability.
+///
+/// * $\beta \in \mathbb{Z}_p$, including zero: $\beta = s + l/t$ is zero when the [Signer] happens to draw
+///   $l = -st$, which is as likely as any other value. ${v_2}^{\beta}$ is then the identity and the equation below
+///   holds as for any other $\beta$.
 ///
 /// * $e(\sigma,w_2\alpha) = e(g_1,{h_2}^{m_0}{g_2}^{m_1}{u_2}{v_2}^{\beta})$
 pub fn verify_signature(
@@ -556,29 +2448,386 @@ pub fn verify_signature(
     alpha: &G2Affine,
     beta: &Scalar,
 ) -> Result<(), Error> {
-    let lhs2 = G2Affine::from(G2Projective::from(public_key.w2) + alpha);
-    let rhs2 = G2Affine::from(public_key.h2 * m0 + public_key.g2 * m1 + public_key.u2 + public_key.v2 * beta);
-    let lhs = bls12_381::pairing(&sigma, &lhs2);
-    let rhs = bls12_381::pairing(&public_key.g1, &rhs2);
+    verify_signature_points(
+        public_key,
+        &G2Affine::from(public_key.h2 * m0),
+        &G2Affine::from(public_key.g2 * m1),
+        &Signature {
+            sigma: *sigma,
+            alpha: *alpha,
+            beta: *beta,
+        },
+    )
+}
 
-    if sigma == &G1Affine::identity() {
+/// Verify that a signature is valid against a [VerifierKey]
+///
+/// Performs the same checks as [verify_signature], which only reads the generators kept in the [VerifierKey].
+pub fn verify_signature_with_verifier_key(
+    verifier_key: &VerifierKey,
+    m0: &Scalar,
+    m1: &Scalar,
+    signature: &Signature,
+) -> Result<(), Error> {
+    if !bool::from(verifier_key_verify_ct(verifier_key, m0, m1, signature)) {
         return Err(Error::InvalidSignature);
     }
 
-    if !bool::from(sigma.is_on_curve()) {
+    Ok(())
+}
+
+/// Verify that a signature is valid without branching on the outcome of any check
+///
+/// Performs the same checks as [verify_signature] but evaluates all of them, including the pairings, and folds
+/// them into a single [Choice] with constant-time operations. [verify_signature] branches on this [Choice] once.
+///
+/// The on-curve and identity checks of `bls12_381` are themselves constant time, but the arithmetic that follows
+/// still runs on whatever points were given, so the time of a rejected malformed signature is not guaranteed to
+/// match that of a rejected well formed one.
+pub fn verify_signature_ct(public_key: &PublicKey, m0: &Scalar, m1: &Scalar, signature: &Signature) -> Choice {
+    verifier_key_verify_ct(&public_key.verifier_key(), m0, m1, signature)
+}
+
+fn verifier_key_verify_ct(verifier_key: &VerifierKey, m0: &Scalar, m1: &Scalar, signature: &Signature) -> Choice {
+    verify_points_ct(verifier_key, verifier_key.h2 * m0, verifier_key.g2 * m1, signature)
+}
+
+/// The checks of [verifier_key_verify_ct] given ${h_2}^{m_0}$ and ${g_2}^{m_1}$
+fn verify_points_ct(
+    verifier_key: &VerifierKey,
+    h2_m0: G2Projective,
+    g2_m1: G2Projective,
+    signature: &Signature,
+) -> Choice {
+    let Signature { sigma, alpha, beta } = signature;
+
+    let lhs2 = G2Affine::from(G2Projective::from(verifier_key.w2) + alpha);
+    let rhs2 = G2Affine::from(h2_m0 + g2_m1 + verifier_key.u2 + verifier_key.v2 * beta);
+    let pairing_holds = bls12_381::pairing(sigma, &lhs2).ct_eq(&bls12_381::pairing(&verifier_key.g1, &rhs2));
+
+    well_formed(&verifier_key.w2, signature) & pairing_holds
+}
+
+/// The checks of [verify_signature] on the [Signature] itself, before any pairing
+///
+/// $\sigma$ must be on the curve and not the identity, $\alpha$ on the curve and neither the identity nor
+/// ${w_2}^{-1}$, which would make $w_2\alpha$ the identity.
+fn well_formed(w2: &G2Affine, signature: &Signature) -> Choice {
+    let Signature { sigma, alpha, .. } = signature;
+
+    !sigma.is_identity() & sigma.is_on_curve() & alpha.is_on_curve() & !alpha.is_identity() & !alpha.ct_eq(&-w2)
+}
+
+/// The checks of [User::sign] on the [PartialSignature] itself: $Y$ and $R$ must be on the curve and not the identity
+fn partial_well_formed(partial: &PartialSignature) -> Choice {
+    let PartialSignature { y, r, .. } = partial;
+
+    y.is_on_curve() & !y.is_identity() & r.is_on_curve() & !r.is_identity()
+}
+
+/// Verify a [Signature] on messages given as the group elements ${h_2}^{m_0}$ and ${g_2}^{m_1}$
+///
+/// Performs the same checks as [verify_signature], which computes the two points from $m_0$ and $m_1$ and calls
+/// this. A caller that already holds them, e.g. as commitments of a composed protocol or cached across many
+/// verifications, saves the two scalar multiplications. The points are taken as given and must be in
+/// $\mathbb{G_2}$, as they are when computed from scalars.
+///
+/// $e(\sigma,w_2\alpha) = e(g_1,{h_2}^{m_0}{g_2}^{m_1}{u_2}{v_2}^{\beta})$
+pub fn verify_signature_points(
+    pk: &PublicKey,
+    h2_m0: &G2Affine,
+    g2_m1: &G2Affine,
+    sig: &Signature,
+) -> Result<(), Error> {
+    if !bool::from(verify_points_ct(&pk.verifier_key(), h2_m0.into(), g2_m1.into(), sig)) {
         return Err(Error::InvalidSignature);
     }
 
-    if !bool::from(alpha.is_on_curve()) {
+    Ok(())
+}
+
+/// Find which of `candidates` a [Signature] was issued for
+///
+/// The checks of [verify_signature] on the signature itself and the pairing $e(\sigma, w_2\alpha)$ are done once,
+/// leaving a single pairing per candidate. Candidates are tried in order.
+///
+/// # Returns
+/// The index of the first $(m_0, m_1)$ in `candidates` the signature verifies for, `None` if there is none or the
+/// signature is malformed.
+pub fn find_matching_message(pk: &PublicKey, signature: &Signature, candidates: &[(Scalar, Scalar)]) -> Option<usize> {
+    let Signature { sigma, alpha, beta } = signature;
+
+    if !bool::from(well_formed(&pk.w2, signature)) {
+        return None;
+    }
+
+    let lhs = bls12_381::pairing(sigma, &G2Affine::from(G2Projective::from(pk.w2) + alpha));
+    let fixed = pk.u2 + pk.v2 * beta;
+
+    candidates.iter().position(|(m0, m1)| {
+        let rhs2 = G2Affine::from(pk.h2 * m0 + pk.g2 * m1 + fixed);
+        bls12_381::pairing(&pk.g1, &rhs2) == lhs
+    })
+}
+
+/// Find which of the currently valid `keys` a [Signature] was issued under, e.g. during a key rollover
+///
+/// Keys are tried in order with [verify_signature], stopping at the first that accepts, so list the key most
+/// signatures are expected under first.
+///
+/// # Returns
+/// The index in `keys` of the first [PublicKey] under which the signature verifies, or [Error::InvalidSignature] if
+/// there is none, including for an empty `keys`.
+pub fn verify_against_keys(keys: &[PublicKey], m0: &Scalar, m1: &Scalar, sig: &Signature) -> Result<usize, Error> {
+    keys.iter()
+        .position(|public_key| verify_signature(public_key, m0, m1, &sig.sigma, &sig.alpha, &sig.beta).is_ok())
+        .ok_or(Error::InvalidSignature)
+}
+
+/// Verify signatures that all share $m_0$, given as `(m1, signature)` pairs
+///
+/// Performs the checks of [verify_signature] on every signature. The part ${h_2}^{m_0}u_2$ of the right-hand side is
+/// computed once for the whole batch, leaving ${g_2}^{m_{1,i}}{v_2}^{\beta_i}$ per signature. Unlike
+/// batch verification each signature is checked on its own, so no randomness is needed.
+///
+/// # Errors
+/// [Error::InvalidSignature] for the first signature in `items` that is malformed or does not verify
+pub fn verify_common_info(pk: &PublicKey, m0: &Scalar, items: &[(Scalar, Signature)]) -> Result<(), Error> {
+    let common = pk.h2 * m0 + pk.u2;
+
+    for (m1, signature) in items {
+        let Signature { sigma, alpha, beta } = signature;
+        if !bool::from(well_formed(&pk.w2, signature)) {
+            return Err(Error::InvalidSignature);
+        }
+
+        let lhs2 = G2Affine::from(G2Projective::from(pk.w2) + alpha);
+        let rhs2 = G2Affine::from(common + pk.g2 * m1 + pk.v2 * beta);
+        if bls12_381::pairing(sigma, &lhs2) != bls12_381::pairing(&pk.g1, &rhs2) {
+            return Err(Error::InvalidSignature);
+        }
+    }
+
+    Ok(())
+}
+
+/// Verify a [Signature] on byte strings `info` and `message`, hashing them to $m_0$ and $m_1$ with [hash_to_scalar]
+///
+/// `info` is hashed under [INFO_DST] and `message` under [MESSAGE_DST], as by [User::set_message_bytes] and
+/// [Signer::set_message_bytes], so a signature issued from those verifies on the same bytes. Use [verify_signature]
+/// for messages that are already [Scalar]s.
+pub fn verify_signature_bytes(
+    public_key: &PublicKey,
+    info: &[u8],
+    message: &[u8],
+    signature: &Signature,
+) -> Result<(), Error> {
+    verify_signature(
+        public_key,
+        &hash_to_scalar(INFO_DST, info),
+        &hash_to_scalar(MESSAGE_DST, message),
+        &signature.sigma,
+        &signature.alpha,
+        &signature.beta,
+    )
+}
+
+/// Serialize everything [verify_serialized] needs into one envelope
+///
+/// [VERIFICATION_ENVELOPE_VERSION] followed by [PublicKey::to_bytes], the little-endian encodings of $m_0$ and
+/// $m_1$ and [Signature::to_bytes]
+pub fn serialize_for_verification(
+    public_key: &PublicKey,
+    m0: &Scalar,
+    m1: &Scalar,
+    signature: &Signature,
+) -> [u8; VERIFICATION_ENVELOPE_BYTES] {
+    let mut bytes = [0u8; VERIFICATION_ENVELOPE_BYTES];
+    let (version, rest) = bytes.split_at_mut(1);
+    let (public_key_bytes, rest) = rest.split_at_mut(PUBLIC_KEY_BYTES);
+    let (m0_bytes, rest) = rest.split_at_mut(32);
+    let (m1_bytes, signature_bytes) = rest.split_at_mut(32);
+
+    version[0] = VERIFICATION_ENVELOPE_VERSION;
+    public_key_bytes.copy_from_slice(&public_key.to_bytes());
+    m0_bytes.copy_from_slice(&m0.to_bytes());
+    m1_bytes.copy_from_slice(&m1.to_bytes());
+    signature_bytes.copy_from_slice(&signature.to_bytes());
+    bytes
+}
+
+/// Verify a signature from the envelope produced by [serialize_for_verification]
+///
+/// Fails with [Error::DecodingFailed] unless `blob` is [VERIFICATION_ENVELOPE_BYTES] long and starts with
+/// [VERIFICATION_ENVELOPE_VERSION]. The parts are decoded with [PublicKey::from_bytes] and [Signature::from_bytes],
+/// whose errors are returned as-is, and the signature is then checked with [verify_signature].
+pub fn verify_serialized(blob: &[u8]) -> Result<(), Error> {
+    if blob.len() != VERIFICATION_ENVELOPE_BYTES || blob[0] != VERIFICATION_ENVELOPE_VERSION {
+        return Err(Error::DecodingFailed);
+    }
+
+    let (public_key, rest) = blob[1..].split_at(PUBLIC_KEY_BYTES);
+    let (m0, rest) = rest.split_at(32);
+    let (m1, signature) = rest.split_at(32);
+
+    let public_key = PublicKey::from_bytes(public_key)?;
+    let (m0, m1) = (decode_scalar(m0)?, decode_scalar(m1)?);
+    let signature = Signature::from_bytes(signature)?;
+
+    verify_signature(
+        &public_key,
+        &m0,
+        &m1,
+        &signature.sigma,
+        &signature.alpha,
+        &signature.beta,
+    )
+}
+
+/// Verify a [Signature] and return a receipt recording which inputs passed, e.g. for an audit log
+///
+/// $\mathrm{SHA256}(|dst| \parallel dst \parallel \mathrm{envelope})$ under [RECEIPT_DST], with $|dst|$ as in
+/// [hash_to_scalar] and the envelope of [serialize_for_verification]. The receipt is 32 bytes however large the
+/// inputs, and an auditor holding them recomputes it to confirm what was checked; any change to the key, a message
+/// or the signature changes it.
+///
+/// # Errors
+/// The error of [verify_signature] if the signature does not verify, in which case no receipt is issued
+pub fn verification_receipt(pk: &PublicKey, m0: &Scalar, m1: &Scalar, sig: &Signature) -> Result<[u8; 32], Error> {
+    verify_signature(pk, m0, m1, &sig.sigma, &sig.alpha, &sig.beta)?;
+
+    let mut hasher = Sha256::new();
+    hasher.update((RECEIPT_DST.len() as u64).to_be_bytes());
+    hasher.update(RECEIPT_DST);
+    hasher.update(serialize_for_verification(pk, m0, m1, sig));
+
+    Ok(hasher.finalize().into())
+}
+
+/// The verification equation $e(\sigma, w_2\alpha) = e(g_1, {h_2}^{m_0}{g_2}^{m_1}{u_2}{v_2}^{\beta})$ alone
+///
+/// No other check is made: [verify_signature] additionally rejects an identity $\sigma$, an $\alpha$ that makes
+/// $w_2\alpha$ or itself the identity and points that are not on the curve, and should be preferred unless the
+/// signature is already known to be well formed.
+pub fn pairing_holds(pk: &PublicKey, m0: &Scalar, m1: &Scalar, signature: &Signature) -> bool {
+    verifier_key_pairing_holds(&pk.verifier_key(), m0, m1, signature)
+}
+
+fn verifier_key_pairing_holds(pk: &VerifierKey, m0: &Scalar, m1: &Scalar, signature: &Signature) -> bool {
+    let lhs2 = G2Affine::from(G2Projective::from(pk.w2) + signature.alpha);
+    let rhs2 = G2Affine::from(pk.h2 * m0 + pk.g2 * m1 + pk.u2 + pk.v2 * signature.beta);
+
+    bls12_381::pairing(&signature.sigma, &lhs2) == bls12_381::pairing(&pk.g1, &rhs2)
+}
+
+/// Verify that a signature is valid using a [PreparedPublicKey]
+///
+/// Performs the same checks as [verify_signature]. Because the prepared key's generators are known to be
+/// consistent, the pairing equation is evaluated in the equivalent form
+///
+/// $e(\sigma,w_2\alpha) \cdot e({h_1}^{m_0}{g_1}^{m_1}{u_1}{v_1}^{\beta}, g_2)^{-1} = 1$
+///
+/// with a single final exponentiation and the message terms computed in $\mathbb{G_1}$.
+#[cfg(feature = "alloc")]
+pub fn verify_signature_prepared(
+    prepared: &PreparedPublicKey,
+    m0: &Scalar,
+    m1: &Scalar,
+    signature: &Signature,
+) -> Result<(), Error> {
+    let pk = &prepared.public_key;
+    let Signature { sigma, alpha, beta } = signature;
+
+    if !bool::from(well_formed(&pk.w2, signature)) {
         return Err(Error::InvalidSignature);
     }
 
-    if lhs != rhs {
+    let lhs2 = G2Prepared::from(G2Affine::from(G2Projective::from(pk.w2) + alpha));
+    let rhs1 = G1Affine::from(-(pk.h1 * m0 + pk.g1 * m1 + pk.u1 + pk.v1 * beta));
+
+    if multi_miller_loop(&[(sigma, &lhs2), (&rhs1, &prepared.g2)]).final_exponentiation() != Gt::identity() {
         return Err(Error::InvalidSignature);
     }
 
     Ok(())
 }
 
+/// The $Y$ of the [PartialSignature] that [Signer::sign] returns for the [Commitment]'s $X$ under the secret key $x$
+///
+/// $Y \leftarrow (X{v_1}^{l})^{1/(x+r)}$
+///
+/// Exposed so that another implementation of the [Signer] can be checked against this one on the same inputs; a
+/// [Signer] computes it as part of [Signer::sign] and [Signer::sign_with].
+///
+/// # Errors
+/// [Error::ScalarIsZero] if $x + r$ is zero and so has no inverse
+#[allow(non_snake_case)]
+pub fn compute_Y(pk: &PublicKey, x: &SecretKey, x_point: &G1Affine, l: &Scalar, r: &Scalar) -> Result<G1Affine, Error> {
+    let inverse = Option::<Scalar>::from((x + r).invert()).ok_or(Error::ScalarIsZero)?;
+
+    Ok(G1Affine::from((x_point + pk.v1 * l) * inverse))
+}
+
+/// Partially re-randomize a [Signature]: $(\sigma, \alpha)$ change, $\beta$ does not
+///
+/// Applies the same transformation as [User::sign] with a fresh $f \in \mathbb{Z}_p^{*}$:
+///
+/// $\sigma' \leftarrow \sigma^{1/f}$
+///
+/// $\alpha' \leftarrow {w_2}^{f-1}\alpha^{f}$
+///
+/// so that $w_2\alpha' = (w_2\alpha)^f$ and the pairing equation of [verify_signature] is unchanged.
+///
+/// $\beta$ is bound to $\sigma$ through the exponent $1/(x+r)$ known only to the [Signer] and so is kept as is. The
+/// result is therefore linkable to the original: anyone who sees both signatures can match them by $\beta$. Use it
+/// to obtain another valid encoding of a signature, not to unlink a shown signature from an earlier showing.
+pub fn rerandomize_partially(pk: &PublicKey, sig: &Signature, mut rng: impl RngCore + CryptoRng) -> Signature {
+    let (f, f_inv) = loop {
+        let f = Scalar::random(&mut rng);
+        if let Some(f_inv) = Option::<Scalar>::from(f.invert()) {
+            break (f, f_inv);
+        }
+    };
+
+    let sigma = sig.sigma * f_inv;
+    let alpha = pk.w2 * (f - Scalar::one()) + sig.alpha * f;
+
+    Signature {
+        sigma: G1Affine::from(sigma),
+        alpha: G2Affine::from(alpha),
+        beta: sig.beta,
+    }
+}
+
+/// Run the full interactive protocol in-process, acting as both the [User] and the [Signer]
+///
+/// This is a convenience for tests and for applications where both roles are local. The [User] draws from
+/// `user_rng` and the [Signer] from `signer_rng`, which must be independent: two deterministic generators with the
+/// same seed, e.g. clones of one [HmacDrbg], would give the [User] the [Signer]'s $r$ and $l$. Any [Error] from an
+/// intermediate step is returned as-is.
+///
+/// # Returns
+/// The [Signature] on $(m_0, m_1)$
+pub fn sign_in_process(
+    key_pair: &KeyPair,
+    m0: Scalar,
+    m1: Scalar,
+    user_rng: impl RandomnessSource,
+    signer_rng: impl RandomnessSource,
+) -> Result<Signature, Error> {
+    let mut user = User::new(&key_pair.public_key, user_rng);
+    let mut signer = Signer::new(key_pair, signer_rng);
+
+    user.set_message(m0, m1)?;
+    signer.set_message(m0)?;
+    let commitment = user.commit()?;
+    let eta = signer.commit(commitment)?;
+    let witness = user.compute_witness(&eta)?;
+    signer.verify_witness(witness)?;
+    let partial = signer.sign()?;
+
+    user.sign(&partial)
+}
+
 #[cfg(test)]
 mod tests;
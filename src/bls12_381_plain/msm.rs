@@ -0,0 +1,40 @@
+//! Multi-scalar multiplication in $\mathbb{G_1}$ for the four generators $h_1, g_1, u_1, v_1$
+
+use bls12_381::{G1Affine, G1Projective, Scalar};
+use subtle::{ConditionallySelectable, ConstantTimeEq};
+
+/// $\sum_i P_i^{k_i}$ for four points by simultaneous double-and-add (Straus' method)
+///
+/// The sixteen subset sums of the points are precomputed, then every bit position costs one doubling and one
+/// addition instead of one of each per point. Table lookups scan every entry and all scalars are processed bit by
+/// bit, so the running time does not depend on the scalars, which may be secret blinding factors.
+pub(crate) fn msm4(points: [&G1Affine; 4], scalars: [&Scalar; 4]) -> G1Projective {
+    let mut table = [G1Projective::identity(); 16];
+    for (i, point) in points.iter().enumerate() {
+        let bit = 1 << i;
+        for j in 0..bit {
+            table[bit | j] = table[j] + *point;
+        }
+    }
+
+    let bytes = scalars.map(Scalar::to_bytes);
+    let mut acc = G1Projective::identity();
+    for byte in (0..32).rev() {
+        for bit in (0..8).rev() {
+            acc = acc.double();
+
+            let mut index = 0u8;
+            for (i, scalar) in bytes.iter().enumerate() {
+                index |= ((scalar[byte] >> bit) & 1) << i;
+            }
+
+            let mut addend = G1Projective::identity();
+            for (j, entry) in table.iter().enumerate() {
+                addend.conditional_assign(entry, (j as u8).ct_eq(&index));
+            }
+            acc += addend;
+        }
+    }
+
+    acc
+}
@@ -0,0 +1,527 @@
+//! Signing a vector of blinded messages $m_{1,1}, \ldots, m_{1,k}$ under a single shared $m_0$
+//!
+//! The single message scheme commits to $m_1$ with the generator pair $(g_1, g_2)$. Signing $k$ messages needs one
+//! generator pair per message, so a [MultiMessagePublicKey] extends a [PublicKey] with $k - 1$ further pairs
+//! $(z_{1,j}, z_{2,j})$ sharing the same discrete logarithm relative to $(g_1, g_2)$. Message $1$ keeps using
+//! $(g_1, g_2)$ and message $j > 1$ uses $(z_{1,j-1}, z_{2,j-1})$, so with $k = 1$ every equation below is exactly
+//! that of the single message scheme.
+//!
+//! The flow mirrors [super::Signer] and [super::User]; only $X$, $W$ and the witness $b_1$ generalize:
+//!
+//! $X \leftarrow ({h_1}^{m_0}\prod_j {z_{1,j}}^{m_{1,j}}{u_1}{v_1}^{s})^{t}$
+//!
+//! $W \leftarrow ({h_1}^{m_0})^{a_2}\prod_j {z_{1,j}}^{a_{1,j}}{u_1}^{a_2}{v_1}^{a_3}$
+//!
+//! $b_{1,j} \leftarrow a_{1,j} + \eta{m_{1,j}}t \mod p$
+//!
+//! and a signature verifies if $e(\sigma,w_2\alpha) = e(g_1,{h_2}^{m_0}\prod_j {z_{2,j}}^{m_{1,j}}{u_2}{v_2}^{\beta})$.
+
+use super::{
+    compute_Y, partial_well_formed, well_formed, AbortReason, Challenge, Commitment, Error, KeyPair, PartialSignature,
+    PublicKey, RandomnessSource, SecretKey, Signature, SignerState, UserState,
+};
+use alloc::vec::Vec;
+use bls12_381::{G1Affine, G1Projective, G2Affine, G2Projective, Scalar};
+use ff::Field;
+use rand_core::{CryptoRng, RngCore};
+
+/// A [PublicKey] extended with a generator pair for every message after the first
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultiMessagePublicKey {
+    pub public_key: PublicKey,
+    /// $z_{1,j} \in \mathbb{G_1}$ for messages $2..k$
+    pub z1: Vec<G1Affine>,
+    /// $z_{2,j} \in \mathbb{G_2}$ for messages $2..k$, with the same discrete logarithms as `z1`
+    pub z2: Vec<G2Affine>,
+}
+
+impl MultiMessagePublicKey {
+    /// The number $k$ of messages that can be signed under this key
+    pub fn len(&self) -> usize {
+        self.z1.len() + 1
+    }
+
+    /// Always `false`, a key signs at least one message
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// $\prod_j {z_{1,j}}^{e_j}$ with $z_{1,1} = g_1$
+    fn commit1(&self, exponents: &[Scalar]) -> G1Projective {
+        core::iter::once(&self.public_key.g1)
+            .chain(&self.z1)
+            .zip(exponents)
+            .map(|(z, e)| z * e)
+            .sum()
+    }
+
+    /// $\prod_j {z_{2,j}}^{e_j}$ with $z_{2,1} = g_2$
+    fn commit2(&self, exponents: &[Scalar]) -> G2Projective {
+        core::iter::once(&self.public_key.g2)
+            .chain(&self.z2)
+            .zip(exponents)
+            .map(|(z, e)| z * e)
+            .sum()
+    }
+}
+
+/// A pair of secret and public keys for signing $k$ messages at once
+pub struct MultiMessageKeyPair {
+    pub public_key: MultiMessagePublicKey,
+    secret_key: SecretKey,
+}
+
+impl MultiMessageKeyPair {
+    /// Generate a fresh [MultiMessageKeyPair] able to sign `len` messages
+    ///
+    /// # Panics
+    /// If `len` is zero
+    pub fn generate(len: usize, mut rng: impl RngCore + CryptoRng) -> Self {
+        assert!(len > 0, "a key must sign at least one message");
+
+        let KeyPair { public_key, secret_key } = KeyPair::generate(&mut rng);
+
+        let mut z1 = Vec::with_capacity(len - 1);
+        let mut z2 = Vec::with_capacity(len - 1);
+        while z1.len() < len - 1 {
+            let z_r = Scalar::random(&mut rng);
+            if z_r.is_zero().into() {
+                continue;
+            }
+            z1.push(G1Affine::from(public_key.g1 * z_r));
+            z2.push(G2Affine::from(public_key.g2 * z_r));
+        }
+
+        Self {
+            public_key: MultiMessagePublicKey { public_key, z1, z2 },
+            secret_key,
+        }
+    }
+}
+
+/// The [MultiMessageUser]'s witness $(b_{1,1}, \ldots, b_{1,k}, b_2, b_3)$ sent to the [MultiMessageSigner] in
+/// Step 3
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultiMessageWitness {
+    /// $b_{1,j} \in \mathbb{Z}_p$, one per message
+    pub b1: Vec<Scalar>,
+    /// $b_2 \in \mathbb{Z}_p$
+    pub b2: Scalar,
+    /// $b_3 \in \mathbb{Z}_p$
+    pub b3: Scalar,
+}
+
+/// The [super::Signer] of the multi-message scheme
+///
+/// Identical to [super::Signer] except that [MultiMessageSigner::verify_witness] takes a [MultiMessageWitness].
+#[allow(non_snake_case)]
+pub struct MultiMessageSigner<'a, R: RandomnessSource> {
+    key_pair: &'a MultiMessageKeyPair,
+    rng: R,
+    state: SignerState,
+    m0: Scalar,
+    W: G1Projective,
+    X: G1Projective,
+    eta: Scalar,
+}
+
+impl<'a, R: RandomnessSource> MultiMessageSigner<'a, R> {
+    /// Create a fresh [MultiMessageSigner] in the starting state given a [MultiMessageKeyPair]
+    pub fn new(key_pair: &'a MultiMessageKeyPair, rng: R) -> Self {
+        Self {
+            key_pair,
+            rng,
+            state: SignerState::ReadyToSetMessage,
+            m0: Scalar::zero(),
+            W: Default::default(),
+            X: Default::default(),
+            eta: Default::default(),
+        }
+    }
+
+    /// Get the current [SignerState]
+    pub fn get_state(&self) -> &SignerState {
+        &self.state
+    }
+
+    /// Step 1. Agree on $m_0$ (aka `info`), see [super::Signer::set_message]
+    ///
+    /// A zero $m_0$ fails with [Error::ScalarIsZero] and aborts, as in [MultiMessageUser::set_message].
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "MultiMessageSigner::set_message", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
+    pub fn set_message(&mut self, m0: Scalar) -> Result<(), Error> {
+        self.state.expect(SignerState::ReadyToSetMessage)?;
+
+        if m0.is_zero().into() {
+            return Err(self.abort_with(Error::ScalarIsZero));
+        }
+
+        self.m0 = m0;
+        self.set_state(SignerState::ReadyToCommit);
+
+        Ok(())
+    }
+
+    /// Step 2. Store the [Commitment] $(W, X)$ and return a fresh challenge $\eta \in \mathbb{Z}_p^{*}$
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "MultiMessageSigner::commit", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
+    pub fn commit(&mut self, commitment: Commitment) -> Result<Challenge, Error> {
+        self.state.expect(SignerState::ReadyToCommit)?;
+
+        if !bool::from(commitment.w.is_on_curve()) || !bool::from(commitment.x.is_on_curve()) {
+            return Err(self.abort_with(Error::PointNotOnCurve));
+        }
+
+        self.eta = loop {
+            let eta = self.rng.next_scalar();
+            if !bool::from(eta.is_zero()) {
+                break eta;
+            }
+        };
+        self.W = G1Projective::from(commitment.w);
+        self.X = G1Projective::from(commitment.x);
+        self.set_state(SignerState::ReadyToVerifyWitness);
+
+        Ok(Challenge::from(self.eta))
+    }
+
+    /// Step 3. Verify that the [MultiMessageUser] knows the values committed to in $(W, X)$
+    ///
+    /// $({h_1}^{m_0})^{b_2}\prod_j {z_{1,j}}^{b_{1,j}}{u_1}^{b_2}{v_1}^{b_3} = WX^{\eta}$
+    ///
+    /// # Errors
+    /// - [Error::WrongState] unless in [SignerState::ReadyToVerifyWitness]
+    /// - [Error::MessageCountMismatch] unless there is one $b_{1,j}$ per message, [Error::ScalarIsZero] if any
+    ///   $b_{1,j}$, $b_2$ or $b_3$ is zero and [Error::WitnessMismatch] if the proof does not hold, which abort
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "MultiMessageSigner::verify_witness", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
+    pub fn verify_witness(&mut self, witness: MultiMessageWitness) -> Result<(), Error> {
+        self.state.expect(SignerState::ReadyToVerifyWitness)?;
+
+        let pk = &self.key_pair.public_key;
+        if witness.b1.len() != pk.len() {
+            return Err(self.abort_with(Error::MessageCountMismatch));
+        }
+
+        let MultiMessageWitness { b1, b2, b3 } = witness;
+        if bool::from(
+            b1.iter()
+                .fold(b2.is_zero() | b3.is_zero(), |zero, b| zero | b.is_zero()),
+        ) {
+            return Err(self.abort_with(Error::ScalarIsZero));
+        }
+
+        let p = &pk.public_key;
+        let lhs = p.h1 * (self.m0 * b2) + pk.commit1(&b1) + p.u1 * b2 + p.v1 * b3;
+        let rhs = self.W + self.X * self.eta;
+
+        if lhs != rhs {
+            return Err(self.abort_with(Error::WitnessMismatch {
+                #[cfg(feature = "debug-errors")]
+                lhs: G1Affine::from(lhs).to_compressed(),
+                #[cfg(feature = "debug-errors")]
+                rhs: G1Affine::from(rhs).to_compressed(),
+            }));
+        }
+
+        self.set_state(SignerState::ReadyToSign);
+
+        Ok(())
+    }
+
+    /// Step 4. Return the partial signature $(Y, R, l)$, see [super::Signer::sign]
+    ///
+    /// $l$ and $r$ are redrawn until both are nonzero and $x + r$ is invertible.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "MultiMessageSigner::sign", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
+    pub fn sign(&mut self) -> Result<PartialSignature, Error> {
+        self.state.expect(SignerState::ReadyToSign)?;
+
+        let (l, r) = loop {
+            let l = self.rng.next_scalar();
+            let r = self.rng.next_scalar();
+            if !bool::from(l.is_zero() | r.is_zero() | (self.key_pair.secret_key + r).is_zero()) {
+                break (l, r);
+            }
+        };
+
+        let pk = &self.key_pair.public_key.public_key;
+        #[allow(non_snake_case)]
+        let Y = match compute_Y(pk, &self.key_pair.secret_key, &G1Affine::from(self.X), &l, &r) {
+            Ok(Y) => Y,
+            Err(error) => return Err(self.abort_with(error)),
+        };
+        #[allow(non_snake_case)]
+        let R = pk.g2 * r;
+
+        self.set_state(SignerState::Signed);
+
+        Ok(PartialSignature {
+            y: Y,
+            r: G2Affine::from(R),
+            l,
+        })
+    }
+
+    /// Abort the protocol preventing further use of the values
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "MultiMessageSigner::abort", skip_all, fields(state = tracing::field::Empty))
+    )]
+    pub fn abort(&mut self) {
+        self.set_state(SignerState::Aborted(AbortReason::UserRequested));
+    }
+
+    /// Abort because of `error`, which is returned for the caller to propagate
+    fn abort_with(&mut self, error: Error) -> Error {
+        self.set_state(SignerState::Aborted(AbortReason::Error(error)));
+        error
+    }
+
+    /// Move to `state`, recording it on the current step's span and in the transition counters
+    fn set_state(&mut self, state: SignerState) {
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("state", tracing::field::debug(&state));
+        #[cfg(feature = "metrics")]
+        super::metrics::record_signer_transition(&state);
+        self.state = state;
+    }
+}
+
+/// The [super::User] of the multi-message scheme
+pub struct MultiMessageUser<'a, R: RandomnessSource> {
+    public_key: &'a MultiMessagePublicKey,
+    state: UserState,
+    rng: R,
+    m0: Scalar,
+    m1: Vec<Scalar>,
+    a1: Vec<Scalar>,
+    a2: Scalar,
+    a3: Scalar,
+    s: Scalar,
+    t: Scalar,
+}
+
+impl<'a, R: RandomnessSource> MultiMessageUser<'a, R> {
+    /// Create a fresh [MultiMessageUser] in the starting state given a [MultiMessagePublicKey]
+    pub fn new(public_key: &'a MultiMessagePublicKey, rng: R) -> Self {
+        Self {
+            public_key,
+            state: UserState::ReadyToSetMessage,
+            rng,
+            m0: Scalar::zero(),
+            m1: Vec::new(),
+            a1: Vec::new(),
+            a2: Scalar::zero(),
+            a3: Scalar::zero(),
+            s: Scalar::zero(),
+            t: Scalar::zero(),
+        }
+    }
+
+    /// Get the current [UserState]
+    pub fn get_state(&self) -> &UserState {
+        &self.state
+    }
+
+    /// Step 1. Set $m_0$ (aka `info`) and the blinded messages $m_{1,1}, \ldots, m_{1,k}$
+    ///
+    /// Fails and aborts with [Error::MessageCountMismatch] unless `m1` holds exactly [MultiMessagePublicKey::len]
+    /// messages, with [Error::ScalarIsZero] if any message is zero and with [Error::DuplicateMessages] if any
+    /// $m_{1,j} = m_0$, which the [MultiMessageSigner] knows, as in [super::User::set_message].
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "MultiMessageUser::set_message", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
+    pub fn set_message(&mut self, m0: Scalar, m1: &[Scalar]) -> Result<(), Error> {
+        self.state.expect(UserState::ReadyToSetMessage)?;
+
+        if m1.len() != self.public_key.len() {
+            return Err(self.abort_with(Error::MessageCountMismatch));
+        }
+
+        if bool::from(m1.iter().fold(m0.is_zero(), |zero, m| zero | m.is_zero())) {
+            return Err(self.abort_with(Error::ScalarIsZero));
+        }
+
+        if m1.contains(&m0) {
+            return Err(self.abort_with(Error::DuplicateMessages));
+        }
+
+        self.m0 = m0;
+        self.m1 = m1.to_vec();
+        self.set_state(UserState::ReadyToCommit);
+
+        Ok(())
+    }
+
+    /// Step 2. Commit to $m_0$, the $m_{1,j}$ and fresh $s, t \in \mathbb{Z}_p^{*}$
+    ///
+    /// Draws $a_{1,1}, \ldots, a_{1,k}, a_2, a_3, s, t$ in that order from the [RandomnessSource].
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "MultiMessageUser::commit", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
+    pub fn commit(&mut self) -> Result<Commitment, Error> {
+        self.state.expect(UserState::ReadyToCommit)?;
+
+        let a1: Vec<Scalar> = self.m1.iter().map(|_| self.rng.next_scalar()).collect();
+        let a2 = self.rng.next_scalar();
+        let a3 = self.rng.next_scalar();
+        let s = self.rng.next_scalar();
+        let t = loop {
+            let t = self.rng.next_scalar();
+            if !bool::from(t.is_zero()) {
+                break t;
+            }
+        };
+        let pk = &self.public_key;
+        let p = &pk.public_key;
+        #[allow(non_snake_case)]
+        let X = (p.h1 * self.m0 + pk.commit1(&self.m1) + p.u1 + p.v1 * s) * t;
+        #[allow(non_snake_case)]
+        let W = p.h1 * (self.m0 * a2) + pk.commit1(&a1) + p.u1 * a2 + p.v1 * a3;
+
+        self.a1 = a1;
+        self.a2 = a2;
+        self.a3 = a3;
+        self.s = s;
+        self.t = t;
+
+        self.set_state(UserState::ReadyToComputeWitness);
+
+        Ok(Commitment {
+            w: G1Affine::from(W),
+            x: G1Affine::from(X),
+        })
+    }
+
+    /// Step 3. Prove knowledge of the values committed to in $(W, X)$
+    ///
+    /// A zero $\eta$ is rejected as in [super::User::compute_witness].
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "MultiMessageUser::compute_witness", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
+    pub fn compute_witness(&mut self, challenge: &Challenge) -> Result<MultiMessageWitness, Error> {
+        self.state.expect(UserState::ReadyToComputeWitness)?;
+
+        let eta = challenge.eta();
+
+        if eta.is_zero().into() {
+            return Err(self.abort_with(Error::ScalarIsZero));
+        }
+
+        let b1 = self
+            .a1
+            .iter()
+            .zip(&self.m1)
+            .map(|(a1, m1)| a1 + eta * m1 * self.t)
+            .collect();
+        let b2 = self.a2 + eta * self.t;
+        let b3 = self.a3 + eta * self.s * self.t;
+
+        self.set_state(UserState::ReadyToSign);
+
+        Ok(MultiMessageWitness { b1, b2, b3 })
+    }
+
+    /// Step 4 (final). Unblind the [PartialSignature] $(Y, R, l)$, see [super::User::sign]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "MultiMessageUser::sign", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
+    pub fn sign(&mut self, partial: &PartialSignature) -> Result<Signature, Error> {
+        self.state.expect(UserState::ReadyToSign)?;
+
+        if !bool::from(partial_well_formed(partial)) {
+            return Err(self.abort_with(Error::PointNotOnCurve));
+        }
+
+        #[allow(non_snake_case)]
+        let PartialSignature { y: Y, r: R, l } = partial;
+        let pk = &self.public_key.public_key;
+        // t is nonzero so any nonzero f makes f * t invertible, and 1/t = f * tau
+        let (f, tau) = loop {
+            let f = self.rng.next_scalar();
+            if let Some(tau) = Option::<Scalar>::from((f * self.t).invert()) {
+                break (f, tau);
+            }
+        };
+        let sigma = Y * tau;
+        let alpha = pk.w2 * (f - Scalar::one()) + (R * f);
+        let beta = self.s + l * f * tau;
+
+        self.set_state(UserState::Signed);
+
+        Ok(Signature {
+            sigma: G1Affine::from(sigma),
+            alpha: G2Affine::from(alpha),
+            beta,
+        })
+    }
+
+    /// Abort the protocol preventing further use of the values
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "MultiMessageUser::abort", skip_all, fields(state = tracing::field::Empty))
+    )]
+    pub fn abort(&mut self) {
+        self.set_state(UserState::Aborted(AbortReason::UserRequested));
+    }
+
+    /// Abort because of `error`, which is returned for the caller to propagate
+    fn abort_with(&mut self, error: Error) -> Error {
+        self.set_state(UserState::Aborted(AbortReason::Error(error)));
+        error
+    }
+
+    /// Move to `state`, recording it on the current step's span and in the transition counters
+    fn set_state(&mut self, state: UserState) {
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("state", tracing::field::debug(&state));
+        #[cfg(feature = "metrics")]
+        super::metrics::record_user_transition(&state);
+        self.state = state;
+    }
+}
+
+/// Verify that a [Signature] is valid on $m_0$ and the messages $m_{1,1}, \ldots, m_{1,k}$
+///
+/// $e(\sigma,w_2\alpha) = e(g_1,{h_2}^{m_0}\prod_j {z_{2,j}}^{m_{1,j}}{u_2}{v_2}^{\beta})$
+///
+/// Fails with [Error::MessageCountMismatch] unless `m1` holds exactly [MultiMessagePublicKey::len] messages.
+pub fn verify_multi_signature(
+    public_key: &MultiMessagePublicKey,
+    m0: &Scalar,
+    m1: &[Scalar],
+    signature: &Signature,
+) -> Result<(), Error> {
+    if m1.len() != public_key.len() {
+        return Err(Error::MessageCountMismatch);
+    }
+
+    let Signature { sigma, alpha, beta } = signature;
+    let pk = &public_key.public_key;
+
+    if !bool::from(well_formed(&pk.w2, signature)) {
+        return Err(Error::InvalidSignature);
+    }
+
+    let lhs2 = G2Affine::from(G2Projective::from(pk.w2) + alpha);
+    let rhs2 = G2Affine::from(pk.h2 * m0 + public_key.commit2(m1) + pk.u2 + pk.v2 * beta);
+
+    if bls12_381::pairing(sigma, &lhs2) != bls12_381::pairing(&pk.g1, &rhs2) {
+        return Err(Error::InvalidSignature);
+    }
+
+    Ok(())
+}
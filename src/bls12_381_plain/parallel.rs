@@ -0,0 +1,21 @@
+//! Verification of independent signatures in parallel with [rayon]
+
+use super::{verify_signature_prepared, Error, PublicKey, Signature};
+use alloc::vec::Vec;
+use bls12_381::Scalar;
+use rayon::prelude::*;
+
+/// Verify every `(m0, m1, signature)` independently across the threads of the global [rayon] pool
+///
+/// Unlike [super::AggregateSignature::verify] the result of each signature is reported, in the order of `items`, so
+/// the caller knows exactly which ones failed. The [PublicKey] is prepared once; if it is malformed every result is
+/// [Error::InvalidPublicKey].
+pub fn verify_many(pk: &PublicKey, items: &[(Scalar, Scalar, Signature)]) -> Vec<Result<(), Error>> {
+    match pk.prepare() {
+        Ok(prepared) => items
+            .par_iter()
+            .map(|(m0, m1, signature)| verify_signature_prepared(&prepared, m0, m1, signature))
+            .collect(),
+        Err(_) => items.iter().map(|_| Err(Error::InvalidPublicKey)).collect(),
+    }
+}
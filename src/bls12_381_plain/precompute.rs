@@ -0,0 +1,114 @@
+//! Drawing the [User]'s blinding factors and their message-independent terms ahead of [User::commit]
+
+use super::msm::msm4;
+use super::{Commitment, Error, RandomnessSource, User, UserState};
+use bls12_381::{G1Affine, G1Projective, Scalar};
+use core::fmt;
+use ff::Field;
+
+/// The random scalars $a_1, a_2, a_3, s, t$ of one [Commitment] with the terms of $W$ and $X$ that do not depend on the
+/// messages, from [User::precompute_commitment]
+///
+/// $W_0 \leftarrow {g_1}^{a_1}{u_1}^{a_2}{v_1}^{a_3}$
+///
+/// $X_0 \leftarrow {u_1}^{t}{v_1}^{st}$
+///
+/// It holds the [User]'s blinding factors, so it is neither [Clone] nor printed by [fmt::Debug]: committing twice
+/// with the same factors would let the [Signer] link the two signatures.
+pub struct PrecomputedCommitment {
+    /// $(g_1, u_1, v_1)$ of the [super::PublicKey] the terms were computed for
+    generators: [G1Affine; 3],
+    a1: Scalar,
+    a2: Scalar,
+    a3: Scalar,
+    s: Scalar,
+    t: Scalar,
+    w0: G1Projective,
+    x0: G1Affine,
+}
+
+/// The blinding factors are redacted
+impl fmt::Debug for PrecomputedCommitment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrecomputedCommitment").finish_non_exhaustive()
+    }
+}
+
+impl<'a, R: RandomnessSource> User<'a, R> {
+    /// Draw the blinding factors of a future [Commitment] and compute its message-independent terms, e.g. while idle
+    /// before [User::set_message]
+    ///
+    /// Does not depend on or change the [UserState]. Pass the result to [User::commit_with] of this or any other
+    /// [User] of the same [super::PublicKey]; each one can be used once.
+    pub fn precompute_commitment(&mut self) -> PrecomputedCommitment {
+        let a1 = self.rng.next_scalar();
+        let a2 = self.rng.next_scalar();
+        let a3 = self.rng.next_scalar();
+        let s = self.rng.next_scalar();
+        let t = loop {
+            let t = self.rng.next_scalar();
+            if !bool::from(t.is_zero()) {
+                break t;
+            }
+        };
+        let pk = self.public_key;
+        let identity = G1Affine::identity();
+        let zero = Scalar::zero();
+
+        PrecomputedCommitment {
+            generators: [pk.g1, pk.u1, pk.v1],
+            w0: msm4([&pk.g1, &pk.u1, &pk.v1, &identity], [&a1, &a2, &a3, &zero]),
+            x0: G1Affine::from(msm4(
+                [&pk.u1, &pk.v1, &identity, &identity],
+                [&t, &(s * t), &zero, &zero],
+            )),
+            a1,
+            a2,
+            a3,
+            s,
+            t,
+        }
+    }
+
+    /// Step 2. As [User::commit] but with the blinding factors of `precomputed`, mixing in only the messages
+    ///
+    /// $W \leftarrow {h_1}^{m_0a_2}W_0$
+    ///
+    /// $X \leftarrow {h_1}^{m_0t}{g_1}^{m_1t}X_0$
+    ///
+    /// # Errors
+    /// - [Error::WrongState] unless in [UserState::ReadyToCommit]
+    /// - [Error::InvalidPublicKey] if `precomputed` was made by a [User] of another [super::PublicKey], which aborts
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "User::commit_with", skip_all, fields(state = tracing::field::Empty), err(Debug))
+    )]
+    pub fn commit_with(&mut self, precomputed: PrecomputedCommitment) -> Result<Commitment, Error> {
+        self.state.expect(UserState::ReadyToCommit)?;
+
+        let pk = self.public_key;
+        if precomputed.generators != [pk.g1, pk.u1, pk.v1] {
+            return Err(self.abort_with(Error::InvalidPublicKey));
+        }
+
+        let PrecomputedCommitment {
+            a1,
+            a2,
+            a3,
+            s,
+            t,
+            w0,
+            x0,
+            ..
+        } = precomputed;
+        #[allow(non_snake_case)]
+        let X = msm4(
+            [&pk.h1, &pk.g1, &x0, &G1Affine::identity()],
+            [&(self.m0 * t), &(self.m1 * t), &Scalar::one(), &Scalar::zero()],
+        );
+        #[allow(non_snake_case)]
+        let W = pk.h1 * (self.m0 * a2) + w0;
+
+        Ok(self.finish_commit([a1, a2, a3, s, t], W, X))
+    }
+}
@@ -0,0 +1,31 @@
+//! The source of every random value drawn by [Signer](super::Signer) and [User](super::User)
+
+use bls12_381::Scalar;
+use ff::Field;
+use rand_core::{CryptoRng, RngCore};
+
+/// Draws the random scalars of the protocol, one [RandomnessSource::next_scalar] per value
+///
+/// [Signer](super::Signer) and [User](super::User) draw nothing else, so implementing this for e.g. a central
+/// randomness beacon makes the exact number and order of draws visible to it:
+///
+/// - the [Signer](super::Signer) draws $\eta$ in `commit` (unless it is derived with Fiat-Shamir) and $l$ then $r$ in
+///   `sign`
+/// - the [User](super::User) draws $a_1, a_2, a_3, s, t$ in that order in `commit` (or in `precompute_commitment`,
+///   and $s, t$ in `blind`) and $f$ in `sign`
+///
+/// A value that is rejected, e.g. a zero $t$ or an $r = -x$, is drawn again. The source must be unpredictable to the
+/// other party; a predictable $\eta$, $r$ or $l$ lets a [User](super::User) forge signatures and predictable blinding
+/// factors let the [Signer](super::Signer) link them.
+///
+/// Every [RngCore] + [CryptoRng] is a source through [Scalar::random].
+pub trait RandomnessSource {
+    /// Draw a uniformly random [Scalar]
+    fn next_scalar(&mut self) -> Scalar;
+}
+
+impl<R: RngCore + CryptoRng> RandomnessSource for R {
+    fn next_scalar(&mut self) -> Scalar {
+        Scalar::random(self)
+    }
+}
@@ -0,0 +1,48 @@
+//! Detecting a [super::Signer] that reuses its nonce $r$ across sessions
+
+use bls12_381::G2Affine;
+use std::collections::HashSet;
+use std::sync::{Mutex, MutexGuard};
+
+/// The $R = {g_2}^r$ of every [super::PartialSignature] a [super::User] has signed with
+///
+/// An honest [super::Signer] draws a fresh $r$ for every session, so a repeated $R$ means its randomness is broken,
+/// e.g. a cloned or restored RNG, and its secret key may already be exposed. A guard shared by every
+/// [super::User] talking to one signer, see [super::User::with_replay_guard], makes them reject such a partial
+/// signature with [super::Error::ReplayDetected].
+///
+/// The guard only grows; it holds 96 bytes per session and can be shared between threads.
+#[derive(Debug, Default)]
+pub struct ReplayGuard {
+    seen: Mutex<HashSet<[u8; 96]>>,
+}
+
+impl ReplayGuard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record `r`, returning `false` if it had been recorded before
+    pub fn record(&self, r: &G2Affine) -> bool {
+        self.seen().insert(r.to_compressed())
+    }
+
+    /// Whether `r` has been recorded
+    pub fn contains(&self, r: &G2Affine) -> bool {
+        self.seen().contains(&r.to_compressed())
+    }
+
+    /// The number of distinct $R$ recorded
+    pub fn len(&self) -> usize {
+        self.seen().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// A set of compressed points stays consistent even if a thread panicked while holding the lock
+    fn seen(&self) -> MutexGuard<'_, HashSet<[u8; 96]>> {
+        self.seen.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
@@ -0,0 +1,138 @@
+//! [serde] implementations for the protocol messages
+//!
+//! Every message is serialized as a fixed-length tuple of bytes holding its `to_bytes` encoding: compressed points
+//! followed by little-endian scalars. Formats without length prefixes for tuples, such as `bincode`, therefore
+//! produce exactly those bytes. Deserialization goes through `from_bytes` and so rejects points that are not on the
+//! curve and scalars that are not canonical, i.e. not less than the field modulus, as well as a zero [Challenge].
+//!
+//! [Commitment], [Challenge], [Witness], [PartialSignature] and [Signature] implement [Serialize] and [Deserialize]
+//! directly. Other [Scalar]s can be serialized through [scalar] with `#[serde(with = "...")]`.
+
+use super::{
+    decode_scalar, Challenge, Commitment, Error, PartialSignature, Signature, Witness, CHALLENGE_BYTES,
+    COMMITMENT_BYTES, PARTIAL_SIGNATURE_BYTES, SIGNATURE_BYTES, WITNESS_BYTES,
+};
+use bls12_381::Scalar;
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::ser::{SerializeTuple, Serializer};
+use serde::{Deserialize, Serialize};
+
+fn serialize_array<S: Serializer, const N: usize>(bytes: &[u8; N], serializer: S) -> Result<S::Ok, S::Error> {
+    let mut tuple = serializer.serialize_tuple(N)?;
+    for byte in bytes {
+        tuple.serialize_element(byte)?;
+    }
+    tuple.end()
+}
+
+fn deserialize_array<'de, D: Deserializer<'de>, const N: usize>(deserializer: D) -> Result<[u8; N], D::Error> {
+    struct ArrayVisitor<const N: usize>(PhantomData<[u8; N]>);
+
+    impl<'de, const N: usize> Visitor<'de> for ArrayVisitor<N> {
+        type Value = [u8; N];
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "{} bytes", N)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut bytes = [0u8; N];
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                *byte = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(i, &self))?;
+            }
+            Ok(bytes)
+        }
+    }
+
+    deserializer.deserialize_tuple(N, ArrayVisitor(PhantomData))
+}
+
+fn decoding_error<E: de::Error>(error: Error) -> E {
+    E::custom(format_args!("{:?}", error))
+}
+
+impl Serialize for Commitment {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_array(&self.to_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Commitment {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Commitment::from_bytes(&deserialize_array::<_, COMMITMENT_BYTES>(deserializer)?).map_err(decoding_error)
+    }
+}
+
+impl Serialize for Challenge {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_array(&self.to_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Challenge {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Challenge::from_bytes(&deserialize_array::<_, CHALLENGE_BYTES>(deserializer)?).map_err(decoding_error)
+    }
+}
+
+impl Serialize for Witness {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_array(&self.to_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Witness {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Witness::from_bytes(&deserialize_array::<_, WITNESS_BYTES>(deserializer)?).map_err(decoding_error)
+    }
+}
+
+impl Serialize for PartialSignature {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_array(&self.to_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PartialSignature {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        PartialSignature::from_bytes(&deserialize_array::<_, PARTIAL_SIGNATURE_BYTES>(deserializer)?)
+            .map_err(decoding_error)
+    }
+}
+
+impl Serialize for Signature {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_array(&self.to_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Signature {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Signature::from_bytes(&deserialize_array::<_, SIGNATURE_BYTES>(deserializer)?).map_err(decoding_error)
+    }
+}
+
+/// A [Scalar] such as a message $m_1$ as its 32-byte little-endian encoding
+///
+/// ```rust
+/// # use bls12_381::Scalar;
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Message {
+///     #[serde(with = "ziglet_okamoto::bls12_381_plain::serialization::scalar")]
+///     m1: Scalar,
+/// }
+/// ```
+pub mod scalar {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(scalar: &Scalar, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_array(&scalar.to_bytes(), serializer)
+    }
+
+    /// Fails unless the encoding is canonical
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Scalar, D::Error> {
+        decode_scalar(&deserialize_array::<_, 32>(deserializer)?).map_err(decoding_error)
+    }
+}
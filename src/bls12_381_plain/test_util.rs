@@ -0,0 +1,61 @@
+//! Helpers shared by the tests of the crate
+//!
+//! Available in the crate's own unit tests and with the `test-internals` feature. An integration test, benchmark or
+//! example using them must be gated on the feature, e.g. with `required-features = ["test-internals"]`, and run with
+//! `--features test-internals`.
+
+use super::{Error, KeyPair, RandomnessSource, Signature, Signer, User};
+use bls12_381::Scalar;
+
+/// A fresh [User] and [Signer] of `key_pair` in their initial states, drawing from `user_rng` and `signer_rng`
+pub fn parties<U: RandomnessSource, S: RandomnessSource>(
+    key_pair: &KeyPair,
+    user_rng: U,
+    signer_rng: S,
+) -> (User<'_, U>, Signer<'_, S>) {
+    (
+        User::new(&key_pair.public_key, user_rng),
+        Signer::new(key_pair, signer_rng),
+    )
+}
+
+/// As [parties], after Step 1 on $(m_0, m_1)$, so both are ready to commit
+///
+/// # Errors
+/// Any [Error] of [User::set_message] or [Signer::set_message], as-is
+pub fn parties_with_messages<U: RandomnessSource, S: RandomnessSource>(
+    key_pair: &KeyPair,
+    m0: Scalar,
+    m1: Scalar,
+    user_rng: U,
+    signer_rng: S,
+) -> Result<(User<'_, U>, Signer<'_, S>), Error> {
+    let (mut user, mut signer) = parties(key_pair, user_rng, signer_rng);
+    user.set_message(m0, m1)?;
+    signer.set_message(m0)?;
+
+    Ok((user, signer))
+}
+
+/// Run the four steps of the protocol on $(m_0, m_1)$ between a fresh [User] and [Signer] of `key_pair`
+///
+/// As [super::sign_in_process], but also returns both parties in [super::SignerState::Signed] and
+/// [super::UserState::Signed] so the caller can inspect them afterwards, e.g. with [Signer::debug_r]. The [User]
+/// draws from `user_rng` and the [Signer] from `signer_rng`, which must be independent.
+///
+/// # Errors
+/// Any [Error] of an intermediate step, as-is
+pub fn run_protocol<'a, U: RandomnessSource, S: RandomnessSource>(
+    key_pair: &'a KeyPair,
+    m0: Scalar,
+    m1: Scalar,
+    user_rng: U,
+    signer_rng: S,
+) -> Result<(Signature, Signer<'a, S>, User<'a, U>), Error> {
+    let (mut user, mut signer) = parties_with_messages(key_pair, m0, m1, user_rng, signer_rng)?;
+    let eta = signer.commit(user.commit()?)?;
+    signer.verify_witness(user.compute_witness(&eta)?)?;
+    let signature = user.sign(&signer.sign()?)?;
+
+    Ok((signature, signer, user))
+}
@@ -1,29 +1,45 @@
-use crate::bls12_381_plain::{verify_signature, Error, KeyPair, Signer, User};
-use bls12_381::{G1Affine, G1Projective, G2Projective, Scalar};
+use crate::bls12_381_plain::test_util::{parties, parties_with_messages, run_protocol};
+use crate::bls12_381_plain::{
+    aggregate, anonymous_token, batch_verify, batch_verify_deterministic, compute_Y, decode_g1, decode_g2,
+    decode_scalar, derive_messages, fiat_shamir_challenge, find_matching_message, hash_to_scalar, pairing_holds,
+    rerandomize_partially, serialize_for_verification, sign_in_process, verification_receipt, verify_against_keys,
+    verify_commitment_proof, verify_common_info, verify_multi_signature, verify_serialized, verify_signature,
+    verify_signature_bytes, verify_signature_ct, verify_signature_points, verify_signature_prepared,
+    verify_signature_with_verifier_key, AbortReason, BatchVerifier, BlindedMessage, Challenge, Commitment,
+    ConstantTimeEq, Encoding, Error, HmacDrbg, KeyPair, MultiMessageKeyPair, MultiMessageSigner, MultiMessageUser,
+    PartialSignature, PublicKey, PublicKeyBuilder, RandomnessSource, ReplayGuard, ScalarHasher, Signature, Signer,
+    SignerState, StateKind, Transcript, User, UserState, VerifierKey, Witness, BLINDED_MESSAGE_BYTES, CHALLENGE_BYTES,
+    COMMITMENT_BYTES, INFO_DST, KEY_GENERATION_ATTEMPTS, MESSAGE_DST, PARTIAL_SIGNATURE_BYTES, PUBLIC_KEY_BYTES,
+    PUBLIC_KEY_UNCOMPRESSED_BYTES, RECEIPT_DST, SESSION_ID_BYTES, SIGNATURE_BYTES, SIGNATURE_UNCOMPRESSED_BYTES,
+    STEP_ABORTED, TRANSCRIPT_BYTES, VERIFICATION_ENVELOPE_BYTES, VERIFICATION_ENVELOPE_VERSION, VERIFIER_KEY_BYTES,
+    WITNESS_BYTES,
+};
+use bls12_381::{G1Affine, G1Projective, G2Affine, G2Projective, Scalar};
 use ff::Field;
-use rand_core::OsRng;
+use rand_core::{CryptoRng, OsRng, RngCore};
 
 #[allow(non_snake_case)]
 #[test]
 fn okamoto_happy_path() -> Result<(), Error> {
     let mut rng = rand_core::OsRng;
 
-    let key_pair = KeyPair::generate(&mut rng);
+    let key_pair = KeyPair::generate(rng);
 
-    let mut user = User::<OsRng>::new(&key_pair.public_key, rng.clone());
-    let mut signer = Signer::<OsRng>::new(&key_pair, rng.clone());
+    let mut user = User::<OsRng>::new(&key_pair.public_key, rng);
+    let mut signer = Signer::<OsRng>::new(&key_pair, rng);
 
     let m0 = Scalar::random(&mut rng);
     let m1 = Scalar::random(&mut rng);
 
     user.set_message(m0, m1)?;
     signer.set_message(m0)?;
-    let (W, X) = user.commit()?;
-    let eta = signer.commit(W, X)?;
-    let (b1, b2, b3) = user.compute_witness(&eta)?;
-    signer.verify_witness(b1, b2, b3)?;
-    let (Y, R, l) = signer.sign()?;
-    let (sigma, alpha, beta) = user.sign(&Y, &R, &l)?;
+    let commitment = user.commit()?;
+    let eta = signer.commit(commitment)?;
+    let witness = user.compute_witness(&eta)?;
+    signer.verify_witness(witness)?;
+    let partial = signer.sign()?;
+    let PartialSignature { y: Y, r: R, l } = partial;
+    let Signature { sigma, alpha, beta } = user.sign(&partial)?;
 
     assert_eq!(user.public_key, &signer.key_pair.public_key);
     assert_eq!(
@@ -55,8 +71,3717 @@ fn okamoto_happy_path() -> Result<(), Error> {
     let alpha2 = user.public_key.g2 * ((user.f - Scalar::one()) * signer.key_pair.secret_key + (user.f * signer.r));
     assert_eq!(G2Projective::from(alpha), alpha2);
 
-    verify_signature(&user.public_key, &user.m0, &user.m1, &sigma, &alpha, &beta)?;
+    verify_signature(user.public_key, &user.m0, &user.m1, &sigma, &alpha, &beta)?;
     verify_signature(&signer.key_pair.public_key, &signer.m0, &user.m1, &sigma, &alpha, &beta)?;
 
     Ok(())
 }
+
+#[test]
+fn commitment_and_witness_round_trip() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let mut user = User::new(&key_pair.public_key, rng);
+    let mut signer = Signer::new(&key_pair, rng);
+
+    let m0 = Scalar::random(&mut rng);
+    let m1 = Scalar::random(&mut rng);
+
+    user.set_message(m0, m1)?;
+    signer.set_message(m0)?;
+    let commitment = user.commit()?;
+    assert_eq!(Commitment::from_bytes(&commitment.to_bytes())?, commitment);
+
+    let eta = signer.commit(commitment)?;
+    let witness = user.compute_witness(&eta)?;
+    assert_eq!(Witness::from_bytes(&witness.to_bytes())?, witness);
+
+    signer.verify_witness(witness)?;
+    let partial = signer.sign()?;
+    assert_eq!(PartialSignature::from_bytes(&partial.to_bytes())?, partial);
+    user.sign(&PartialSignature::from_bytes(&partial.to_bytes())?)?;
+
+    let mut bytes = commitment.to_bytes();
+    bytes[0] ^= 0xff;
+    assert!(matches!(Commitment::from_bytes(&bytes), Err(Error::PointNotOnCurve)));
+
+    let mut bytes = witness.to_bytes();
+    bytes[31] = 0xff;
+    assert!(matches!(Witness::from_bytes(&bytes), Err(Error::NonCanonicalScalar)));
+
+    Ok(())
+}
+
+#[test]
+fn sign_in_process_produces_valid_signature() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let m0 = Scalar::random(&mut rng);
+    let m1 = Scalar::random(&mut rng);
+
+    let signature = sign_in_process(&key_pair, m0, m1, rng, rng)?;
+    verify_signature(
+        &key_pair.public_key,
+        &m0,
+        &m1,
+        &signature.sigma,
+        &signature.alpha,
+        &signature.beta,
+    )?;
+
+    assert!(matches!(
+        sign_in_process(&key_pair, m0, Scalar::zero(), rng, rng),
+        Err(Error::ScalarIsZero)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn partially_rerandomized_signature_verifies_and_keeps_beta() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let m0 = Scalar::random(&mut rng);
+    let m1 = Scalar::random(&mut rng);
+
+    let signature = sign_in_process(&key_pair, m0, m1, rng, rng)?;
+    let rerandomized = rerandomize_partially(pk, &signature, rng);
+
+    assert_ne!(rerandomized.sigma, signature.sigma);
+    assert_ne!(rerandomized.alpha, signature.alpha);
+    assert_eq!(rerandomized.beta, signature.beta);
+    verify_signature(
+        pk,
+        &m0,
+        &m1,
+        &rerandomized.sigma,
+        &rerandomized.alpha,
+        &rerandomized.beta,
+    )?;
+    assert!(matches!(
+        verify_signature(
+            pk,
+            &m0,
+            &m0,
+            &rerandomized.sigma,
+            &rerandomized.alpha,
+            &rerandomized.beta
+        ),
+        Err(Error::InvalidSignature)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn message_getters_require_set_message() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let mut user = User::new(&key_pair.public_key, rng);
+    let mut signer = Signer::new(&key_pair, rng);
+
+    assert!(matches!(
+        user.info(),
+        Err(Error::WrongState {
+            expected: StateKind::ReadyToCommit,
+            actual: StateKind::ReadyToSetMessage
+        })
+    ));
+    assert!(matches!(
+        user.message(),
+        Err(Error::WrongState {
+            expected: StateKind::ReadyToCommit,
+            actual: StateKind::ReadyToSetMessage
+        })
+    ));
+    assert!(matches!(
+        signer.info(),
+        Err(Error::WrongState {
+            expected: StateKind::ReadyToCommit,
+            actual: StateKind::ReadyToSetMessage
+        })
+    ));
+
+    let m0 = Scalar::random(&mut rng);
+    let m1 = Scalar::random(&mut rng);
+    user.set_message(m0, m1)?;
+    signer.set_message(m0)?;
+
+    assert_eq!(user.info()?, m0);
+    assert_eq!(user.message()?, m1);
+    assert_eq!(signer.info()?, m0);
+
+    Ok(())
+}
+
+#[test]
+fn prepared_verification_matches_unprepared() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let prepared = pk.prepare()?;
+    let m0 = Scalar::random(&mut rng);
+    let m1 = Scalar::random(&mut rng);
+
+    let signature = sign_in_process(&key_pair, m0, m1, rng, rng)?;
+    verify_signature(pk, &m0, &m1, &signature.sigma, &signature.alpha, &signature.beta)?;
+    verify_signature_prepared(&prepared, &m0, &m1, &signature)?;
+
+    assert!(matches!(
+        verify_signature_prepared(&prepared, &m1, &m0, &signature),
+        Err(Error::InvalidSignature)
+    ));
+    let tampered = Signature {
+        beta: signature.beta + Scalar::one(),
+        ..signature
+    };
+    assert!(matches!(
+        verify_signature_prepared(&prepared, &m0, &m1, &tampered),
+        Err(Error::InvalidSignature)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn verify_signature_bytes_matches_set_message_bytes() -> Result<(), Error> {
+    let rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let mut user = User::new(pk, rng);
+    let mut signer = Signer::new(&key_pair, rng);
+
+    user.set_message_bytes(b"withdraw 10", b"serial number")?;
+    signer.set_message_bytes(b"withdraw 10")?;
+    let challenge = signer.commit(user.commit()?)?;
+    signer.verify_witness(user.compute_witness(&challenge)?)?;
+    let signature = user.sign(&signer.sign()?)?;
+
+    verify_signature_bytes(pk, b"withdraw 10", b"serial number", &signature)?;
+    verify_signature(
+        pk,
+        &hash_to_scalar(INFO_DST, b"withdraw 10"),
+        &hash_to_scalar(MESSAGE_DST, b"serial number"),
+        &signature.sigma,
+        &signature.alpha,
+        &signature.beta,
+    )?;
+    for (info, message) in [
+        (&b"withdraw 11"[..], &b"serial number"[..]),
+        (b"withdraw 10", b"serial numbers"),
+        (b"serial number", b"withdraw 10"),
+    ] {
+        assert_eq!(
+            verify_signature_bytes(pk, info, message, &signature),
+            Err(Error::InvalidSignature)
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn derive_messages_separates_info_from_message() {
+    let (m0, m1) = derive_messages(b"EXAMPLE-APP", b"same bytes", b"same bytes");
+    assert_ne!(m0, m1);
+    assert_eq!((m0, m1), derive_messages(b"EXAMPLE-APP", b"same bytes", b"same bytes"));
+
+    let (m0_other_domain, _) = derive_messages(b"EXAMPLE-APP\x00", b"same bytes", b"same bytes");
+    assert_ne!(m0, m0_other_domain);
+    assert_eq!(m0, hash_to_scalar(b"EXAMPLE-APP\x00", b"same bytes"));
+    assert_eq!(m1, hash_to_scalar(b"EXAMPLE-APP\x01", b"same bytes"));
+}
+
+#[test]
+fn verify_serialized_checks_the_envelope() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+    let signature = sign_in_process(&key_pair, m0, m1, rng, rng)?;
+
+    let blob = serialize_for_verification(pk, &m0, &m1, &signature);
+    assert_eq!(blob.len(), VERIFICATION_ENVELOPE_BYTES);
+    verify_serialized(&blob)?;
+
+    let wrong_message = serialize_for_verification(pk, &m0, &(m1 + Scalar::one()), &signature);
+    assert_eq!(verify_serialized(&wrong_message), Err(Error::InvalidSignature));
+
+    let mut wrong_version = blob;
+    wrong_version[0] = VERIFICATION_ENVELOPE_VERSION + 1;
+    assert_eq!(verify_serialized(&wrong_version), Err(Error::DecodingFailed));
+    assert_eq!(verify_serialized(&blob[..blob.len() - 1]), Err(Error::DecodingFailed));
+    assert_eq!(
+        verify_serialized(&[blob.as_slice(), &[0]].concat()),
+        Err(Error::DecodingFailed)
+    );
+    assert_eq!(verify_serialized(&[]), Err(Error::DecodingFailed));
+
+    let mut non_canonical = blob;
+    non_canonical[1 + PUBLIC_KEY_BYTES..1 + PUBLIC_KEY_BYTES + 32].copy_from_slice(&modulus_plus_one());
+    assert_eq!(verify_serialized(&non_canonical), Err(Error::NonCanonicalScalar));
+
+    Ok(())
+}
+
+#[test]
+fn generate_with_dst_separates_deployments() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate_with_dst(rng, b"DEPLOYMENT-A");
+    key_pair.verify_consistency()?;
+    let secret = key_pair.export_secret();
+    let same_secret_other_dst = KeyPair::from_secret_bytes_with_dst(secret.as_bytes(), b"DEPLOYMENT-B")?;
+    let same_secret_no_dst = KeyPair::from_secret_bytes(secret.as_bytes())?;
+    assert_eq!(
+        KeyPair::from_secret_bytes_with_dst(secret.as_bytes(), b"DEPLOYMENT-A")?.public_key,
+        key_pair.public_key
+    );
+
+    let (a, b) = (&key_pair.public_key, &same_secret_other_dst.public_key);
+    let generators_a = [a.g1, a.h1, a.u1, a.v1];
+    for generator in [b.g1, b.h1, b.u1, b.v1] {
+        assert!(!generators_a.contains(&generator));
+    }
+    assert_ne!(same_secret_no_dst.public_key, key_pair.public_key);
+
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+    let signature = sign_in_process(&key_pair, m0, m1, rng, rng)?;
+    let Signature { sigma, alpha, beta } = signature;
+    verify_signature(a, &m0, &m1, &sigma, &alpha, &beta)?;
+    assert_eq!(
+        verify_signature(b, &m0, &m1, &sigma, &alpha, &beta),
+        Err(Error::InvalidSignature)
+    );
+    assert_eq!(
+        verify_signature(&same_secret_no_dst.public_key, &m0, &m1, &sigma, &alpha, &beta),
+        Err(Error::InvalidSignature)
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "cache")]
+#[test]
+fn verification_cache_answers_repeats() -> Result<(), Error> {
+    use crate::bls12_381_plain::VerificationCache;
+    use core::num::NonZeroUsize;
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+    let signature = sign_in_process(&key_pair, m0, m1, rng, rng)?;
+    let mut cache = VerificationCache::new(NonZeroUsize::new(2).unwrap());
+
+    cache.verify_cached(pk, &m0, &m1, &signature)?;
+    assert_eq!(cache.hits(), 0);
+    cache.verify_cached(pk, &m0, &m1, &signature)?;
+    assert_eq!(cache.hits(), 1);
+
+    // A failure is cached under its own inputs and does not affect the valid entry
+    let wrong = m1 + Scalar::one();
+    assert_eq!(
+        cache.verify_cached(pk, &m0, &wrong, &signature),
+        Err(Error::InvalidSignature)
+    );
+    assert_eq!(
+        cache.verify_cached(pk, &m0, &wrong, &signature),
+        Err(Error::InvalidSignature)
+    );
+    assert_eq!(cache.hits(), 2);
+    cache.verify_cached(pk, &m0, &m1, &signature)?;
+    assert_eq!((cache.hits(), cache.len()), (3, 2));
+
+    // The least recently used entry is evicted
+    let other = sign_in_process(&key_pair, m0, m1, rng, rng)?;
+    cache.verify_cached(pk, &m0, &m1, &other)?;
+    assert_eq!(
+        cache.verify_cached(pk, &m0, &wrong, &signature),
+        Err(Error::InvalidSignature)
+    );
+    assert_eq!((cache.hits(), cache.len()), (3, 2));
+
+    Ok(())
+}
+
+#[test]
+fn public_keys_with_an_identity_point_are_rejected() -> Result<(), Error> {
+    let pk = KeyPair::generate(rand_core::OsRng).public_key;
+
+    let identities: [fn(&mut PublicKey); 9] = [
+        |pk| pk.g1 = G1Affine::identity(),
+        |pk| pk.h1 = G1Affine::identity(),
+        |pk| pk.u1 = G1Affine::identity(),
+        |pk| pk.v1 = G1Affine::identity(),
+        |pk| pk.g2 = G2Affine::identity(),
+        |pk| pk.h2 = G2Affine::identity(),
+        |pk| pk.u2 = G2Affine::identity(),
+        |pk| pk.v2 = G2Affine::identity(),
+        |pk| pk.w2 = G2Affine::identity(),
+    ];
+    for set_identity in identities {
+        let mut corrupted = pk;
+        set_identity(&mut corrupted);
+        assert_eq!(
+            PublicKey::from_bytes(&corrupted.to_bytes()),
+            Err(Error::InvalidPublicKey)
+        );
+        assert_eq!(
+            PublicKey::decode(Encoding::Uncompressed, &corrupted.encode(Encoding::Uncompressed)),
+            Err(Error::InvalidPublicKey)
+        );
+        assert_eq!(corrupted.validate(), Err(Error::InvalidPublicKey));
+    }
+
+    let mut no_w2 = pk.verifier_key();
+    no_w2.w2 = G2Affine::identity();
+    assert_eq!(VerifierKey::from_bytes(&no_w2.to_bytes()), Err(Error::InvalidPublicKey));
+
+    // Adding the negated key cancels w2
+    let negated = PublicKey { w2: -pk.w2, ..pk };
+    assert_eq!(pk.combine(&negated), Err(Error::InvalidPublicKey));
+
+    Ok(())
+}
+
+#[test]
+fn combine_adds_the_secrets_of_keys_over_shared_generators() {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = key_pair.public_key;
+    let x = Scalar::random(&mut rng);
+    let other = PublicKey {
+        w2: G2Affine::from(pk.g2 * x),
+        ..pk
+    };
+
+    let combined = pk.combine(&other).unwrap();
+    assert_eq!(combined.w2, G2Affine::from(pk.g2 * (key_pair.secret_key + x)));
+    assert_eq!(combined, other.combine(&pk).unwrap());
+    assert_eq!(PublicKey { w2: combined.w2, ..pk }, combined);
+
+    assert_eq!(
+        pk.combine(&KeyPair::generate(rng).public_key),
+        Err(Error::InvalidPublicKey)
+    );
+}
+
+#[test]
+fn verifier_key_verifies_like_the_public_key() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let verifier_key = VerifierKey::from_bytes(&pk.verifier_key().to_bytes())?;
+    assert_eq!(verifier_key, VerifierKey::from(pk));
+
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+    let signature = sign_in_process(&key_pair, m0, m1, rng, rng)?;
+    verify_signature_with_verifier_key(&verifier_key, &m0, &m1, &signature)?;
+    assert_eq!(
+        verify_signature_with_verifier_key(&verifier_key, &m0, &(m1 + Scalar::one()), &signature),
+        Err(Error::InvalidSignature)
+    );
+    let identity = Signature {
+        sigma: G1Affine::identity(),
+        ..signature
+    };
+    assert_eq!(
+        verify_signature_with_verifier_key(&verifier_key, &m0, &m1, &identity),
+        Err(Error::InvalidSignature)
+    );
+
+    assert_eq!(
+        VerifierKey::from_bytes(&pk.to_bytes()[..VERIFIER_KEY_BYTES - 1]),
+        Err(Error::DecodingFailed)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn verify_signature_ct_agrees_with_verify_signature() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+    let signature = sign_in_process(&key_pair, m0, m1, rng, rng)?;
+
+    let mut off_curve = G2Affine::generator().to_uncompressed();
+    off_curve[191] ^= 1;
+    let cases = [
+        (m1, signature, true),
+        (m1 + Scalar::one(), signature, false),
+        (
+            m1,
+            Signature {
+                sigma: G1Affine::identity(),
+                ..signature
+            },
+            false,
+        ),
+        (
+            m1,
+            Signature {
+                alpha: G2Affine::from_uncompressed_unchecked(&off_curve).unwrap(),
+                ..signature
+            },
+            false,
+        ),
+    ];
+    for (m1, signature, valid) in cases {
+        assert_eq!(bool::from(verify_signature_ct(pk, &m0, &m1, &signature)), valid);
+        assert_eq!(
+            verify_signature(pk, &m0, &m1, &signature.sigma, &signature.alpha, &signature.beta).is_ok(),
+            valid
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn new_checked_compares_the_fingerprint() -> Result<(), Error> {
+    let rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let fingerprint = pk.fingerprint();
+    assert_eq!(fingerprint, PublicKey::from_bytes(&pk.to_bytes())?.fingerprint());
+    assert_ne!(fingerprint, KeyPair::generate(rng).public_key.fingerprint());
+
+    let user = User::new_checked(pk, &fingerprint, rng)?;
+    assert!(matches!(user.get_state(), UserState::ReadyToSetMessage));
+
+    let mut wrong = fingerprint;
+    wrong[31] ^= 1;
+    assert!(matches!(
+        User::new_checked(pk, &wrong, rng),
+        Err(Error::InvalidPublicKey)
+    ));
+
+    Ok(())
+}
+
+/// The little-endian encoding of the field modulus plus one, which is not canonical
+fn modulus_plus_one() -> [u8; 32] {
+    let mut bytes = (-Scalar::one()).to_bytes();
+    let mut carry = 2u16;
+    for byte in bytes.iter_mut() {
+        let sum = *byte as u16 + carry;
+        *byte = sum as u8;
+        carry = sum >> 8;
+    }
+    bytes
+}
+
+#[test]
+fn decoders_reject_non_canonical_scalars() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let non_canonical = modulus_plus_one();
+    assert!(bool::from(Scalar::from_bytes(&non_canonical).is_none()));
+
+    let key_pair = KeyPair::generate(rng);
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+    let signature = sign_in_process(&key_pair, m0, m1, rng, rng)?;
+    let partial = PartialSignature {
+        y: G1Affine::generator(),
+        r: G2Affine::generator(),
+        l: Scalar::one(),
+    };
+
+    for offset in [0, 32, 64] {
+        let mut bytes = [0u8; WITNESS_BYTES];
+        bytes[offset..offset + 32].copy_from_slice(&non_canonical);
+        assert_eq!(Witness::from_bytes(&bytes), Err(Error::NonCanonicalScalar));
+    }
+    let mut bytes = partial.to_bytes();
+    bytes[144..].copy_from_slice(&non_canonical);
+    assert_eq!(PartialSignature::from_bytes(&bytes), Err(Error::NonCanonicalScalar));
+    let mut bytes = signature.to_bytes();
+    bytes[144..].copy_from_slice(&non_canonical);
+    assert_eq!(Signature::from_bytes(&bytes), Err(Error::NonCanonicalScalar));
+    assert!(matches!(
+        KeyPair::from_secret_bytes(&non_canonical),
+        Err(Error::NonCanonicalScalar)
+    ));
+
+    Ok(())
+}
+
+/// Hash `tag` to a point of $\mathbb{G_1}$ by try-and-increment on the $x$-coordinate followed by clearing the
+/// cofactor, so that nobody knows its discrete logarithm
+fn hash_to_g1(tag: &[u8]) -> G1Affine {
+    use sha2::Digest;
+
+    (0u32..)
+        .find_map(|counter| {
+            let mut x = [0u8; 48];
+            x.copy_from_slice(&sha2::Sha512::digest([tag, &counter.to_be_bytes()].concat())[..48]);
+            x[0] = (x[0] & 0b0001_1111) | 0b1000_0000;
+            Option::<G1Affine>::from(G1Affine::from_compressed_unchecked(&x))
+        })
+        .map(|point| G1Affine::from(G1Projective::from(point).clear_cofactor()))
+        .unwrap()
+}
+
+/// As [hash_to_g1] for $\mathbb{G_2}$
+fn hash_to_g2(tag: &[u8]) -> G2Affine {
+    use sha2::Digest;
+
+    (0u32..)
+        .find_map(|counter| {
+            let mut x = [0u8; 96];
+            for (i, half) in x.chunks_mut(48).enumerate() {
+                half.copy_from_slice(&sha2::Sha512::digest([tag, &counter.to_be_bytes(), &[i as u8]].concat())[..48]);
+                half[0] &= 0b0001_1111;
+            }
+            x[0] |= 0b1000_0000;
+            Option::<G2Affine>::from(G2Affine::from_compressed_unchecked(&x))
+        })
+        .map(|point| G2Affine::from(G2Projective::from(point).clear_cofactor()))
+        .unwrap()
+}
+
+#[test]
+fn public_key_builder_validates_generators() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    // Generators shared with another signer, whose exponents nobody else knows
+    let setup = KeyPair::generate(rng).public_key;
+    let secret_key = Scalar::random(&mut rng);
+
+    let builder = PublicKeyBuilder::new()
+        .g1(setup.g1)
+        .h1(setup.h1)
+        .u1(setup.u1)
+        .v1(setup.v1)
+        .g2(setup.g2)
+        .h2(setup.h2)
+        .u2(setup.u2)
+        .v2(setup.v2)
+        .w2(G2Affine::from(setup.g2 * secret_key));
+    let public_key = builder.build()?;
+
+    let key_pair = KeyPair { public_key, secret_key };
+    key_pair.verify_consistency()?;
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+    let signature = sign_in_process(&key_pair, m0, m1, rng, rng)?;
+    verify_signature(
+        &public_key,
+        &m0,
+        &m1,
+        &signature.sigma,
+        &signature.alpha,
+        &signature.beta,
+    )?;
+
+    // Independently hashed pairs cannot share an exponent
+    assert_eq!(
+        builder.h1(hash_to_g1(b"h1")).h2(hash_to_g2(b"h2")).build(),
+        Err(Error::InvalidPublicKey)
+    );
+    assert_eq!(builder.w2(G2Affine::identity()).build(), Err(Error::InvalidPublicKey));
+    assert_eq!(PublicKeyBuilder::new().build(), Err(Error::InvalidPublicKey));
+    let mut off_curve = setup.g1.to_uncompressed();
+    off_curve[95] ^= 0x01;
+    let off_curve = G1Affine::from_uncompressed_unchecked(&off_curve).unwrap();
+    assert_eq!(builder.v1(off_curve).build(), Err(Error::InvalidPublicKey));
+
+    Ok(())
+}
+
+#[test]
+fn prepare_rejects_inconsistent_generators() {
+    let key_pair = KeyPair::generate(rand_core::OsRng);
+    let mut pk = key_pair.public_key;
+    std::mem::swap(&mut pk.h2, &mut pk.u2);
+
+    assert!(matches!(pk.prepare(), Err(Error::InvalidPublicKey)));
+}
+
+#[test]
+fn set_message_bytes_agrees_on_info() -> Result<(), Error> {
+    let rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let mut user = User::new(&key_pair.public_key, rng);
+    let mut signer = Signer::new(&key_pair, rng);
+
+    user.set_message_bytes(b"withdraw 10", b"serial number")?;
+    signer.set_message_bytes(b"withdraw 10")?;
+
+    assert_eq!(user.info()?, signer.info()?);
+    assert_eq!(user.info()?, hash_to_scalar(INFO_DST, b"withdraw 10"));
+    assert_eq!(user.message()?, hash_to_scalar(MESSAGE_DST, b"serial number"));
+    assert_ne!(hash_to_scalar(INFO_DST, b"same"), hash_to_scalar(MESSAGE_DST, b"same"));
+
+    let commitment = user.commit()?;
+    let eta = signer.commit(commitment)?;
+    let witness = user.compute_witness(&eta)?;
+    signer.verify_witness(witness)?;
+
+    Ok(())
+}
+
+#[test]
+fn public_key_can_key_a_hash_map() {
+    let rng = rand_core::OsRng;
+
+    let first = KeyPair::generate(rng).public_key;
+    let second = KeyPair::generate(rng).public_key;
+
+    let mut counters = std::collections::HashMap::new();
+    *counters.entry(first).or_insert(0) += 1;
+    *counters.entry(second).or_insert(0) += 1;
+    *counters.entry(first).or_insert(0) += 1;
+
+    assert_eq!(counters.len(), 2);
+    assert_eq!(counters[&first], 2);
+    assert_eq!(counters[&second], 1);
+}
+
+/// An [RngCore] that yields zero bytes for the first `zeros` calls to `fill_bytes` and then defers to [OsRng]
+struct ZeroThenOsRng {
+    zeros: usize,
+}
+
+impl RngCore for ZeroThenOsRng {
+    fn next_u32(&mut self) -> u32 {
+        OsRng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        OsRng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if self.zeros > 0 {
+            self.zeros -= 1;
+            dest.fill(0);
+        } else {
+            OsRng.fill_bytes(dest);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for ZeroThenOsRng {}
+
+#[test]
+fn key_generation_resamples_a_zero_secret_key() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    for key_pair in [
+        KeyPair::generate(ZeroThenOsRng { zeros: 1 }),
+        KeyPair::generate_with_dst(ZeroThenOsRng { zeros: 1 }, b"DEPLOYMENT"),
+    ] {
+        assert!(!bool::from(key_pair.secret_key.is_zero()));
+        assert!(!bool::from(key_pair.public_key.w2.is_identity()));
+        key_pair.verify_consistency()?;
+
+        let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+        let signature = sign_in_process(&key_pair, m0, m1, rng, rng)?;
+        verify_signature(
+            &key_pair.public_key,
+            &m0,
+            &m1,
+            &signature.sigma,
+            &signature.alpha,
+            &signature.beta,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn try_generate_gives_up_on_an_rng_that_only_yields_zeros() {
+    let stuck = ZeroThenOsRng { zeros: usize::MAX };
+    assert!(matches!(KeyPair::try_generate(stuck), Err(Error::ScalarIsZero)));
+
+    let recovering = ZeroThenOsRng {
+        zeros: KEY_GENERATION_ATTEMPTS - 1,
+    };
+    assert!(KeyPair::try_generate(recovering).is_ok());
+}
+
+#[test]
+fn anonymous_tokens_verify_on_their_bytes() -> Result<(), Error> {
+    let key_pair = KeyPair::generate(rand_core::OsRng);
+    let other = KeyPair::generate(rand_core::OsRng);
+
+    let token = anonymous_token::issue(&key_pair, b"info", b"message")?;
+    token.verify(&key_pair.public_key)?;
+    assert_eq!(token.verify(&other.public_key), Err(Error::InvalidSignature));
+
+    let mut tampered = token.clone();
+    tampered.message = b"other message".to_vec();
+    assert_eq!(tampered.verify(&key_pair.public_key), Err(Error::InvalidSignature));
+
+    let mut corrupted = token.clone();
+    corrupted.signature[0] ^= 0x80;
+    assert!(corrupted.verify(&key_pair.public_key).is_err());
+
+    // Equal byte strings are hashed under different tags, so they are distinct messages
+    anonymous_token::issue(&key_pair, b"same", b"same")?.verify(&key_pair.public_key)?;
+
+    Ok(())
+}
+
+#[test]
+fn verify_against_keys_finds_the_signing_key() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+    let keys: Vec<KeyPair> = (0..3).map(|_| KeyPair::generate(rng)).collect();
+    let public_keys: Vec<PublicKey> = keys.iter().map(|key_pair| key_pair.public_key).collect();
+
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+    let signature = sign_in_process(&keys[1], m0, m1, rng, rng)?;
+
+    assert_eq!(verify_against_keys(&public_keys, &m0, &m1, &signature), Ok(1));
+    assert_eq!(
+        verify_against_keys(&public_keys[..1], &m0, &m1, &signature),
+        Err(Error::InvalidSignature)
+    );
+    assert_eq!(
+        verify_against_keys(&[], &m0, &m1, &signature),
+        Err(Error::InvalidSignature)
+    );
+    assert_eq!(
+        verify_against_keys(&public_keys, &m0, &(m1 + Scalar::one()), &signature),
+        Err(Error::InvalidSignature)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn signer_rejects_a_zero_witness_component() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let mut user = User::new(&key_pair.public_key, rng);
+    let mut signer = Signer::new(&key_pair, rng);
+
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+    user.set_message(m0, m1)?;
+    signer.set_message(m0)?;
+    let eta = signer.commit(user.commit()?)?;
+    let witness = user.compute_witness(&eta)?;
+
+    assert_eq!(
+        signer.verify_witness(Witness {
+            b2: Scalar::zero(),
+            ..witness
+        }),
+        Err(Error::ScalarIsZero)
+    );
+    assert!(matches!(
+        signer.get_state(),
+        SignerState::Aborted(AbortReason::Error(Error::ScalarIsZero))
+    ));
+
+    Ok(())
+}
+
+#[cfg(feature = "strict")]
+#[test]
+#[should_panic(expected = "strict: e(Y, w2 R) != e(X v1^l, g2)")]
+fn strict_signer_asserts_the_partial_signature() {
+    let mut rng = rand_core::OsRng;
+    let mut key_pair = KeyPair::generate(rng);
+    let mut user = User::new(&key_pair.public_key, rng);
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+    user.set_message(m0, m1).unwrap();
+    let commitment = user.commit().unwrap();
+
+    // A secret key that no longer matches w2, as a regression in the signing arithmetic would behave
+    key_pair.secret_key += Scalar::one();
+    let mut signer = Signer::new(&key_pair, rng);
+    signer.set_message(m0).unwrap();
+    let eta = signer.commit(commitment).unwrap();
+    signer.verify_witness(user.compute_witness(&eta).unwrap()).unwrap();
+    let _ = signer.sign();
+}
+
+#[test]
+fn sign_with_uses_the_given_nonces() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+    let (r, l) = (Scalar::from(7u64), Scalar::from(11u64));
+
+    let ready_signer = |user: &mut User<OsRng>| -> Result<(Signer<OsRng>, Commitment), Error> {
+        let mut signer = Signer::new(&key_pair, rng);
+        user.set_message(m0, m1)?;
+        signer.set_message(m0)?;
+        let commitment = user.commit()?;
+        let eta = signer.commit(commitment)?;
+        signer.verify_witness(user.compute_witness(&eta)?)?;
+        Ok((signer, commitment))
+    };
+
+    let mut user = User::new(pk, rng);
+    let (mut signer, commitment) = ready_signer(&mut user)?;
+    let partial = signer.sign_with(r, l)?;
+
+    let inverse = (key_pair.secret_key + r).invert().unwrap();
+    assert_eq!(partial.y, G1Affine::from((commitment.x + pk.v1 * l) * inverse));
+    assert_eq!(partial.r, G2Affine::from(pk.g2 * r));
+    assert_eq!(partial.l, l);
+    assert_eq!(
+        signer.sign_with(r, l),
+        Err(Error::WrongState {
+            expected: StateKind::ReadyToSign,
+            actual: StateKind::Signed
+        })
+    );
+
+    let signature = user.sign(&partial)?;
+    verify_signature(pk, &m0, &m1, &signature.sigma, &signature.alpha, &signature.beta)?;
+
+    for (r, l) in [(Scalar::zero(), l), (r, Scalar::zero()), (-key_pair.secret_key, l)] {
+        let (mut signer, _) = ready_signer(&mut User::new(pk, rng))?;
+        assert_eq!(signer.sign_with(r, l), Err(Error::ScalarIsZero));
+        assert!(matches!(
+            signer.get_state(),
+            SignerState::Aborted(AbortReason::Error(Error::ScalarIsZero))
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn decoders_map_failures_to_the_same_errors() {
+    let g1 = G1Affine::generator();
+    let g2 = G2Affine::generator();
+
+    assert_eq!(decode_g1(&g1.to_compressed()), Ok(g1));
+    assert_eq!(decode_g1(&g1.to_uncompressed()), Ok(g1));
+    assert_eq!(decode_g1(&g1.to_compressed()[..47]), Err(Error::DecodingFailed));
+    assert_eq!(decode_g1(&[0xff; 48]), Err(Error::PointNotOnCurve));
+    assert_eq!(decode_g2(&g2.to_compressed()), Ok(g2));
+    assert_eq!(decode_g2(&g2.to_uncompressed()), Ok(g2));
+    assert_eq!(decode_g2(&g2.to_compressed()[..95]), Err(Error::DecodingFailed));
+    assert_eq!(decode_g2(&[0xff; 96]), Err(Error::PointNotOnCurve));
+    assert_eq!(decode_scalar(&Scalar::one().to_bytes()), Ok(Scalar::one()));
+    assert_eq!(decode_scalar(&[0; 31]), Err(Error::DecodingFailed));
+    assert_eq!(decode_scalar(&[0xff; 32]), Err(Error::NonCanonicalScalar));
+
+    // The wire types report the failures of their parts the same way
+    let mut bytes = [0xff; SIGNATURE_BYTES];
+    assert_eq!(Signature::from_bytes(&bytes), Err(Error::PointNotOnCurve));
+    bytes[..48].copy_from_slice(&g1.to_compressed());
+    bytes[48..144].copy_from_slice(&g2.to_compressed());
+    assert_eq!(Signature::from_bytes(&bytes), Err(Error::NonCanonicalScalar));
+    assert_eq!(
+        Witness::from_bytes(&[0xff; WITNESS_BYTES]),
+        Err(Error::NonCanonicalScalar)
+    );
+}
+
+#[test]
+fn signature_with_zero_beta_verifies() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let mut user = User::new(pk, rng);
+    let mut signer = Signer::new(&key_pair, rng);
+
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+    user.set_message(m0, m1)?;
+    signer.set_message(m0)?;
+    let eta = signer.commit(user.commit()?)?;
+    signer.verify_witness(user.compute_witness(&eta)?)?;
+
+    // beta = s + l / t
+    let l = -(user.s * user.t);
+    let signature = user.sign(&signer.sign_with(Scalar::random(&mut rng), l)?)?;
+    assert_eq!(signature.beta, Scalar::zero());
+
+    verify_signature(pk, &m0, &m1, &signature.sigma, &signature.alpha, &signature.beta)?;
+    verify_signature_with_verifier_key(&pk.verifier_key(), &m0, &m1, &signature)?;
+    assert!(bool::from(verify_signature_ct(pk, &m0, &m1, &signature)));
+    assert_eq!(Signature::from_bytes(&signature.to_bytes())?, signature);
+
+    Ok(())
+}
+
+#[test]
+fn batch_verifier_matches_individual_verification() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+
+    let items = (0..4)
+        .map(|_| {
+            let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+            sign_in_process(&key_pair, m0, m1, rng, rng).map(|signature| (m0, m1, signature))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let mut tampered = items.clone();
+    tampered[2].2.beta += Scalar::one();
+    let mut malformed = items.clone();
+    malformed[1].2.sigma = G1Affine::identity();
+
+    for items in [&items, &tampered, &malformed] {
+        let individually = items.iter().all(|(m0, m1, signature)| {
+            verify_signature(pk, m0, m1, &signature.sigma, &signature.alpha, &signature.beta).is_ok()
+        });
+
+        let mut batch = BatchVerifier::new(pk, rng);
+        for (m0, m1, signature) in items {
+            batch.push(m0, m1, signature);
+        }
+        assert_eq!(batch.len(), items.len());
+        assert_eq!(batch.finalize().is_ok(), individually);
+    }
+    assert_eq!(BatchVerifier::new(pk, rng).finalize(), Ok(()));
+
+    // A valid signature under another key fails the batch
+    let other = KeyPair::generate(rng);
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+    let mut batch = BatchVerifier::new(pk, rng);
+    batch.push(&items[0].0, &items[0].1, &items[0].2);
+    batch.push(&m0, &m1, &sign_in_process(&other, m0, m1, rng, rng)?);
+    assert_eq!(batch.finalize(), Err(Error::InvalidSignature));
+
+    Ok(())
+}
+
+#[test]
+fn info_commitment_detects_a_different_m0_early() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let mut user = User::new(&key_pair.public_key, rng);
+    let mut signer = Signer::new(&key_pair, rng);
+
+    assert_eq!(
+        user.info_commitment(),
+        Err(Error::WrongState {
+            expected: StateKind::ReadyToCommit,
+            actual: StateKind::ReadyToSetMessage
+        })
+    );
+    assert_eq!(
+        signer.check_info_commitment(&[0; 32]),
+        Err(Error::WrongState {
+            expected: StateKind::ReadyToCommit,
+            actual: StateKind::ReadyToSetMessage
+        })
+    );
+
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+    user.set_message(m0, m1)?;
+    signer.set_message(m0 + Scalar::one())?;
+    assert_eq!(
+        signer.check_info_commitment(&user.info_commitment()?),
+        Err(Error::InfoMismatch)
+    );
+    assert!(matches!(signer.get_state(), SignerState::ReadyToCommit));
+
+    // The same m0 under another key commits differently
+    let other = KeyPair::generate(rng);
+    let mut other_user = User::new(&other.public_key, rng);
+    other_user.set_message(m0, m1)?;
+    assert_ne!(other_user.info_commitment()?, user.info_commitment()?);
+
+    signer.update_info(m0)?;
+    signer.check_info_commitment(&user.info_commitment()?)?;
+    let eta = signer.commit(user.commit()?)?;
+    signer.verify_witness(user.compute_witness(&eta)?)?;
+    user.sign(&signer.sign()?)?;
+
+    Ok(())
+}
+
+/// A [RandomnessSource] that is not an [RngCore] and logs every draw
+#[derive(Clone, Default)]
+struct Beacon {
+    draws: std::rc::Rc<std::cell::RefCell<Vec<Scalar>>>,
+}
+
+impl RandomnessSource for Beacon {
+    fn next_scalar(&mut self) -> Scalar {
+        let scalar = Scalar::random(OsRng);
+        self.draws.borrow_mut().push(scalar);
+        scalar
+    }
+}
+
+#[test]
+fn randomness_source_supplies_every_draw() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let (user_beacon, signer_beacon) = (Beacon::default(), Beacon::default());
+    let mut user = User::new(&key_pair.public_key, user_beacon.clone());
+    let mut signer = Signer::new(&key_pair, signer_beacon.clone());
+
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+    user.set_message(m0, m1)?;
+    signer.set_message(m0)?;
+    let eta = signer.commit(user.commit()?)?;
+    let witness = user.compute_witness(&eta)?;
+    signer.verify_witness(witness)?;
+    let signature = user.sign(&signer.sign()?)?;
+    verify_signature(
+        &key_pair.public_key,
+        &m0,
+        &m1,
+        &signature.sigma,
+        &signature.alpha,
+        &signature.beta,
+    )?;
+
+    let user_draws = user_beacon.draws.borrow();
+    assert_eq!(*user_draws, [user.a1, user.a2, user.a3, user.s, user.t, user.f]);
+    let signer_draws = signer_beacon.draws.borrow();
+    assert_eq!(*signer_draws, [*eta.eta(), signer.l, signer.r]);
+
+    Ok(())
+}
+
+#[test]
+fn compute_y_is_the_signers_equation() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let mut user = User::new(pk, rng);
+    let mut signer = Signer::new(&key_pair, rng);
+
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+    user.set_message(m0, m1)?;
+    signer.set_message(m0)?;
+    let commitment = user.commit()?;
+    let eta = signer.commit(commitment)?;
+    signer.verify_witness(user.compute_witness(&eta)?)?;
+    let partial = signer.sign()?;
+
+    assert_eq!(
+        compute_Y(pk, &key_pair.secret_key, &commitment.x, &partial.l, &signer.r),
+        Ok(partial.y)
+    );
+    assert_eq!(
+        compute_Y(
+            pk,
+            &key_pair.secret_key,
+            &commitment.x,
+            &partial.l,
+            &-key_pair.secret_key
+        ),
+        Err(Error::ScalarIsZero)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn signer_state_round_trips_through_a_checkpoint() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+    let (mut user, mut signer) = parties_with_messages(&key_pair, m0, m1, rng, rng)?;
+
+    let eta = signer.commit(user.commit()?)?;
+    let at_witness = signer.save_state()?;
+    signer.verify_witness(user.compute_witness(&eta)?)?;
+    let at_sign = signer.save_state()?;
+    let latest = signer.epoch();
+
+    // Resume after a restart and finish the flow
+    let mut signer = Signer::restore_state(&key_pair, &at_sign, latest, rng)?;
+    assert!(matches!(signer.get_state(), SignerState::ReadyToSign));
+    assert_eq!(signer.epoch(), latest);
+    assert_eq!(signer.info(), Ok(m0));
+    let signature = user.sign(&signer.sign()?)?;
+    verify_signature(pk, &m0, &m1, &signature.sigma, &signature.alpha, &signature.beta)?;
+    assert_eq!(
+        signer.save_state(),
+        Err(Error::WrongState {
+            expected: StateKind::ReadyToSign,
+            actual: StateKind::Signed
+        })
+    );
+
+    // Once the flow has moved on, neither checkpoint can resume it for a second partial signature
+    let latest = signer.epoch();
+    assert_eq!(
+        Signer::restore_state(&key_pair, &at_witness, latest, rng).err(),
+        Some(Error::StaleCheckpoint)
+    );
+    assert_eq!(
+        Signer::restore_state(&key_pair, &at_sign, latest, rng).err(),
+        Some(Error::StaleCheckpoint)
+    );
+
+    // The state and the epoch cannot be altered, and the checkpoint is bound to the key
+    let mut earlier = at_sign.clone();
+    earlier[1] = at_witness[1];
+    assert_eq!(
+        Signer::restore_state(&key_pair, &earlier, 0, rng).err(),
+        Some(Error::DecodingFailed)
+    );
+    let mut rewound = at_witness.clone();
+    rewound[4 + SESSION_ID_BYTES] = u8::MAX;
+    assert_eq!(
+        Signer::restore_state(&key_pair, &rewound, latest, rng).err(),
+        Some(Error::DecodingFailed)
+    );
+    let other = KeyPair::generate(rng);
+    assert_eq!(
+        Signer::restore_state(&other, &at_sign, 0, rng).err(),
+        Some(Error::DecodingFailed)
+    );
+    assert_eq!(
+        Signer::restore_state(&key_pair, &at_sign[1..], 0, rng).err(),
+        Some(Error::DecodingFailed)
+    );
+    assert!(matches!(
+        Signer::restore_state(&key_pair, &at_witness, 0, rng)?.get_state(),
+        SignerState::ReadyToVerifyWitness
+    ));
+    assert_eq!(
+        Signer::new_deterministic(&key_pair, b"session").save_state(),
+        Err(Error::Unsupported)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn wrong_state_names_the_expected_and_actual_state() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let mut signer = Signer::new(&key_pair, rng);
+    let mut user = User::new(&key_pair.public_key, rng);
+
+    let m0 = Scalar::random(&mut rng);
+    user.set_message(m0, Scalar::random(&mut rng))?;
+    signer.set_message(m0)?;
+    signer.commit(user.commit()?)?;
+    let partial = PartialSignature {
+        y: G1Affine::generator(),
+        r: G2Affine::generator(),
+        l: Scalar::one(),
+    };
+
+    let error = user.sign(&partial).unwrap_err();
+    assert_eq!(
+        error,
+        Error::WrongState {
+            expected: StateKind::ReadyToSign,
+            actual: StateKind::ReadyToComputeWitness
+        }
+    );
+    assert_eq!(
+        format!("{error:?}"),
+        "WrongState { expected: ReadyToSign, actual: ReadyToComputeWitness }"
+    );
+    assert!(matches!(user.get_state(), UserState::ReadyToComputeWitness));
+    assert_eq!(UserState::Aborted(AbortReason::UserRequested).name(), "Aborted");
+    assert_eq!(signer.get_state().name(), "ReadyToVerifyWitness");
+
+    // The Signer reports the same way, and an aborted state matches whatever its reason
+    assert_eq!(
+        signer.sign(),
+        Err(Error::WrongState {
+            expected: StateKind::ReadyToSign,
+            actual: StateKind::ReadyToVerifyWitness
+        })
+    );
+    signer.abort();
+    assert!(matches!(
+        signer.set_message(m0),
+        Err(Error::WrongState {
+            actual: StateKind::Aborted,
+            ..
+        })
+    ));
+    assert_eq!(
+        SignerState::Aborted(AbortReason::Error(Error::ScalarIsZero)).kind(),
+        StateKind::Aborted
+    );
+
+    let multi_key_pair = MultiMessageKeyPair::generate(2, rng);
+    let mut multi_signer = MultiMessageSigner::new(&multi_key_pair, rng);
+    assert_eq!(
+        multi_signer.sign(),
+        Err(Error::WrongState {
+            expected: StateKind::ReadyToSign,
+            actual: StateKind::ReadyToSetMessage
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn deterministic_batch_verification_matches_batch_verify() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+
+    let m0 = Scalar::random(&mut rng);
+    let items = (0..4)
+        .map(|_| {
+            let m1 = Scalar::random(&mut rng);
+            sign_in_process(&key_pair, m0, m1, rng, rng).map(|signature| (m0, m1, signature))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let mut tampered = items.clone();
+    tampered[3].1 += Scalar::one();
+    let mut malformed = items.clone();
+    malformed[0].2.alpha = G2Affine::identity();
+    malformed[0].2.sigma = G1Affine::identity();
+    let mut swapped = items.clone();
+    swapped[0].2 = items[1].2;
+
+    for items in [&items, &tampered, &malformed, &swapped] {
+        let individually = items.iter().all(|(m0, m1, signature)| {
+            verify_signature(pk, m0, m1, &signature.sigma, &signature.alpha, &signature.beta).is_ok()
+        });
+
+        assert_eq!(batch_verify(pk, items, rng).is_ok(), individually);
+        assert_eq!(batch_verify_deterministic(pk, items).is_ok(), individually);
+        assert_eq!(
+            batch_verify_deterministic(pk, items),
+            batch_verify_deterministic(pk, items)
+        );
+    }
+    assert_eq!(batch_verify_deterministic(pk, &[]), Ok(()));
+
+    Ok(())
+}
+
+#[test]
+fn states_report_their_step() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let mut signer = Signer::new(&key_pair, rng);
+    let mut user = User::new(&key_pair.public_key, rng);
+    let steps = |signer: &Signer<OsRng>, user: &User<OsRng>| (signer.get_state().step(), user.get_state().step());
+
+    assert_eq!(steps(&signer, &user), (0, 0));
+    let m0 = Scalar::random(&mut rng);
+    signer.set_message(m0)?;
+    user.set_message(m0, Scalar::random(&mut rng))?;
+    assert_eq!(steps(&signer, &user), (1, 1));
+    let eta = signer.commit(user.commit()?)?;
+    assert_eq!(steps(&signer, &user), (2, 2));
+    signer.verify_witness(user.compute_witness(&eta)?)?;
+    assert_eq!(steps(&signer, &user), (3, 3));
+    assert!(!signer.get_state().is_terminal() && !user.get_state().is_terminal());
+    user.sign(&signer.sign()?)?;
+    assert_eq!(steps(&signer, &user), (4, 4));
+    assert!(signer.get_state().is_terminal() && user.get_state().is_terminal());
+
+    signer.reset()?;
+    user.reset()?;
+    signer.abort();
+    user.abort();
+    assert_eq!(steps(&signer, &user), (STEP_ABORTED, STEP_ABORTED));
+    assert!(signer.get_state().is_terminal() && user.get_state().is_terminal());
+
+    Ok(())
+}
+
+#[test]
+fn verify_common_info_matches_verify_signature() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+
+    let m0 = Scalar::random(&mut rng);
+    let items = (0..4)
+        .map(|_| {
+            let m1 = Scalar::random(&mut rng);
+            sign_in_process(&key_pair, m0, m1, rng, rng).map(|signature| (m1, signature))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    assert_eq!(verify_common_info(pk, &m0, &items), Ok(()));
+    assert_eq!(verify_common_info(pk, &m0, &[]), Ok(()));
+
+    let mut tampered = items.clone();
+    tampered[2].1.beta += Scalar::one();
+    let mut malformed = items.clone();
+    malformed[1].1.sigma = G1Affine::identity();
+    for items in [&tampered, &malformed] {
+        assert!(items.iter().any(|(m1, signature)| {
+            verify_signature(pk, &m0, m1, &signature.sigma, &signature.alpha, &signature.beta).is_err()
+        }));
+        assert_eq!(verify_common_info(pk, &m0, items), Err(Error::InvalidSignature));
+    }
+    assert_eq!(
+        verify_common_info(pk, &(m0 + Scalar::one()), &items),
+        Err(Error::InvalidSignature)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn signer_bound_to_a_session_rejects_another_sessions_messages() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let (session_a, session_b) = ([0xa; SESSION_ID_BYTES], [0xb; SESSION_ID_BYTES]);
+
+    let start = |rng: &mut OsRng| -> Result<(Signer<OsRng>, User<OsRng>), Error> {
+        let mut signer = Signer::new_with_session(&key_pair, *rng, session_a);
+        let mut user = User::new(pk, *rng);
+        let m0 = Scalar::random(&mut *rng);
+        signer.set_message(m0)?;
+        user.set_message(m0, Scalar::random(&mut *rng))?;
+        Ok((signer, user))
+    };
+    let aborted = |signer: &Signer<OsRng>| {
+        matches!(
+            signer.get_state(),
+            SignerState::Aborted(AbortReason::Error(Error::SessionMismatch))
+        )
+    };
+
+    // Session a runs to completion, including through a checkpoint
+    let (mut signer, mut user) = start(&mut rng)?;
+    assert_eq!(signer.session_id(), Some(&session_a));
+    let eta = signer.commit_in_session(&session_a, user.commit()?)?;
+    let mut signer = Signer::restore_state(&key_pair, &signer.save_state()?, signer.epoch(), rng)?;
+    assert_eq!(signer.session_id(), Some(&session_a));
+    signer.verify_witness_in_session(&session_a, user.compute_witness(&eta)?)?;
+    user.sign(&signer.sign()?)?;
+
+    // A commitment of session b, or one without a session, aborts
+    let (mut signer, mut user) = start(&mut rng)?;
+    assert_eq!(
+        signer.commit_in_session(&session_b, user.commit()?),
+        Err(Error::SessionMismatch)
+    );
+    assert!(aborted(&signer));
+    let (mut signer, mut user) = start(&mut rng)?;
+    assert_eq!(signer.commit(user.commit()?), Err(Error::SessionMismatch));
+    assert!(aborted(&signer));
+
+    // A witness of session b interleaved after session a's commitment aborts
+    let (mut signer, mut user) = start(&mut rng)?;
+    let mut other = User::new(pk, rng);
+    other.set_message(signer.info()?, Scalar::random(&mut rng))?;
+    let eta = signer.commit_in_session(&session_a, user.commit()?)?;
+    other.commit()?;
+    assert_eq!(
+        signer.verify_witness_in_session(&session_b, other.compute_witness(&eta)?),
+        Err(Error::SessionMismatch)
+    );
+    assert!(aborted(&signer));
+
+    // A Signer without a session accepts no session id
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+    let (mut user, mut signer) = parties_with_messages(&key_pair, m0, m1, rng, rng)?;
+    assert_eq!(signer.session_id(), None);
+    assert_eq!(
+        signer.commit_in_session(&session_a, user.commit()?),
+        Err(Error::SessionMismatch)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn points_outside_the_subgroup_are_rejected() -> Result<(), Error> {
+    // Compressed encodings with a small x coordinate, which are on the curve for about half of all x but almost
+    // never in the prime-order subgroup
+    let g1 = (1u8..)
+        .find_map(|x| {
+            let mut bytes = [0u8; 48];
+            bytes[0] = 0x80;
+            bytes[47] = x;
+            Option::<G1Affine>::from(G1Affine::from_compressed_unchecked(&bytes))
+        })
+        .expect("a point on the curve");
+    let g2 = (1u8..)
+        .find_map(|x| {
+            let mut bytes = [0u8; 96];
+            bytes[0] = 0x80;
+            bytes[95] = x;
+            Option::<G2Affine>::from(G2Affine::from_compressed_unchecked(&bytes))
+        })
+        .expect("a point on the curve");
+    assert!(bool::from(g1.is_on_curve() & !g1.is_torsion_free()));
+    assert!(bool::from(g2.is_on_curve() & !g2.is_torsion_free()));
+
+    assert_eq!(decode_g1(&g1.to_compressed()), Err(Error::NotInSubgroup));
+    assert_eq!(decode_g1(&g1.to_uncompressed()), Err(Error::NotInSubgroup));
+    assert_eq!(decode_g2(&g2.to_compressed()), Err(Error::NotInSubgroup));
+    assert_eq!(decode_g2(&g2.to_uncompressed()), Err(Error::NotInSubgroup));
+
+    let key_pair = KeyPair::generate(rand_core::OsRng);
+    let pk = PublicKey {
+        h1: g1,
+        ..key_pair.public_key
+    };
+    assert_eq!(pk.validate(), Err(Error::NotInSubgroup));
+    assert_eq!(PublicKey::from_bytes(&pk.to_bytes()), Err(Error::NotInSubgroup));
+    assert_eq!(
+        PublicKey::decode(Encoding::Uncompressed, &pk.encode(Encoding::Uncompressed)),
+        Err(Error::NotInSubgroup)
+    );
+
+    let signature = sign_in_process(
+        &key_pair,
+        Scalar::from(1),
+        Scalar::from(2),
+        rand_core::OsRng,
+        rand_core::OsRng,
+    )?;
+    let outside = Signature { alpha: g2, ..signature };
+    assert_eq!(Signature::from_bytes(&outside.to_bytes()), Err(Error::NotInSubgroup));
+    let outside = Commitment { w: g1, x: g1 };
+    assert_eq!(Commitment::from_bytes(&outside.to_bytes()), Err(Error::NotInSubgroup));
+
+    Ok(())
+}
+
+#[test]
+fn run_protocol_returns_the_finished_parties() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+    let (signature, signer, user) = run_protocol(&key_pair, m0, m1, rng, rng)?;
+    verify_signature(pk, &m0, &m1, &signature.sigma, &signature.alpha, &signature.beta)?;
+    assert!(matches!(signer.get_state(), SignerState::Signed));
+    assert!(matches!(user.get_state(), UserState::Signed));
+
+    // alpha = w2^(f - 1) R^f with R = g2^r
+    let alpha = pk.w2 * (user.f - Scalar::one()) + pk.g2 * (signer.r * user.f);
+    assert_eq!(G2Affine::from(alpha), signature.alpha);
+
+    assert_eq!(
+        run_protocol(&key_pair, m0, m0, rng, rng).err(),
+        Some(Error::DuplicateMessages)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn verify_signature_points_matches_verify_signature() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+    let signature = sign_in_process(&key_pair, m0, m1, rng, rng)?;
+    let (h2_m0, g2_m1) = (G2Affine::from(pk.h2 * m0), G2Affine::from(pk.g2 * m1));
+    assert_eq!(verify_signature_points(pk, &h2_m0, &g2_m1, &signature), Ok(()));
+
+    let other = Scalar::random(&mut rng);
+    let tampered = Signature {
+        beta: signature.beta + Scalar::one(),
+        ..signature
+    };
+    let malformed = Signature {
+        sigma: G1Affine::identity(),
+        ..signature
+    };
+    for (m0, m1, signature) in [
+        (other, m1, signature),
+        (m0, other, signature),
+        (m0, m1, tampered),
+        (m0, m1, malformed),
+    ] {
+        let (h2_m0, g2_m1) = (G2Affine::from(pk.h2 * m0), G2Affine::from(pk.g2 * m1));
+        assert_eq!(
+            verify_signature_points(pk, &h2_m0, &g2_m1, &signature),
+            verify_signature(pk, &m0, &m1, &signature.sigma, &signature.alpha, &signature.beta)
+        );
+        assert_eq!(
+            verify_signature_points(pk, &h2_m0, &g2_m1, &signature),
+            Err(Error::InvalidSignature)
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn challenge_bytes_round_trip_and_reject_zero() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let mut signer = Signer::new(&key_pair, rng);
+    let mut user = User::new(&key_pair.public_key, rng);
+
+    let m0 = Scalar::random(&mut rng);
+    user.set_message(m0, Scalar::random(&mut rng))?;
+    signer.set_message(m0)?;
+
+    let challenge = signer.commit(user.commit()?)?;
+    let bytes = challenge.to_bytes();
+    assert_eq!(bytes.len(), CHALLENGE_BYTES);
+    assert_eq!(Challenge::from_bytes(&bytes)?, challenge);
+
+    assert_eq!(Challenge::from_bytes(&[0u8; CHALLENGE_BYTES]), Err(Error::ScalarIsZero));
+    assert_eq!(
+        Challenge::from_bytes(&[0xff; CHALLENGE_BYTES]),
+        Err(Error::NonCanonicalScalar)
+    );
+    assert_eq!(Challenge::from_bytes(&bytes[1..]), Err(Error::DecodingFailed));
+
+    Ok(())
+}
+
+#[test]
+fn degenerate_alpha_is_rejected() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    // The test knows the exponents of the generators, which lets it solve for the values an attacker would need
+    let (g1, g2) = (G1Affine::generator(), G2Affine::generator());
+    let (h, u, v) = (
+        Scalar::random(&mut rng),
+        Scalar::random(&mut rng),
+        Scalar::random(&mut rng),
+    );
+    let x = Scalar::random(&mut rng);
+    let public_key = PublicKeyBuilder::new()
+        .g1(g1)
+        .h1(G1Affine::from(g1 * h))
+        .u1(G1Affine::from(g1 * u))
+        .v1(G1Affine::from(g1 * v))
+        .g2(g2)
+        .h2(G2Affine::from(g2 * h))
+        .u2(G2Affine::from(g2 * u))
+        .v2(G2Affine::from(g2 * v))
+        .w2(G2Affine::from(g2 * x))
+        .build()?;
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+
+    let rejected_everywhere = |signature: &Signature| {
+        let Signature { sigma, alpha, beta } = signature;
+        assert_eq!(
+            verify_signature(&public_key, &m0, &m1, sigma, alpha, beta),
+            Err(Error::InvalidSignature)
+        );
+        assert!(!bool::from(verify_signature_ct(&public_key, &m0, &m1, signature)));
+        let prepared = public_key.prepare().unwrap();
+        assert_eq!(
+            verify_signature_prepared(&prepared, &m0, &m1, signature),
+            Err(Error::InvalidSignature)
+        );
+        assert_eq!(
+            verify_common_info(&public_key, &m0, &[(m1, *signature)]),
+            Err(Error::InvalidSignature)
+        );
+        assert_eq!(find_matching_message(&public_key, signature, &[(m0, m1)]), None);
+        assert_eq!(
+            batch_verify(&public_key, &[(m0, m1, *signature)], OsRng),
+            Err(Error::InvalidSignature)
+        );
+    };
+
+    // alpha = 1 / w2 makes the left-hand side 1 for any sigma, and the beta that makes the right-hand side 1 completes
+    // a forgery
+    let beta = -(h * m0 + m1 + u) * v.invert().unwrap();
+    let forged = Signature {
+        sigma: hash_to_g1(b"sigma"),
+        alpha: -public_key.w2,
+        beta,
+    };
+    assert!(pairing_holds(&public_key, &m0, &m1, &forged));
+    rejected_everywhere(&forged);
+
+    // alpha = 1 is a signature with s = 0, which an honest user produces only with negligible probability
+    let beta = Scalar::random(&mut rng);
+    let sigma = G1Affine::from(public_key.g1 * ((h * m0 + m1 + u + v * beta) * x.invert().unwrap()));
+    let identity_alpha = Signature {
+        sigma,
+        alpha: G2Affine::identity(),
+        beta,
+    };
+    assert!(pairing_holds(&public_key, &m0, &m1, &identity_alpha));
+    rejected_everywhere(&identity_alpha);
+
+    Ok(())
+}
+
+#[test]
+fn verification_receipt_is_stable_and_covers_every_input() -> Result<(), Error> {
+    use sha2::{Digest, Sha256};
+
+    let mut rng = rand_core::OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let other = KeyPair::generate(rng);
+    let (m0, m1, other_m) = (
+        Scalar::random(&mut rng),
+        Scalar::random(&mut rng),
+        Scalar::random(&mut rng),
+    );
+    let pk = &key_pair.public_key;
+
+    let signature = sign_in_process(&key_pair, m0, m1, rng, rng)?;
+    let receipt = verification_receipt(pk, &m0, &m1, &signature)?;
+    assert_eq!(verification_receipt(pk, &m0, &m1, &signature)?, receipt);
+
+    let mut envelope = RECEIPT_DST.len().to_be_bytes().to_vec();
+    envelope.extend_from_slice(RECEIPT_DST);
+    envelope.extend_from_slice(&serialize_for_verification(pk, &m0, &m1, &signature));
+    assert_eq!(<[u8; 32]>::from(Sha256::digest(&envelope)), receipt);
+
+    let others = [
+        verification_receipt(&other.public_key, &m0, &m1, &sign_in_process(&other, m0, m1, rng, rng)?)?,
+        verification_receipt(pk, &other_m, &m1, &sign_in_process(&key_pair, other_m, m1, rng, rng)?)?,
+        verification_receipt(pk, &m0, &other_m, &sign_in_process(&key_pair, m0, other_m, rng, rng)?)?,
+        verification_receipt(pk, &m0, &m1, &rerandomize_partially(pk, &signature, rng))?,
+    ];
+    for other in others {
+        assert_ne!(other, receipt);
+    }
+
+    assert_eq!(
+        verification_receipt(pk, &m0, &other_m, &signature),
+        Err(Error::InvalidSignature)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn signer_resamples_zero_eta() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let mut user = User::new(&key_pair.public_key, rng);
+    let mut signer = Signer::new(&key_pair, ZeroThenOsRng { zeros: 1 });
+
+    let m0 = Scalar::random(&mut rng);
+    let m1 = Scalar::random(&mut rng);
+    user.set_message(m0, m1)?;
+    signer.set_message(m0)?;
+
+    let eta = signer.commit(user.commit()?)?;
+    assert!(!bool::from(eta.eta().is_zero()));
+
+    Ok(())
+}
+
+#[test]
+fn user_rejects_zero_eta() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let mut user = User::new(&key_pair.public_key, rng);
+
+    user.set_message(Scalar::random(&mut rng), Scalar::random(&mut rng))?;
+    user.commit()?;
+
+    assert!(matches!(
+        user.compute_witness(&Challenge::from(Scalar::zero())),
+        Err(Error::ScalarIsZero)
+    ));
+    assert!(matches!(
+        user.get_state(),
+        UserState::Aborted(AbortReason::Error(Error::ScalarIsZero))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn fiat_shamir_signer_binds_eta_to_commitment() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let mut user = User::new(&key_pair.public_key, rng);
+    let mut signer = Signer::new_fiat_shamir(&key_pair, rng);
+
+    let m0 = Scalar::random(&mut rng);
+    let m1 = Scalar::random(&mut rng);
+    user.set_message(m0, m1)?;
+    signer.set_message(m0)?;
+
+    let commitment = user.commit()?;
+    let eta = signer.commit(commitment)?;
+    assert_eq!(
+        *eta.eta(),
+        fiat_shamir_challenge(&key_pair.public_key, &m0, &commitment)
+    );
+
+    let witness = user.compute_witness(&eta)?;
+    signer.verify_witness(witness)?;
+    #[allow(non_snake_case)]
+    let partial = signer.sign()?;
+    let signature = user.sign(&partial)?;
+    verify_signature(
+        &key_pair.public_key,
+        &m0,
+        &m1,
+        &signature.sigma,
+        &signature.alpha,
+        &signature.beta,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn commitment_proof_can_be_verified_offline() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let mut user = User::new(pk, rng);
+
+    let m0 = Scalar::random(&mut rng);
+    user.set_message(m0, Scalar::random(&mut rng))?;
+    let commitment = user.commit()?;
+    let eta = Scalar::random(&mut rng);
+    let witness = user.compute_witness(&Challenge::from(eta))?;
+
+    verify_commitment_proof(pk, &m0, &commitment.w, &commitment.x, &eta, &witness)?;
+    assert!(matches!(
+        verify_commitment_proof(pk, &m0, &commitment.w, &commitment.x, &(eta + Scalar::one()), &witness),
+        Err(Error::WitnessMismatch { .. })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn public_key_and_signature_round_trip() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = key_pair.public_key;
+    let signature = sign_in_process(&key_pair, Scalar::random(&mut rng), Scalar::random(&mut rng), rng, rng)?;
+
+    assert_eq!(PublicKey::from_bytes(&pk.to_bytes())?, pk);
+    assert_eq!(Signature::from_bytes(&signature.to_bytes())?, signature);
+
+    let mut bytes = signature.to_bytes();
+    bytes[175] = 0xff;
+    assert!(matches!(Signature::from_bytes(&bytes), Err(Error::NonCanonicalScalar)));
+
+    let mut bytes = pk.to_bytes();
+    bytes[200] ^= 0x01;
+    assert!(PublicKey::from_bytes(&bytes).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn aggregate_rejects_mixed_batch() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = key_pair.public_key;
+    let m0 = Scalar::random(&mut rng);
+
+    let mut sigs = Vec::new();
+    for _ in 0..4 {
+        let m1 = Scalar::random(&mut rng);
+        sigs.push((m1, sign_in_process(&key_pair, m0, m1, rng, rng)?));
+    }
+    let m1s: Vec<Scalar> = sigs.iter().map(|(m1, _)| *m1).collect();
+
+    let valid = aggregate(&sigs);
+    assert_eq!(valid.len(), 4);
+    valid.verify(&pk, &m0, &m1s)?;
+
+    // Messages may be supplied in any order
+    let reversed: Vec<Scalar> = m1s.iter().rev().copied().collect();
+    valid.verify(&pk, &m0, &reversed)?;
+
+    assert!(valid.verify(&pk, &Scalar::random(&mut rng), &m1s).is_err());
+    assert!(valid.verify(&pk, &m0, &m1s[..3]).is_err());
+
+    // One invalid signature fails the whole aggregate
+    let mut mixed = sigs.clone();
+    mixed[2].1.beta += Scalar::one();
+    assert!(matches!(
+        aggregate(&mixed).verify(&pk, &m0, &m1s),
+        Err(Error::InvalidSignature)
+    ));
+
+    // Duplicate messages are rejected even when every signature verifies
+    let duplicated = [sigs[0], sigs[0]];
+    assert!(verify_signature(&pk, &m0, &m1s[0], &sigs[0].1.sigma, &sigs[0].1.alpha, &sigs[0].1.beta).is_ok());
+    assert!(aggregate(&duplicated).verify(&pk, &m0, &[m1s[0], m1s[0]]).is_err());
+
+    assert!(aggregate(&[]).verify(&pk, &m0, &[]).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn constant_time_eq_matches_eq() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let other_key_pair = KeyPair::generate(rng);
+    let m0 = Scalar::random(&mut rng);
+    let m1 = Scalar::random(&mut rng);
+    let signature = sign_in_process(&key_pair, m0, m1, rng, rng)?;
+    let other = rerandomize_partially(&key_pair.public_key, &signature, rng);
+
+    assert!(bool::from(signature.ct_eq(&signature)));
+    assert!(!bool::from(signature.ct_eq(&other)));
+    assert!(bool::from(key_pair.public_key.ct_eq(&key_pair.public_key)));
+    assert!(!bool::from(key_pair.public_key.ct_eq(&other_key_pair.public_key)));
+
+    Ok(())
+}
+
+#[allow(non_snake_case)]
+#[test]
+fn transcript_records_every_message() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let mut user = User::new(pk, rng);
+    let mut signer = Signer::new(&key_pair, rng);
+    let mut transcript = Transcript::new();
+    assert_eq!(TRANSCRIPT_BYTES, 401);
+    assert_eq!(transcript.to_bytes(), [0u8; TRANSCRIPT_BYTES]);
+
+    let m0 = Scalar::random(&mut rng);
+    user.set_message(m0, Scalar::random(&mut rng))?;
+    signer.set_message(m0)?;
+
+    let commitment = user.commit()?;
+    transcript.record_commitment(&commitment);
+    let eta = signer.commit(commitment)?;
+    transcript.record_challenge(&eta);
+    let witness = user.compute_witness(&eta)?;
+    transcript.record_witness(&witness);
+    signer.verify_witness(witness)?;
+    let partial = signer.sign()?;
+    transcript.record_partial_signature(&partial);
+
+    // The recorded messages are enough to re-check the proof after the fact
+    let recorded = transcript.commitment().unwrap();
+    verify_commitment_proof(
+        pk,
+        &m0,
+        &recorded.w,
+        &recorded.x,
+        transcript.challenge().unwrap().eta(),
+        transcript.witness().unwrap(),
+    )?;
+    assert_eq!(transcript.partial_signature(), Some(&partial));
+
+    let bytes = transcript.to_bytes();
+    assert_eq!(bytes[0], 0b1111);
+    assert_eq!(&bytes[1..97], &commitment.to_bytes());
+    assert_eq!(&bytes[97..129], &eta.eta().to_bytes());
+    assert_eq!(&bytes[129..225], &witness.to_bytes());
+    assert_eq!(&bytes[225..401], &partial.to_bytes());
+
+    Ok(())
+}
+
+#[cfg(feature = "test-internals")]
+#[allow(non_snake_case)]
+#[test]
+fn test_internals_expose_intermediate_values() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let mut user = User::new(&key_pair.public_key, rng);
+    let mut signer = Signer::new(&key_pair, rng);
+
+    let m0 = Scalar::random(&mut rng);
+    user.set_message(m0, Scalar::random(&mut rng))?;
+    signer.set_message(m0)?;
+    let commitment = user.commit()?;
+    assert_eq!((user.debug_w(), user.debug_x()), (commitment.w, commitment.x));
+    let eta = signer.commit(commitment)?;
+    let witness = user.compute_witness(&eta)?;
+    signer.verify_witness(witness)?;
+    assert_eq!(
+        (signer.debug_b1(), signer.debug_b2(), signer.debug_b3()),
+        (witness.b1, witness.b2, witness.b3)
+    );
+    let partial = signer.sign()?;
+    assert_eq!(signer.debug_l(), partial.l);
+    assert_eq!(
+        bls12_381::G2Affine::from(key_pair.public_key.g2 * signer.debug_r()),
+        partial.r
+    );
+    let signature = user.sign(&partial)?;
+    let f = user.debug_f();
+    let alpha = key_pair.public_key.w2 * (f - Scalar::one()) + partial.r * f;
+    assert_eq!(bls12_381::G2Affine::from(alpha), signature.alpha);
+
+    Ok(())
+}
+
+#[allow(non_snake_case)]
+#[test]
+fn multi_message_happy_path() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = MultiMessageKeyPair::generate(3, rng);
+    let pk = &key_pair.public_key;
+    assert_eq!(pk.len(), 3);
+    assert!(pk.public_key.prepare().is_ok());
+
+    let mut user = MultiMessageUser::new(pk, rng);
+    let mut signer = MultiMessageSigner::new(&key_pair, rng);
+
+    let m0 = Scalar::random(&mut rng);
+    let m1 = [
+        Scalar::random(&mut rng),
+        Scalar::random(&mut rng),
+        Scalar::random(&mut rng),
+    ];
+
+    assert!(matches!(
+        user.set_message(m0, &m1[..2]),
+        Err(Error::MessageCountMismatch)
+    ));
+    assert!(matches!(
+        user.get_state(),
+        UserState::Aborted(AbortReason::Error(Error::MessageCountMismatch))
+    ));
+    let mut user = MultiMessageUser::new(pk, rng);
+    user.set_message(m0, &m1)?;
+    signer.set_message(m0)?;
+    let commitment = user.commit()?;
+    let eta = signer.commit(commitment)?;
+    let witness = user.compute_witness(&eta)?;
+    signer.verify_witness(witness)?;
+    let partial = signer.sign()?;
+    let signature = user.sign(&partial)?;
+
+    verify_multi_signature(pk, &m0, &m1, &signature)?;
+    assert!(verify_multi_signature(pk, &m0, &[m1[1], m1[0], m1[2]], &signature).is_err());
+    assert!(matches!(
+        verify_multi_signature(pk, &m0, &m1[..2], &signature),
+        Err(Error::MessageCountMismatch)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn multi_message_with_one_message_is_the_plain_scheme() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = MultiMessageKeyPair::generate(1, rng);
+    let pk = &key_pair.public_key;
+    let mut user = MultiMessageUser::new(pk, rng);
+    let mut signer = MultiMessageSigner::new(&key_pair, rng);
+
+    let m0 = Scalar::random(&mut rng);
+    let m1 = Scalar::random(&mut rng);
+    user.set_message(m0, &[m1])?;
+    signer.set_message(m0)?;
+    let eta = signer.commit(user.commit()?)?;
+    let mut witness = user.compute_witness(&eta)?;
+    witness.b2 += Scalar::one();
+    assert!(matches!(
+        signer.verify_witness(witness),
+        Err(Error::WitnessMismatch { .. })
+    ));
+
+    let mut user = MultiMessageUser::new(pk, rng);
+    let mut signer = MultiMessageSigner::new(&key_pair, rng);
+    user.set_message(m0, &[m1])?;
+    signer.set_message(m0)?;
+    let eta = signer.commit(user.commit()?)?;
+    signer.verify_witness(user.compute_witness(&eta)?)?;
+    let partial = signer.sign()?;
+    let signature = user.sign(&partial)?;
+
+    verify_signature(
+        &pk.public_key,
+        &m0,
+        &m1,
+        &signature.sigma,
+        &signature.alpha,
+        &signature.beta,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn multi_message_rejects_what_the_plain_scheme_rejects() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = MultiMessageKeyPair::generate(2, rng);
+    let pk = &key_pair.public_key;
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+
+    for (messages, error) in [
+        ([Scalar::zero(), m1], Error::ScalarIsZero),
+        ([m1, m0], Error::DuplicateMessages),
+    ] {
+        let mut user = MultiMessageUser::new(pk, rng);
+        assert_eq!(user.set_message(m0, &messages), Err(error));
+        assert!(matches!(user.get_state(), UserState::Aborted(AbortReason::Error(e)) if *e == error));
+    }
+    let mut user = MultiMessageUser::new(pk, rng);
+    assert_eq!(user.set_message(Scalar::zero(), &[m0, m1]), Err(Error::ScalarIsZero));
+    let mut signer = MultiMessageSigner::new(&key_pair, rng);
+    assert_eq!(signer.set_message(Scalar::zero()), Err(Error::ScalarIsZero));
+    assert!(matches!(
+        signer.get_state(),
+        SignerState::Aborted(AbortReason::Error(Error::ScalarIsZero))
+    ));
+
+    let mut user = MultiMessageUser::new(pk, rng);
+    let mut signer = MultiMessageSigner::new(&key_pair, rng);
+    user.set_message(m0, &[m1, m1])?;
+    signer.set_message(m0)?;
+    let eta = signer.commit(user.commit()?)?;
+    let mut witness = user.compute_witness(&eta)?;
+    witness.b2 = Scalar::zero();
+    assert_eq!(signer.verify_witness(witness), Err(Error::ScalarIsZero));
+    assert!(matches!(
+        signer.get_state(),
+        SignerState::Aborted(AbortReason::Error(Error::ScalarIsZero))
+    ));
+    assert_eq!(
+        signer.sign(),
+        Err(Error::WrongState {
+            expected: StateKind::ReadyToSign,
+            actual: StateKind::Aborted
+        })
+    );
+
+    Ok(())
+}
+
+/// A [RandomnessSource] whose `zero_at`-th draw (from zero) is zero
+struct ZeroAtDraw {
+    draws: usize,
+    zero_at: usize,
+}
+
+impl RandomnessSource for ZeroAtDraw {
+    fn next_scalar(&mut self) -> Scalar {
+        self.draws += 1;
+        match self.draws - 1 == self.zero_at {
+            true => Scalar::zero(),
+            false => Scalar::random(OsRng),
+        }
+    }
+}
+
+#[test]
+fn multi_message_signer_redraws_a_zero_l() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = MultiMessageKeyPair::generate(2, rng);
+    let pk = &key_pair.public_key;
+    let mut user = MultiMessageUser::new(pk, rng);
+    // eta is the first draw and l the second
+    let mut signer = MultiMessageSigner::new(&key_pair, ZeroAtDraw { draws: 0, zero_at: 1 });
+
+    let (m0, m1) = (
+        Scalar::random(&mut rng),
+        [Scalar::random(&mut rng), Scalar::random(&mut rng)],
+    );
+    user.set_message(m0, &m1)?;
+    signer.set_message(m0)?;
+    let eta = signer.commit(user.commit()?)?;
+    signer.verify_witness(user.compute_witness(&eta)?)?;
+    let partial = signer.sign()?;
+    assert!(!bool::from(partial.l.is_zero()));
+
+    verify_multi_signature(pk, &m0, &m1, &user.sign(&partial)?)
+}
+
+#[cfg(feature = "serde")]
+#[allow(non_snake_case)]
+#[test]
+fn serde_round_trips_every_message() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let mut user = User::new(&key_pair.public_key, rng);
+    let mut signer = Signer::new(&key_pair, rng);
+
+    let m0 = Scalar::random(&mut rng);
+    user.set_message(m0, Scalar::random(&mut rng))?;
+    signer.set_message(m0)?;
+
+    let commitment = user.commit()?;
+    let bytes = bincode::serialize(&commitment).unwrap();
+    assert_eq!(bytes, commitment.to_bytes());
+    assert_eq!(bincode::deserialize::<Commitment>(&bytes).unwrap(), commitment);
+
+    let eta = signer.commit(commitment)?;
+    let bytes = bincode::serialize(&eta).unwrap();
+    assert_eq!(bytes, eta.eta().to_bytes());
+    assert_eq!(bincode::deserialize::<Challenge>(&bytes).unwrap(), eta);
+
+    let witness = user.compute_witness(&eta)?;
+    let bytes = bincode::serialize(&witness).unwrap();
+    assert_eq!(bytes, witness.to_bytes());
+    assert_eq!(bincode::deserialize::<Witness>(&bytes).unwrap(), witness);
+
+    signer.verify_witness(witness)?;
+    let partial = signer.sign()?;
+    let bytes = bincode::serialize(&partial).unwrap();
+    assert_eq!(bytes, partial.to_bytes());
+    assert_eq!(bincode::deserialize::<PartialSignature>(&bytes).unwrap(), partial);
+
+    let signature = user.sign(&partial)?;
+    let bytes = bincode::serialize(&signature).unwrap();
+    assert_eq!(bytes, signature.to_bytes());
+    assert_eq!(bincode::deserialize::<Signature>(&bytes).unwrap(), signature);
+
+    // Scalars at or above the modulus are rejected rather than reduced
+    assert!(bincode::deserialize::<Challenge>(&[0xff; 32]).is_err());
+    let mut bytes = witness.to_bytes();
+    bytes[64..96].copy_from_slice(&[0xff; 32]);
+    assert!(bincode::deserialize::<Witness>(&bytes).is_err());
+    let mut bytes = signature.to_bytes();
+    bytes[144..176].copy_from_slice(&[0xff; 32]);
+    assert!(bincode::deserialize::<Signature>(&bytes).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn out_of_order_calls_keep_the_state_and_failures_abort() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let m0 = Scalar::random(&mut rng);
+
+    let mut off_curve = G1Affine::generator().to_uncompressed();
+    off_curve[95] ^= 0x01;
+    let off_curve = G1Affine::from_uncompressed_unchecked(&off_curve).unwrap();
+
+    // Every step called out of order fails without changing the state
+    let mut signer = Signer::new(&key_pair, rng);
+    let mut user = User::new(pk, rng);
+    let commitment = {
+        let mut user = User::new(pk, rng);
+        user.set_message(m0, Scalar::random(&mut rng))?;
+        user.commit()?
+    };
+    assert_eq!(
+        signer.commit(commitment),
+        Err(Error::WrongState {
+            expected: StateKind::ReadyToCommit,
+            actual: StateKind::ReadyToSetMessage
+        })
+    );
+    assert_eq!(
+        signer.sign(),
+        Err(Error::WrongState {
+            expected: StateKind::ReadyToSign,
+            actual: StateKind::ReadyToSetMessage
+        })
+    );
+    assert_eq!(
+        user.commit(),
+        Err(Error::WrongState {
+            expected: StateKind::ReadyToCommit,
+            actual: StateKind::ReadyToSetMessage
+        })
+    );
+    assert_eq!(
+        user.compute_witness(&Challenge::from(Scalar::one())),
+        Err(Error::WrongState {
+            expected: StateKind::ReadyToComputeWitness,
+            actual: StateKind::ReadyToSetMessage
+        })
+    );
+    assert!(matches!(signer.get_state(), SignerState::ReadyToSetMessage));
+    assert!(matches!(user.get_state(), UserState::ReadyToSetMessage));
+
+    signer.set_message(m0)?;
+    user.set_message(m0, Scalar::random(&mut rng))?;
+    assert_eq!(
+        signer.set_message(m0),
+        Err(Error::WrongState {
+            expected: StateKind::ReadyToSetMessage,
+            actual: StateKind::ReadyToCommit
+        })
+    );
+    assert_eq!(
+        signer.verify_witness(Witness::from_bytes(&[0; WITNESS_BYTES])?),
+        Err(Error::WrongState {
+            expected: StateKind::ReadyToVerifyWitness,
+            actual: StateKind::ReadyToCommit
+        })
+    );
+    assert_eq!(
+        user.set_message(m0, m0),
+        Err(Error::WrongState {
+            expected: StateKind::ReadyToSetMessage,
+            actual: StateKind::ReadyToCommit
+        })
+    );
+    let partial = PartialSignature {
+        y: G1Affine::generator(),
+        r: G2Affine::generator(),
+        l: Scalar::one(),
+    };
+    assert_eq!(
+        user.sign(&partial),
+        Err(Error::WrongState {
+            expected: StateKind::ReadyToSign,
+            actual: StateKind::ReadyToCommit
+        })
+    );
+    assert!(matches!(signer.get_state(), SignerState::ReadyToCommit));
+    assert!(matches!(user.get_state(), UserState::ReadyToCommit));
+
+    // Every failure of a step aborts
+    let aborted_signer = |signer: &Signer<OsRng>, expected: Error| matches!(signer.get_state(), SignerState::Aborted(AbortReason::Error(error)) if *error == expected);
+    let aborted_user = |user: &User<OsRng>, expected: Error| matches!(user.get_state(), UserState::Aborted(AbortReason::Error(error)) if *error == expected);
+
+    let mut user = User::new(pk, rng);
+    assert_eq!(user.set_message(m0, Scalar::zero()), Err(Error::ScalarIsZero));
+    assert!(aborted_user(&user, Error::ScalarIsZero));
+
+    let mut signer = Signer::new(&key_pair, rng);
+    signer.set_message(m0)?;
+    assert_eq!(
+        signer.commit(Commitment {
+            w: off_curve,
+            ..commitment
+        }),
+        Err(Error::PointNotOnCurve)
+    );
+    assert!(aborted_signer(&signer, Error::PointNotOnCurve));
+
+    let mut signer = Signer::new(&key_pair, rng);
+    signer.set_message(m0)?;
+    signer.commit(commitment)?;
+    assert!(matches!(
+        signer.verify_witness(Witness::from_bytes(&[1; WITNESS_BYTES])?),
+        Err(Error::WitnessMismatch { .. })
+    ));
+    assert!(matches!(
+        signer.get_state(),
+        SignerState::Aborted(AbortReason::Error(Error::WitnessMismatch { .. }))
+    ));
+
+    let mut user = User::new(pk, rng);
+    user.set_message(m0, Scalar::random(&mut rng))?;
+    user.commit()?;
+    assert_eq!(
+        user.compute_witness(&Challenge::from(Scalar::zero())),
+        Err(Error::ScalarIsZero)
+    );
+    assert!(aborted_user(&user, Error::ScalarIsZero));
+
+    let mut signer = Signer::new(&key_pair, rng);
+    let mut user = User::new(pk, rng);
+    signer.set_message(m0)?;
+    user.set_message(m0, Scalar::random(&mut rng))?;
+    let eta = signer.commit(user.commit()?)?;
+    signer.verify_witness(user.compute_witness(&eta)?)?;
+    let partial = PartialSignature {
+        y: G1Affine::identity(),
+        ..signer.sign()?
+    };
+    assert_eq!(user.sign(&partial), Err(Error::PointNotOnCurve));
+    assert!(aborted_user(&user, Error::PointNotOnCurve));
+
+    Ok(())
+}
+
+#[test]
+fn replay_guard_detects_a_reused_r() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let m0 = Scalar::random(&mut rng);
+    let guard = ReplayGuard::new();
+
+    // A cloned signer draws the same eta, r and l in both sessions
+    let mut signer = Signer::new(&key_pair, HmacDrbg::new(&[b"cloned signer"]));
+    signer.set_message(m0)?;
+    let mut signers = [signer.clone(), signer];
+
+    let mut users = [
+        User::new(pk, rng).with_replay_guard(&guard),
+        User::new(pk, rng).with_replay_guard(&guard),
+    ];
+    let mut partials = Vec::new();
+    for (user, signer) in users.iter_mut().zip(signers.iter_mut()) {
+        user.set_message(m0, Scalar::random(&mut rng))?;
+        let eta = signer.commit(user.commit()?)?;
+        signer.verify_witness(user.compute_witness(&eta)?)?;
+        partials.push(signer.sign()?);
+    }
+    assert_eq!(partials[0].r, partials[1].r);
+
+    users[0].sign(&partials[0])?;
+    assert!(guard.contains(&partials[0].r));
+    assert_eq!(users[1].sign(&partials[1]), Err(Error::ReplayDetected));
+    assert!(matches!(
+        users[1].get_state(),
+        UserState::Aborted(AbortReason::Error(Error::ReplayDetected))
+    ));
+
+    // Fresh nonces pass
+    let mut user = User::new(pk, rng).with_replay_guard(&guard);
+    let mut signer = Signer::new(&key_pair, rng);
+    user.set_message(m0, Scalar::random(&mut rng))?;
+    signer.set_message(m0)?;
+    let eta = signer.commit(user.commit()?)?;
+    signer.verify_witness(user.compute_witness(&eta)?)?;
+    user.sign(&signer.sign()?)?;
+    assert_eq!(guard.len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn user_rejects_equal_info_and_message() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let m = Scalar::random(&mut rng);
+
+    let mut user = User::new(pk, rng);
+    assert_eq!(user.set_message(m, m), Err(Error::DuplicateMessages));
+    assert!(matches!(
+        user.get_state(),
+        UserState::Aborted(AbortReason::Error(Error::DuplicateMessages))
+    ));
+
+    let mut user = User::new(pk, rng);
+    user.set_message(m, m + Scalar::one())?;
+    assert_eq!(user.update_info(m + Scalar::one()), Err(Error::DuplicateMessages));
+
+    let mut user = User::new(pk, rng);
+    user.set_message_bytes(b"same bytes", b"same bytes")?;
+    assert!(matches!(user.get_state(), UserState::ReadyToCommit));
+
+    Ok(())
+}
+
+#[test]
+fn precomputed_commitment_matches_commit() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+
+    // The same draws give the same commitment either way
+    let drbg = HmacDrbg::new(&[b"precompute"]);
+    let mut user = User::new(pk, drbg.clone());
+    user.set_message(m0, m1)?;
+    let expected = user.commit()?;
+    let mut user = User::new(pk, drbg);
+    let precomputed = user.precompute_commitment();
+    user.set_message(m0, m1)?;
+    assert_eq!(user.commit_with(precomputed)?, expected);
+
+    let mut user = User::new(pk, rng);
+    let mut signer = Signer::new(&key_pair, rng);
+    let precomputed = user.precompute_commitment();
+    let spare = user.precompute_commitment();
+    assert_eq!(
+        user.commit_with(spare),
+        Err(Error::WrongState {
+            expected: StateKind::ReadyToCommit,
+            actual: StateKind::ReadyToSetMessage
+        })
+    );
+    user.set_message(m0, m1)?;
+    signer.set_message(m0)?;
+    let eta = signer.commit(user.commit_with(precomputed)?)?;
+    signer.verify_witness(user.compute_witness(&eta)?)?;
+    let signature = user.sign(&signer.sign()?)?;
+    verify_signature(pk, &m0, &m1, &signature.sigma, &signature.alpha, &signature.beta)?;
+
+    let other = KeyPair::generate(rng);
+    let foreign = User::new(&other.public_key, rng).precompute_commitment();
+    let mut user = User::new(pk, rng);
+    user.set_message(m0, m1)?;
+    assert_eq!(user.commit_with(foreign), Err(Error::InvalidPublicKey));
+    assert!(matches!(
+        user.get_state(),
+        UserState::Aborted(AbortReason::Error(Error::InvalidPublicKey))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn blind_and_unblind_compose_into_a_signature() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+
+    let (mut user, mut signer) = parties(&key_pair, rng, rng);
+    let blinded = BlindedMessage::from_bytes(&user.blind(m0, m1)?.to_bytes())?;
+    assert!(matches!(user.get_state(), UserState::ReadyToSign));
+    assert_eq!(
+        user.blind(m0, m1),
+        Err(Error::WrongState {
+            expected: StateKind::ReadyToSetMessage,
+            actual: StateKind::ReadyToSign
+        })
+    );
+
+    // The Signer signs X once the application has proven knowledge of its opening, here with the proof of
+    // verify_commitment_proof built from the blinding factors
+    let pk = &key_pair.public_key;
+    let [a1, a2, a3] = [
+        Scalar::random(&mut rng),
+        Scalar::random(&mut rng),
+        Scalar::random(&mut rng),
+    ];
+    let w = G1Affine::from(pk.h1 * (m0 * a2) + pk.g1 * a1 + pk.u1 * a2 + pk.v1 * a3);
+    signer.set_message(m0)?;
+    let eta = *signer.commit(Commitment { w, x: blinded.x })?.eta();
+    signer.verify_witness(Witness {
+        b1: a1 + eta * m1 * user.t,
+        b2: a2 + eta * user.t,
+        b3: a3 + eta * user.s * user.t,
+    })?;
+    let token = signer.sign()?;
+    let signature = user.unblind(&token)?;
+    verify_signature(pk, &m0, &m1, &signature.sigma, &signature.alpha, &signature.beta)?;
+    assert_eq!(
+        user.unblind(&token),
+        Err(Error::WrongState {
+            expected: StateKind::ReadyToSign,
+            actual: StateKind::Signed
+        })
+    );
+
+    // unblind also finishes the four-step flow
+    let (mut user, mut signer) = parties_with_messages(&key_pair, m0, m1, rng, rng)?;
+    let eta = signer.commit(user.commit()?)?;
+    signer.verify_witness(user.compute_witness(&eta)?)?;
+    let signature = user.unblind(&signer.sign()?)?;
+    verify_signature(pk, &m0, &m1, &signature.sigma, &signature.alpha, &signature.beta)?;
+
+    let (mut user, _) = parties(&key_pair, rng, rng);
+    assert_eq!(user.blind(m0, m0), Err(Error::DuplicateMessages));
+    assert_eq!(
+        BlindedMessage::from_bytes(&[0; BLINDED_MESSAGE_BYTES - 1]),
+        Err(Error::DecodingFailed)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn update_info_is_only_allowed_before_the_commitment() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+    let renegotiated = Scalar::random(&mut rng);
+    let wrong_state = |actual: StateKind| -> Result<(), Error> {
+        Err(Error::WrongState {
+            expected: StateKind::ReadyToCommit,
+            actual,
+        })
+    };
+
+    let (mut user, mut signer) = parties(&key_pair, rng, rng);
+    assert_eq!(
+        signer.update_info(renegotiated),
+        wrong_state(StateKind::ReadyToSetMessage)
+    );
+    assert_eq!(
+        user.update_info(renegotiated),
+        wrong_state(StateKind::ReadyToSetMessage)
+    );
+
+    // Allowed between set_message and commit, keeping m1
+    let (mut user, mut signer) = parties_with_messages(&key_pair, m0, m1, rng, rng)?;
+    signer.update_info(renegotiated)?;
+    user.update_info(renegotiated)?;
+    assert_eq!(
+        (signer.info()?, user.info()?, user.message()?),
+        (renegotiated, renegotiated, m1)
+    );
+
+    let eta = signer.commit(user.commit()?)?;
+    assert_eq!(signer.update_info(m0), wrong_state(StateKind::ReadyToVerifyWitness));
+    assert_eq!(user.update_info(m0), wrong_state(StateKind::ReadyToComputeWitness));
+    signer.verify_witness(user.compute_witness(&eta)?)?;
+    assert_eq!(signer.update_info(m0), wrong_state(StateKind::ReadyToSign));
+    let signature = user.sign(&signer.sign()?)?;
+    assert_eq!(signer.update_info(m0), wrong_state(StateKind::Signed));
+    assert_eq!(user.update_info(m0), wrong_state(StateKind::Signed));
+    let Signature { sigma, alpha, beta } = signature;
+    verify_signature(&key_pair.public_key, &renegotiated, &m1, &sigma, &alpha, &beta)?;
+
+    // Both sides reject a zero m0 and abort
+    let (mut user, mut signer) = parties_with_messages(&key_pair, m0, m1, rng, rng)?;
+    assert_eq!(user.update_info(Scalar::zero()), Err(Error::ScalarIsZero));
+    assert_eq!(signer.update_info(Scalar::zero()), Err(Error::ScalarIsZero));
+    assert!(matches!(
+        user.get_state(),
+        UserState::Aborted(AbortReason::Error(Error::ScalarIsZero))
+    ));
+    assert!(matches!(
+        signer.get_state(),
+        SignerState::Aborted(AbortReason::Error(Error::ScalarIsZero))
+    ));
+    let (_, mut signer) = parties(&key_pair, rng, rng);
+    assert_eq!(signer.set_message(Scalar::zero()), Err(Error::ScalarIsZero));
+
+    Ok(())
+}
+
+/// One end of a pair of in-memory channels
+#[cfg(feature = "async")]
+struct ChannelTransport {
+    tx: futures::channel::mpsc::UnboundedSender<Vec<u8>>,
+    rx: futures::channel::mpsc::UnboundedReceiver<Vec<u8>>,
+}
+
+#[cfg(feature = "async")]
+impl ChannelTransport {
+    fn pair() -> (Self, Self) {
+        let (to_signer, from_user) = futures::channel::mpsc::unbounded();
+        let (to_user, from_signer) = futures::channel::mpsc::unbounded();
+        (
+            ChannelTransport {
+                tx: to_signer,
+                rx: from_signer,
+            },
+            ChannelTransport {
+                tx: to_user,
+                rx: from_user,
+            },
+        )
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl crate::bls12_381_plain::Transport for ChannelTransport {
+    async fn send(&mut self, frame: &[u8]) -> Result<(), Error> {
+        self.tx
+            .unbounded_send(frame.to_vec())
+            .map_err(|_| Error::TransportFailed)
+    }
+
+    async fn recv(&mut self) -> Result<Vec<u8>, Error> {
+        use futures::StreamExt;
+        self.rx.next().await.ok_or(Error::TransportFailed)
+    }
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn async_drivers_run_the_protocol_over_a_transport() -> Result<(), Error> {
+    use crate::bls12_381_plain::{run_signer, run_user};
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+
+    let mut signer = Signer::new(&key_pair, rng);
+    let mut user = User::new(pk, rng);
+    signer.set_message(m0)?;
+    user.set_message(m0, m1)?;
+    let (mut user_end, mut signer_end) = ChannelTransport::pair();
+    let (signature, signed) = futures::executor::block_on(async {
+        futures::join!(
+            run_user(&mut user, &mut user_end),
+            run_signer(&mut signer, &mut signer_end)
+        )
+    });
+    signed?;
+    let signature = signature?;
+    assert!(matches!(signer.get_state(), SignerState::Signed));
+    assert!(matches!(user.get_state(), UserState::Signed));
+    verify_signature(pk, &m0, &m1, &signature.sigma, &signature.alpha, &signature.beta)?;
+
+    // The signer has gone away, so the user cannot complete
+    let mut user = User::new(pk, rng);
+    user.set_message(m0, m1)?;
+    let (mut user_end, signer_end) = ChannelTransport::pair();
+    signer_end.tx.close_channel();
+    let result = futures::executor::block_on(run_user(&mut user, &mut user_end));
+    assert_eq!(result, Err(Error::TransportFailed));
+    assert!(matches!(
+        user.get_state(),
+        UserState::Aborted(AbortReason::Error(Error::TransportFailed))
+    ));
+
+    // A malformed frame aborts the signer
+    let mut signer = Signer::new(&key_pair, rng);
+    signer.set_message(m0)?;
+    let (mut user_end, mut signer_end) = ChannelTransport::pair();
+    futures::executor::block_on(crate::bls12_381_plain::Transport::send(&mut user_end, &[0u8; 3]))?;
+    let result = futures::executor::block_on(run_signer(&mut signer, &mut signer_end));
+    assert_eq!(result, Err(Error::DecodingFailed));
+    assert!(matches!(
+        signer.get_state(),
+        SignerState::Aborted(AbortReason::Error(Error::DecodingFailed))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn user_rejects_tampered_partial_signature() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let m0 = Scalar::random(&mut rng);
+
+    let mut off_curve = G1Affine::generator().to_uncompressed();
+    off_curve[95] ^= 0x01;
+    let off_curve = G1Affine::from_uncompressed_unchecked(&off_curve).unwrap();
+    assert!(!bool::from(off_curve.is_on_curve()));
+
+    let tamperings = [
+        |partial: &mut PartialSignature, off_curve: G1Affine| partial.y = off_curve,
+        |partial: &mut PartialSignature, _| partial.y = G1Affine::identity(),
+        |partial: &mut PartialSignature, _| partial.r = bls12_381::G2Affine::identity(),
+    ];
+
+    for tamper in tamperings {
+        let mut user = User::new(&key_pair.public_key, rng);
+        let mut signer = Signer::new(&key_pair, rng);
+        user.set_message(m0, Scalar::random(&mut rng))?;
+        signer.set_message(m0)?;
+        let eta = signer.commit(user.commit()?)?;
+        signer.verify_witness(user.compute_witness(&eta)?)?;
+
+        let mut partial = signer.sign()?;
+        tamper(&mut partial, off_curve);
+
+        assert!(matches!(user.sign(&partial), Err(Error::PointNotOnCurve)));
+        assert!(matches!(
+            user.get_state(),
+            UserState::Aborted(AbortReason::Error(Error::PointNotOnCurve))
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn user_detects_cheating_signer() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let mut user = User::new(&key_pair.public_key, rng);
+    let mut signer = Signer::new(&key_pair, rng);
+
+    let m0 = Scalar::random(&mut rng);
+    user.set_message(m0, Scalar::random(&mut rng))?;
+    signer.set_message(m0)?;
+    let eta = signer.commit(user.commit()?)?;
+    let witness = user.compute_witness(&eta)?;
+    signer.verify_witness(witness)?;
+    let partial = signer.sign()?;
+
+    let cheating = PartialSignature {
+        l: partial.l + Scalar::one(),
+        ..partial
+    };
+    assert!(matches!(user.verify_partial(&cheating), Err(Error::InvalidSignature)));
+    let cheating = PartialSignature {
+        y: G1Affine::from(partial.y * Scalar::from(2)),
+        ..partial
+    };
+    assert!(matches!(user.verify_partial(&cheating), Err(Error::InvalidSignature)));
+
+    user.verify_partial(&partial)?;
+    let signature = user.sign(&partial)?;
+    assert!(matches!(
+        user.verify_partial(&partial),
+        Err(Error::WrongState {
+            expected: StateKind::ReadyToSign,
+            actual: StateKind::Signed
+        })
+    ));
+    verify_signature(
+        &key_pair.public_key,
+        &m0,
+        &user.message()?,
+        &signature.sigma,
+        &signature.alpha,
+        &signature.beta,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn encodings_round_trip_and_reject_each_other() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = key_pair.public_key;
+    let signature = sign_in_process(&key_pair, Scalar::random(&mut rng), Scalar::random(&mut rng), rng, rng)?;
+
+    assert_eq!(Encoding::default(), Encoding::CompressedCanonical);
+    assert_eq!(pk.encode(Encoding::CompressedCanonical), pk.to_bytes());
+    assert_eq!(signature.encode(Encoding::CompressedCanonical), signature.to_bytes());
+
+    for (format, pk_len, signature_len) in [
+        (Encoding::CompressedCanonical, PUBLIC_KEY_BYTES, SIGNATURE_BYTES),
+        (
+            Encoding::Uncompressed,
+            PUBLIC_KEY_UNCOMPRESSED_BYTES,
+            SIGNATURE_UNCOMPRESSED_BYTES,
+        ),
+    ] {
+        let pk_bytes = pk.encode(format);
+        let signature_bytes = signature.encode(format);
+        assert_eq!((pk_bytes.len(), signature_bytes.len()), (pk_len, signature_len));
+        assert_eq!(PublicKey::decode(format, &pk_bytes)?, pk);
+        assert_eq!(Signature::decode(format, &signature_bytes)?, signature);
+        assert_eq!(
+            PublicKey::decode(format, &pk_bytes[..pk_len - 1]),
+            Err(Error::DecodingFailed)
+        );
+    }
+
+    // Across formats the lengths differ
+    let uncompressed = signature.encode(Encoding::Uncompressed);
+    assert_eq!(
+        Signature::decode(Encoding::CompressedCanonical, &uncompressed),
+        Err(Error::DecodingFailed)
+    );
+    assert_eq!(
+        PublicKey::decode(Encoding::Uncompressed, &pk.to_bytes()),
+        Err(Error::DecodingFailed)
+    );
+
+    // and so do the compression flags of points of the same size
+    let mut as_uncompressed = [signature.sigma.to_compressed(), signature.sigma.to_compressed()].concat();
+    as_uncompressed.extend_from_slice(&[0; 192 + 32]);
+    assert_eq!(
+        Signature::decode(Encoding::Uncompressed, &as_uncompressed),
+        Err(Error::PointNotOnCurve)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn hex_and_base64_round_trip() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = key_pair.public_key;
+    let signature = sign_in_process(&key_pair, Scalar::random(&mut rng), Scalar::random(&mut rng), rng, rng)?;
+
+    let hex = pk.to_hex();
+    assert_eq!(hex.len(), 1344);
+    assert_eq!(PublicKey::from_hex(&hex)?, pk);
+    assert_eq!(PublicKey::from_hex(&hex.to_uppercase())?, pk);
+
+    let hex = signature.to_hex();
+    assert_eq!(&hex[0..2], &format!("{:02x}", signature.to_bytes()[0]));
+    assert_eq!(Signature::from_hex(&hex)?, signature);
+    assert!(matches!(Signature::from_hex(&hex[2..]), Err(Error::DecodingFailed)));
+    assert!(matches!(
+        Signature::from_hex(&hex.replacen(&hex[0..1], "g", 1)),
+        Err(Error::DecodingFailed)
+    ));
+
+    #[cfg(feature = "base64")]
+    {
+        assert_eq!(PublicKey::from_base64(&pk.to_base64())?, pk);
+        let text = signature.to_base64();
+        assert_eq!(Signature::from_base64(&text)?, signature);
+        assert!(matches!(Signature::from_base64(&text[4..]), Err(Error::DecodingFailed)));
+        assert!(matches!(
+            Signature::from_base64("not base64!"),
+            Err(Error::DecodingFailed)
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "base64")]
+#[test]
+fn pem_round_trips_and_checks_the_label() -> Result<(), Error> {
+    use crate::bls12_381_plain::{PUBLIC_KEY_PEM_LABEL, SECRET_KEY_PEM_LABEL};
+
+    let key_pair = KeyPair::generate(rand_core::OsRng);
+    let pk = key_pair.public_key;
+
+    let pem = pk.to_pem();
+    assert!(pem.starts_with("-----BEGIN OKAMOTO PUBLIC KEY-----\n"));
+    assert!(pem.ends_with("\n-----END OKAMOTO PUBLIC KEY-----\n"));
+    assert!(pem.lines().all(|line| line.len() <= 64));
+    assert_eq!(PublicKey::from_pem(&pem)?, pk);
+    assert_eq!(PublicKey::from_pem(&pem.replace('\n', "\r\n"))?, pk);
+
+    let secret = key_pair.to_pem();
+    assert!(secret.as_str().starts_with("-----BEGIN OKAMOTO SECRET KEY-----\n"));
+    assert_eq!(KeyPair::from_pem(secret.as_str())?.public_key, pk);
+
+    for text in [
+        secret.as_str().replace(SECRET_KEY_PEM_LABEL, PUBLIC_KEY_PEM_LABEL),
+        pem.replacen("PUBLIC", "PRIVATE", 1),
+        pem.replacen("-----\n", "-----\n*", 1),
+        pem.lines()
+            .filter(|line| line.len() == 64)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        std::format!("{}{}", pem, pem),
+    ] {
+        assert_eq!(PublicKey::from_pem(&text), Err(Error::DecodingFailed), "{}", text);
+    }
+    assert!(matches!(KeyPair::from_pem(&pem), Err(Error::DecodingFailed)));
+
+    Ok(())
+}
+
+#[test]
+fn hmac_drbg_matches_sp800_90a() {
+    let mut drbg = HmacDrbg::new(&[b"seed", b"material"]);
+    drbg.reseed(&[b"more"]);
+
+    let mut output = [0u8; 40];
+    drbg.fill_bytes(&mut output);
+    assert_eq!(
+        output[..],
+        [
+            0x94, 0x12, 0x1d, 0x13, 0x1e, 0xc7, 0x86, 0x7e, 0x67, 0xae, 0xc1, 0x37, 0xb0, 0x91, 0xf7, 0x4d, 0xe5, 0x5e,
+            0xa9, 0x67, 0x97, 0xa7, 0x06, 0xe3, 0x98, 0x12, 0x6a, 0xf2, 0x97, 0x8d, 0xa7, 0x9a, 0x40, 0x38, 0x73, 0x6d,
+            0x89, 0xb6, 0x2f, 0x70,
+        ]
+    );
+    assert_eq!(
+        drbg.next_u64().to_le_bytes(),
+        [0x88, 0x57, 0x33, 0xdc, 0xd5, 0xdf, 0xf1, 0xbe]
+    );
+}
+
+#[test]
+fn deterministic_signer_is_reproducible() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let mut user = User::new(&key_pair.public_key, rng);
+    let m0 = Scalar::random(&mut rng);
+    let m1 = Scalar::random(&mut rng);
+    user.set_message(m0, m1)?;
+    let commitment = user.commit()?;
+
+    let mut first = Signer::new_deterministic(&key_pair, b"session-1");
+    let mut second = Signer::new_deterministic(&key_pair, b"session-1");
+    let mut other = Signer::new_deterministic(&key_pair, b"session-2");
+    for signer in [&mut first, &mut second, &mut other] {
+        signer.set_message(m0)?;
+    }
+
+    let eta = first.commit(commitment)?;
+    assert_eq!(second.commit(commitment)?, eta);
+    assert_ne!(other.commit(commitment)?, eta);
+
+    let witness = user.compute_witness(&eta)?;
+    first.verify_witness(witness)?;
+    second.verify_witness(witness)?;
+    let partial = first.sign()?;
+    assert_eq!(second.sign()?, partial);
+
+    let signature = user.sign(&partial)?;
+    verify_signature(
+        &key_pair.public_key,
+        &m0,
+        &m1,
+        &signature.sigma,
+        &signature.alpha,
+        &signature.beta,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn witness_errors_distinguish_zero_challenge_from_mismatch() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let mut user = User::new(pk, rng);
+
+    let m0 = Scalar::random(&mut rng);
+    user.set_message(m0, Scalar::random(&mut rng))?;
+    let commitment = user.commit()?;
+    let eta = Scalar::random(&mut rng);
+    let witness = user.compute_witness(&Challenge::from(eta))?;
+
+    assert!(matches!(
+        verify_commitment_proof(pk, &m0, &commitment.w, &commitment.x, &Scalar::zero(), &witness),
+        Err(Error::ScalarIsZero)
+    ));
+
+    let error = verify_commitment_proof(pk, &(m0 + Scalar::one()), &commitment.w, &commitment.x, &eta, &witness);
+    assert!(matches!(error, Err(Error::WitnessMismatch { .. })));
+
+    #[cfg(feature = "debug-errors")]
+    if let Err(Error::WitnessMismatch { lhs, rhs }) = error {
+        // Off by exactly the h_1^{b_2} contributed by the wrong m_0
+        let lhs = G1Affine::from_compressed(&lhs).unwrap();
+        let rhs = G1Affine::from_compressed(&rhs).unwrap();
+        assert_eq!(lhs - pk.h1 * witness.b2, G1Projective::from(rhs));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn reset_reuses_signer_and_user_across_flows() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let mut signer = Signer::new(&key_pair, rng);
+    let mut user = User::new(pk, rng);
+
+    for _ in 0..2 {
+        let m0 = Scalar::random(&mut rng);
+        let m1 = Scalar::random(&mut rng);
+        user.set_message(m0, m1)?;
+        signer.set_message(m0)?;
+        let eta = signer.commit(user.commit()?)?;
+        signer.verify_witness(user.compute_witness(&eta)?)?;
+        let signature = user.sign(&signer.sign()?)?;
+        verify_signature(pk, &m0, &m1, &signature.sigma, &signature.alpha, &signature.beta)?;
+
+        signer.reset()?;
+        user.reset()?;
+        assert!(matches!(signer.get_state(), SignerState::ReadyToSetMessage));
+        assert!(matches!(user.get_state(), UserState::ReadyToSetMessage));
+        assert!(matches!(
+            user.info(),
+            Err(Error::WrongState {
+                expected: StateKind::ReadyToCommit,
+                actual: StateKind::ReadyToSetMessage
+            })
+        ));
+    }
+
+    // Mid-flow resets must be forced
+    signer.set_message(Scalar::one())?;
+    user.set_message(Scalar::one(), Scalar::from(2u64))?;
+    user.commit()?;
+    assert!(matches!(
+        signer.reset(),
+        Err(Error::WrongState {
+            expected: StateKind::Signed,
+            actual: StateKind::ReadyToCommit
+        })
+    ));
+    assert!(matches!(
+        user.reset(),
+        Err(Error::WrongState {
+            expected: StateKind::Signed,
+            actual: StateKind::ReadyToComputeWitness
+        })
+    ));
+    assert!(matches!(signer.get_state(), SignerState::ReadyToCommit));
+
+    signer.force_reset();
+    user.force_reset();
+    assert!(matches!(signer.get_state(), SignerState::ReadyToSetMessage));
+    assert!(matches!(user.get_state(), UserState::ReadyToSetMessage));
+
+    // An aborted flow can be reset without force
+    signer.set_message(Scalar::one())?;
+    signer.abort();
+    assert!(matches!(
+        signer.get_state(),
+        SignerState::Aborted(AbortReason::UserRequested)
+    ));
+    signer.reset()?;
+
+    Ok(())
+}
+
+#[test]
+fn secret_key_export_round_trips() -> Result<(), Error> {
+    let key_pair = KeyPair::generate(rand_core::OsRng);
+
+    let secret = key_pair.export_secret();
+    let restored = KeyPair::from_secret_bytes(secret.as_bytes())?;
+    assert_eq!(restored.public_key(), key_pair.public_key());
+    assert_eq!(restored.export_secret().as_bytes(), secret.as_bytes());
+
+    let m0 = Scalar::from(3u64);
+    let m1 = Scalar::from(5u64);
+    let signature = sign_in_process(&restored, m0, m1, rand_core::OsRng, rand_core::OsRng)?;
+    verify_signature(
+        key_pair.public_key(),
+        &m0,
+        &m1,
+        &signature.sigma,
+        &signature.alpha,
+        &signature.beta,
+    )?;
+
+    assert!(matches!(
+        KeyPair::from_secret_bytes(&[0u8; 32]),
+        Err(Error::ScalarIsZero)
+    ));
+    assert!(matches!(
+        KeyPair::from_secret_bytes(&[0xff; 32]),
+        Err(Error::NonCanonicalScalar)
+    ));
+
+    Ok(())
+}
+
+#[cfg(feature = "blinding-factors")]
+#[test]
+fn blinding_factors_unblind_the_partial_signature() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let mut signer = Signer::new(&key_pair, rng);
+    let mut user = User::new(pk, rng);
+
+    user.set_message(Scalar::random(&mut rng), Scalar::random(&mut rng))?;
+    signer.set_message(user.info()?)?;
+    let eta = signer.commit(user.commit()?)?;
+    signer.verify_witness(user.compute_witness(&eta)?)?;
+    assert_eq!(
+        user.blinding_factors().err(),
+        Some(Error::WrongState {
+            expected: StateKind::Signed,
+            actual: StateKind::ReadyToSign
+        })
+    );
+
+    let partial = signer.sign()?;
+    let signature = user.sign(&partial)?;
+    let factors = user.blinding_factors()?;
+
+    let tau = (factors.f * factors.t).invert().unwrap();
+    assert_eq!(signature.sigma, G1Affine::from(partial.y * tau));
+    assert_eq!(signature.beta, factors.s + partial.l * factors.t.invert().unwrap());
+
+    Ok(())
+}
+
+#[test]
+fn cloned_user_completes_the_flow() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let mut signer = Signer::new(&key_pair, rng);
+    let mut user = User::new(pk, rng);
+
+    let m0 = Scalar::random(&mut rng);
+    let m1 = Scalar::random(&mut rng);
+    user.set_message(m0, m1)?;
+    signer.set_message(m0)?;
+
+    // Continue with the clone, the original is left untouched
+    let mut clone = user.clone();
+    let eta = signer.commit(clone.commit()?)?;
+    signer.verify_witness(clone.compute_witness(&eta)?)?;
+    let signature = clone.sign(&signer.sign()?)?;
+
+    verify_signature(pk, &m0, &m1, &signature.sigma, &signature.alpha, &signature.beta)?;
+    assert!(matches!(user.get_state(), UserState::ReadyToCommit));
+    assert!(matches!(clone.get_state(), UserState::Signed));
+
+    Ok(())
+}
+
+#[test]
+fn msm_matches_naive_sum() {
+    let mut rng = rand_core::OsRng;
+
+    let points: [G1Affine; 4] =
+        core::array::from_fn(|_| G1Affine::from(G1Affine::generator() * Scalar::random(&mut rng)));
+    let naive = |scalars: &[Scalar; 4]| -> G1Projective { points.iter().zip(scalars).map(|(p, k)| p * k).sum() };
+
+    for _ in 0..8 {
+        let scalars: [Scalar; 4] = core::array::from_fn(|_| Scalar::random(&mut rng));
+        let msm = crate::bls12_381_plain::msm::msm4(points.each_ref(), scalars.each_ref());
+        assert_eq!(msm, naive(&scalars));
+    }
+
+    // Edge cases: zero and -1 (every bit set up to the modulus) and repeated points
+    let scalars = [Scalar::zero(), -Scalar::one(), Scalar::one(), Scalar::zero()];
+    assert_eq!(
+        crate::bls12_381_plain::msm::msm4(points.each_ref(), scalars.each_ref()),
+        naive(&scalars)
+    );
+    let repeated = [&points[0]; 4];
+    assert_eq!(
+        crate::bls12_381_plain::msm::msm4(repeated, scalars.each_ref()),
+        points[0] * scalars.iter().sum::<Scalar>()
+    );
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn verify_many_reports_each_result_in_order() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let m0 = Scalar::random(&mut rng);
+
+    let mut items = (0..6)
+        .map(|_| {
+            let m1 = Scalar::random(&mut rng);
+            sign_in_process(&key_pair, m0, m1, rng, rng).map(|signature| (m0, m1, signature))
+        })
+        .collect::<Result<std::vec::Vec<_>, Error>>()?;
+    items[1].1 += Scalar::one();
+    items[4].2.beta += Scalar::one();
+
+    let results = crate::bls12_381_plain::verify_many(pk, &items);
+    assert_eq!(results.len(), items.len());
+    for (i, result) in results.iter().enumerate() {
+        match i {
+            1 | 4 => assert!(matches!(result, Err(Error::InvalidSignature))),
+            _ => assert!(result.is_ok()),
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn debug_output_redacts_secrets() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let mut signer = Signer::new(&key_pair, rng);
+    let mut user = User::new(&key_pair.public_key, rng);
+
+    let m0 = Scalar::random(&mut rng);
+    let m1 = Scalar::random(&mut rng);
+    user.set_message(m0, m1)?;
+    signer.set_message(m0)?;
+    let eta = signer.commit(user.commit()?)?;
+
+    let secret = key_pair.export_secret();
+    let outputs = [
+        std::format!("{:?}", key_pair),
+        std::format!("{:?}", signer),
+        std::format!("{:?}", user),
+    ];
+    for output in &outputs {
+        assert!(output.contains("<redacted>"), "{}", output);
+        for needle in [
+            std::format!("{:?}", Scalar::from_bytes(secret.as_bytes()).unwrap()),
+            std::format!("{:?}", eta),
+        ] {
+            assert!(!output.contains(&needle), "{}", output);
+        }
+    }
+    assert!(!outputs[2].contains(&std::format!("{:?}", m1)));
+    assert!(outputs[1].contains("ReadyToVerifyWitness"));
+
+    Ok(())
+}
+
+#[cfg(feature = "fiat_shamir")]
+#[test]
+fn non_interactive_commitment_signs_and_rejects_tampering() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let m0 = Scalar::random(&mut rng);
+    let m1 = Scalar::random(&mut rng);
+
+    // Interactive
+    let signature = sign_in_process(&key_pair, m0, m1, rng, rng)?;
+    verify_signature(pk, &m0, &m1, &signature.sigma, &signature.alpha, &signature.beta)?;
+
+    // Non-interactive
+    let mut signer = Signer::new(&key_pair, rng);
+    let mut user = User::new(pk, rng);
+    user.set_message(m0, m1)?;
+    signer.set_message(m0)?;
+    let (commitment, witness) = user.commit_non_interactive()?;
+    signer.verify_non_interactive(commitment, witness)?;
+    let signature = user.sign(&signer.sign()?)?;
+    verify_signature(pk, &m0, &m1, &signature.sigma, &signature.alpha, &signature.beta)?;
+
+    // A tampered W changes eta, so the witness no longer matches
+    let mut signer = Signer::new(&key_pair, rng);
+    let mut user = User::new(pk, rng);
+    user.set_message(m0, m1)?;
+    signer.set_message(m0)?;
+    let (mut commitment, witness) = user.commit_non_interactive()?;
+    commitment.w = G1Affine::from(G1Projective::from(commitment.w) + pk.g1);
+    assert!(matches!(
+        signer.verify_non_interactive(commitment, witness),
+        Err(Error::WitnessMismatch { .. })
+    ));
+    assert!(matches!(
+        signer.get_state(),
+        SignerState::Aborted(AbortReason::Error(Error::WitnessMismatch { .. }))
+    ));
+
+    Ok(())
+}
+
+#[cfg(feature = "fiat_shamir")]
+#[test]
+fn session_signer_verifies_a_non_interactive_commitment_in_its_session() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+    let (session_a, session_b) = ([0xa; SESSION_ID_BYTES], [0xb; SESSION_ID_BYTES]);
+
+    let start = |rng: OsRng| -> Result<(User<OsRng>, Signer<OsRng>), Error> {
+        let (user, _) = parties_with_messages(&key_pair, m0, m1, rng, rng)?;
+        let mut signer = Signer::new_with_session(&key_pair, rng, session_a);
+        signer.set_message(m0)?;
+        Ok((user, signer))
+    };
+
+    let (mut user, mut signer) = start(rng)?;
+    let (commitment, witness) = user.commit_non_interactive()?;
+    signer.verify_non_interactive_in_session(&session_a, commitment, witness)?;
+    let signature = user.sign(&signer.sign()?)?;
+    verify_signature(
+        &key_pair.public_key,
+        &m0,
+        &m1,
+        &signature.sigma,
+        &signature.alpha,
+        &signature.beta,
+    )?;
+
+    // Another session, or no session at all, aborts
+    let (mut user, mut signer) = start(rng)?;
+    let (commitment, witness) = user.commit_non_interactive()?;
+    assert_eq!(
+        signer.verify_non_interactive_in_session(&session_b, commitment, witness),
+        Err(Error::SessionMismatch)
+    );
+    assert!(matches!(
+        signer.get_state(),
+        SignerState::Aborted(AbortReason::Error(Error::SessionMismatch))
+    ));
+    let (mut user, mut signer) = start(rng)?;
+    let (commitment, witness) = user.commit_non_interactive()?;
+    assert_eq!(
+        signer.verify_non_interactive(commitment, witness),
+        Err(Error::SessionMismatch)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn pairing_holds_matches_verify_signature() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let m0 = Scalar::random(&mut rng);
+    let m1 = Scalar::random(&mut rng);
+    let signature = sign_in_process(&key_pair, m0, m1, rng, rng)?;
+
+    assert!(pairing_holds(pk, &m0, &m1, &signature));
+    assert!(!pairing_holds(pk, &m0, &(m1 + Scalar::one()), &signature));
+
+    let mut tampered = signature;
+    tampered.beta += Scalar::one();
+    assert!(!pairing_holds(pk, &m0, &m1, &tampered));
+    assert!(matches!(
+        verify_signature(pk, &m0, &m1, &tampered.sigma, &tampered.alpha, &tampered.beta),
+        Err(Error::InvalidSignature)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn from_bytes_checks_the_length() -> Result<(), Error> {
+    let rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let signature = sign_in_process(&key_pair, Scalar::one(), Scalar::from(2u64), rng, rng)?;
+
+    assert_eq!(pk.to_bytes().len(), PUBLIC_KEY_BYTES);
+    assert_eq!(signature.to_bytes().len(), SIGNATURE_BYTES);
+    assert_eq!(
+        Commitment::from_bytes(&[0u8; COMMITMENT_BYTES + 1]),
+        Err(Error::DecodingFailed)
+    );
+    assert_eq!(
+        Witness::from_bytes(&[0u8; WITNESS_BYTES - 1]),
+        Err(Error::DecodingFailed)
+    );
+    assert_eq!(
+        PartialSignature::from_bytes(&[0u8; PARTIAL_SIGNATURE_BYTES / 2]),
+        Err(Error::DecodingFailed)
+    );
+
+    let bytes = pk.to_bytes();
+    assert_eq!(PublicKey::from_bytes(&bytes)?, *pk);
+    assert!(matches!(PublicKey::from_bytes(&bytes[1..]), Err(Error::DecodingFailed)));
+
+    let mut framed = std::vec::Vec::from(signature.to_bytes());
+    assert_eq!(Signature::from_bytes(&framed)?, signature);
+    framed.push(0);
+    assert!(matches!(Signature::from_bytes(&framed), Err(Error::DecodingFailed)));
+    assert_eq!(Signature::from_bytes(&framed[..SIGNATURE_BYTES])?, signature);
+
+    Ok(())
+}
+
+#[test]
+fn find_matching_message_returns_the_first_match() -> Result<(), Error> {
+    let mut rng = rand_core::OsRng;
+
+    let key_pair = KeyPair::generate(rng);
+    let pk = &key_pair.public_key;
+    let m0 = Scalar::random(&mut rng);
+    let m1 = Scalar::random(&mut rng);
+    let signature = sign_in_process(&key_pair, m0, m1, rng, rng)?;
+
+    let mut candidates: std::vec::Vec<(Scalar, Scalar)> = (0..4)
+        .map(|_| (Scalar::random(&mut rng), Scalar::random(&mut rng)))
+        .collect();
+    candidates.push((m0, Scalar::random(&mut rng)));
+    candidates.push((Scalar::random(&mut rng), m1));
+    assert_eq!(find_matching_message(pk, &signature, &candidates), None);
+
+    candidates.insert(3, (m0, m1));
+    candidates.push((m0, m1));
+    assert_eq!(find_matching_message(pk, &signature, &candidates), Some(3));
+
+    let mut tampered = signature;
+    tampered.sigma = G1Affine::identity();
+    assert_eq!(find_matching_message(pk, &tampered, &candidates), None);
+
+    Ok(())
+}
+
+#[test]
+fn verify_consistency_detects_mutated_public_keys() -> Result<(), Error> {
+    let key_pair = KeyPair::generate(rand_core::OsRng);
+    key_pair.verify_consistency()?;
+
+    let restored = KeyPair::from_secret_bytes(key_pair.export_secret().as_bytes())?;
+    restored.verify_consistency()?;
+
+    let other = KeyPair::generate(rand_core::OsRng).public_key;
+    let mutations: [fn(&mut PublicKey, &PublicKey); 5] = [
+        |pk, other| pk.w2 = other.w2,
+        |pk, other| pk.g2 = other.g2,
+        |pk, other| pk.h1 = other.h1,
+        |pk, other| pk.u2 = other.u2,
+        |pk, other| pk.v1 = other.v1,
+    ];
+    for mutate in mutations {
+        let mut corrupted = KeyPair::from_secret_bytes(key_pair.export_secret().as_bytes())?;
+        mutate(&mut corrupted.public_key, &other);
+        assert_eq!(corrupted.verify_consistency(), Err(Error::InconsistentKeyPair));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn scalar_hasher_matches_hash_to_scalar() -> Result<(), Error> {
+    let payload: std::vec::Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+    let expected = hash_to_scalar(INFO_DST, &payload);
+
+    for split in [0, 1, 4096, payload.len()] {
+        let (first, second) = payload.split_at(split);
+        let mut hasher = ScalarHasher::new(INFO_DST);
+        hasher.update(first);
+        hasher.update(second);
+        assert_eq!(hasher.finalize(), expected);
+    }
+
+    // Streamed on both sides, the parties agree on m0
+    let mut signer_hasher = ScalarHasher::new(INFO_DST);
+    for chunk in payload.chunks(1000) {
+        signer_hasher.update(chunk);
+    }
+    let key_pair = KeyPair::generate(rand_core::OsRng);
+    let mut signer = Signer::new(&key_pair, rand_core::OsRng);
+    signer.set_message(signer_hasher.finalize())?;
+    let mut user = User::new(&key_pair.public_key, rand_core::OsRng);
+    user.set_message_bytes(&payload, b"message")?;
+    assert_eq!(signer.info()?, user.info()?);
+
+    assert_ne!(
+        ScalarHasher::new(MESSAGE_DST).finalize(),
+        ScalarHasher::new(INFO_DST).finalize()
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "metrics")]
+#[test]
+fn metrics_count_transitions_and_aborts() -> Result<(), Error> {
+    use metrics::{Counter, CounterFn, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit};
+    use std::collections::BTreeMap;
+    use std::string::String;
+    use std::sync::{Arc, Mutex};
+
+    type Counts = Arc<Mutex<BTreeMap<String, u64>>>;
+
+    /// Counts increments per counter, keyed by the name followed by the label values
+    #[derive(Default)]
+    struct CountingRecorder {
+        counts: Counts,
+    }
+
+    struct Entry {
+        key: String,
+        counts: Counts,
+    }
+
+    impl CounterFn for Entry {
+        fn increment(&self, value: u64) {
+            *self.counts.lock().unwrap().entry(self.key.clone()).or_default() += value;
+        }
+
+        fn absolute(&self, _: u64) {}
+    }
+
+    impl Recorder for CountingRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            let labels: std::vec::Vec<&str> = key.labels().map(|label| label.value()).collect();
+            Counter::from_arc(Arc::new(Entry {
+                key: std::format!("{} {}", key.name(), labels.join(",")),
+                counts: self.counts.clone(),
+            }))
+        }
+
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::noop()
+        }
+
+        fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::noop()
+        }
+    }
+
+    let mut rng = rand_core::OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let (m0, m1) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+    let recorder = CountingRecorder::default();
+
+    metrics::with_local_recorder(&recorder, || -> Result<(), Error> {
+        sign_in_process(&key_pair, m0, m1, rng, rng)?;
+
+        let mut user = User::new(&key_pair.public_key, rng);
+        assert_eq!(user.set_message(Scalar::zero(), m1), Err(Error::ScalarIsZero));
+        let mut signer = Signer::new(&key_pair, rng);
+        signer.abort();
+
+        Ok(())
+    })?;
+
+    let counts = recorder.counts.lock().unwrap();
+    let count = |key: &str| counts.get(key).copied().unwrap_or_default();
+    for state in ["ReadyToCommit", "ReadyToVerifyWitness", "ReadyToSign", "Signed"] {
+        assert_eq!(
+            count(&std::format!("okamoto_signer_state_transitions_total {state}")),
+            1
+        );
+    }
+    for state in ["ReadyToCommit", "ReadyToComputeWitness", "ReadyToSign", "Signed"] {
+        assert_eq!(count(&std::format!("okamoto_user_state_transitions_total {state}")), 1);
+    }
+    assert_eq!(count("okamoto_signer_state_transitions_total Aborted"), 1);
+    assert_eq!(count("okamoto_signer_aborts_total UserRequested"), 1);
+    assert_eq!(count("okamoto_user_state_transitions_total Aborted"), 1);
+    assert_eq!(count("okamoto_user_aborts_total ScalarIsZero"), 1);
+
+    Ok(())
+}
+
+#[cfg(all(feature = "tracing", feature = "std"))]
+#[test]
+fn tracing_spans_record_the_resulting_state() -> Result<(), Error> {
+    use std::string::String;
+    use std::sync::Mutex;
+    use std::vec::Vec;
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata};
+    use tracing_core::span::Current;
+
+    /// Collects the name and the recorded `state` of every span
+    #[derive(Default)]
+    struct Recorder {
+        spans: Mutex<Vec<(&'static Metadata<'static>, Option<String>)>>,
+        entered: Mutex<Vec<Id>>,
+    }
+
+    struct StateVisitor<'a>(&'a mut Option<String>);
+
+    impl Visit for StateVisitor<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn core::fmt::Debug) {
+            if field.name() == "state" {
+                *self.0 = Some(std::format!("{:?}", value));
+            }
+        }
+    }
+
+    impl tracing::Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, attributes: &Attributes<'_>) -> Id {
+            let mut spans = self.spans.lock().unwrap();
+            spans.push((attributes.metadata(), None));
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, id: &Id, values: &Record<'_>) {
+            let mut spans = self.spans.lock().unwrap();
+            values.record(&mut StateVisitor(&mut spans[id.into_u64() as usize - 1].1));
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, _: &Event<'_>) {}
+
+        fn enter(&self, id: &Id) {
+            self.entered.lock().unwrap().push(id.clone());
+        }
+
+        fn exit(&self, _: &Id) {
+            self.entered.lock().unwrap().pop();
+        }
+
+        fn current_span(&self) -> Current {
+            match self.entered.lock().unwrap().last() {
+                Some(id) => Current::new(id.clone(), self.spans.lock().unwrap()[id.into_u64() as usize - 1].0),
+                None => Current::none(),
+            }
+        }
+    }
+
+    let mut rng = rand_core::OsRng;
+    let key_pair = KeyPair::generate(rng);
+    let recorder = std::sync::Arc::new(Recorder::default());
+
+    tracing::subscriber::with_default(recorder.clone(), || -> Result<(), Error> {
+        let mut signer = Signer::new(&key_pair, rng);
+        let mut user = User::new(&key_pair.public_key, rng);
+
+        let m0 = Scalar::random(&mut rng);
+        user.set_message(m0, Scalar::random(&mut rng))?;
+        signer.set_message(m0)?;
+        let eta = signer.commit(user.commit()?)?;
+        signer.verify_witness(user.compute_witness(&eta)?)?;
+        user.sign(&signer.sign()?)?;
+        assert!(signer.sign().is_err());
+
+        let mut user = User::new(&key_pair.public_key, rng);
+        user.set_message(m0, Scalar::random(&mut rng))?;
+        user.commit()?;
+        assert!(user.compute_witness(&Challenge::from(Scalar::zero())).is_err());
+        Ok(())
+    })?;
+
+    let spans = recorder
+        .spans
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(metadata, state)| (metadata.name(), state.clone()))
+        .collect::<Vec<_>>();
+    let expected = [
+        ("User::set_message", Some("ReadyToCommit")),
+        ("Signer::set_message", Some("ReadyToCommit")),
+        ("User::commit", Some("ReadyToComputeWitness")),
+        ("Signer::commit", Some("ReadyToVerifyWitness")),
+        ("User::compute_witness", Some("ReadyToSign")),
+        ("Signer::verify_witness", Some("ReadyToSign")),
+        ("Signer::sign", Some("Signed")),
+        ("User::sign", Some("Signed")),
+        ("Signer::sign", None),
+        ("User::set_message", Some("ReadyToCommit")),
+        ("User::commit", Some("ReadyToComputeWitness")),
+        ("User::compute_witness", Some("Aborted(Error(ScalarIsZero))")),
+    ];
+    assert_eq!(spans.len(), expected.len(), "{:?}", spans);
+    for ((name, state), (expected_name, expected_state)) in spans.iter().zip(expected) {
+        assert_eq!(*name, expected_name);
+        assert_eq!(state.as_deref(), expected_state, "{}", name);
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn arbitrary_messages_are_deterministic_and_sometimes_invalid() {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    let mut rng = HmacDrbg::new(&[b"arbitrary"]);
+    let (mut valid, mut off_curve, mut identity) = (0, 0, 0);
+    for _ in 0..64 {
+        let mut data = [0u8; 512];
+        rng.fill_bytes(&mut data);
+
+        let signature = Signature::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        assert_eq!(Signature::arbitrary(&mut Unstructured::new(&data)).unwrap(), signature);
+        let commitment = Commitment::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        assert_eq!(
+            Commitment::arbitrary(&mut Unstructured::new(&data)).unwrap(),
+            commitment
+        );
+
+        match signature.sigma {
+            sigma if sigma == G1Affine::identity() => identity += 1,
+            sigma if !bool::from(sigma.is_on_curve()) => off_curve += 1,
+            _ => valid += 1,
+        }
+    }
+    assert!(
+        valid > 32 && off_curve > 0 && identity > 0,
+        "{} {} {}",
+        valid,
+        off_curve,
+        identity
+    );
+}
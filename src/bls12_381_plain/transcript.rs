@@ -0,0 +1,102 @@
+//! Record of the messages exchanged in a run of the protocol
+
+use super::{
+    Challenge, Commitment, PartialSignature, Witness, CHALLENGE_BYTES, COMMITMENT_BYTES, PARTIAL_SIGNATURE_BYTES,
+    TRANSCRIPT_BYTES, WITNESS_BYTES,
+};
+
+/// Offsets of the messages in [Transcript::to_bytes], each following the previous one
+const COMMITMENT_OFFSET: usize = 1;
+const CHALLENGE_OFFSET: usize = COMMITMENT_OFFSET + COMMITMENT_BYTES;
+const WITNESS_OFFSET: usize = CHALLENGE_OFFSET + CHALLENGE_BYTES;
+const PARTIAL_SIGNATURE_OFFSET: usize = WITNESS_OFFSET + WITNESS_BYTES;
+
+/// Every message exchanged between the [super::User] and the [super::Signer] in one run of the protocol
+///
+/// Either party records the messages as they are sent or received. The transcript is intended for debugging and
+/// audit logs, e.g. to replay [super::verify_commitment_proof] after an [super::Error::WitnessMismatch], and holds
+/// nothing that is not already seen by both parties.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Transcript {
+    commitment: Option<Commitment>,
+    challenge: Option<Challenge>,
+    witness: Option<Witness>,
+    partial_signature: Option<PartialSignature>,
+}
+
+impl Transcript {
+    /// Create an empty transcript
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Step 2. Record the commitment $(W, X)$ sent by the [super::User]
+    pub fn record_commitment(&mut self, commitment: &Commitment) {
+        self.commitment = Some(*commitment);
+    }
+
+    /// Step 2. Record the [Challenge] $\eta$ returned by the [super::Signer]
+    pub fn record_challenge(&mut self, challenge: &Challenge) {
+        self.challenge = Some(*challenge);
+    }
+
+    /// Step 3. Record the witness $(b_1, b_2, b_3)$ sent by the [super::User]
+    pub fn record_witness(&mut self, witness: &Witness) {
+        self.witness = Some(*witness);
+    }
+
+    /// Step 4. Record the [PartialSignature] $(Y, R, l)$ returned by the [super::Signer]
+    pub fn record_partial_signature(&mut self, partial: &PartialSignature) {
+        self.partial_signature = Some(*partial);
+    }
+
+    /// The recorded commitment $(W, X)$, if any
+    pub fn commitment(&self) -> Option<&Commitment> {
+        self.commitment.as_ref()
+    }
+
+    /// The recorded challenge $\eta$, if any
+    pub fn challenge(&self) -> Option<&Challenge> {
+        self.challenge.as_ref()
+    }
+
+    /// The recorded witness $(b_1, b_2, b_3)$, if any
+    pub fn witness(&self) -> Option<&Witness> {
+        self.witness.as_ref()
+    }
+
+    /// The recorded partial signature $(Y, R, l)$, if any
+    pub fn partial_signature(&self) -> Option<&PartialSignature> {
+        self.partial_signature.as_ref()
+    }
+
+    /// Serialize as a presence byte followed by each message in protocol order, [TRANSCRIPT_BYTES] in all
+    ///
+    /// Bit $i$ of the first byte is set if the $i$-th message was recorded: the commitment (as [Commitment::to_bytes]),
+    /// $\eta$ (as [Challenge::to_bytes]), the witness (as [Witness::to_bytes]) and $(Y, R, l)$ (as
+    /// [PartialSignature::to_bytes]). Messages that were not recorded are left as zeros so every field has a fixed
+    /// offset.
+    pub fn to_bytes(&self) -> [u8; TRANSCRIPT_BYTES] {
+        let mut bytes = [0u8; TRANSCRIPT_BYTES];
+
+        if let Some(commitment) = &self.commitment {
+            bytes[0] |= 1;
+            bytes[COMMITMENT_OFFSET..CHALLENGE_OFFSET].copy_from_slice(&commitment.to_bytes());
+        }
+        if let Some(challenge) = &self.challenge {
+            bytes[0] |= 1 << 1;
+            bytes[CHALLENGE_OFFSET..WITNESS_OFFSET].copy_from_slice(&challenge.to_bytes());
+        }
+        if let Some(witness) = &self.witness {
+            bytes[0] |= 1 << 2;
+            bytes[WITNESS_OFFSET..PARTIAL_SIGNATURE_OFFSET].copy_from_slice(&witness.to_bytes());
+        }
+        if let Some(partial) = &self.partial_signature {
+            bytes[0] |= 1 << 3;
+            bytes[PARTIAL_SIGNATURE_OFFSET..PARTIAL_SIGNATURE_OFFSET + PARTIAL_SIGNATURE_BYTES]
+                .copy_from_slice(&partial.to_bytes());
+        }
+
+        bytes
+    }
+}
@@ -0,0 +1,90 @@
+//! Driving a [Signer] or [User] through the interactive protocol over an asynchronous [Transport]
+//!
+//! The state machines stay free of IO; [run_user] and [run_signer] move the `to_bytes` encodings of the protocol
+//! messages across the [Transport] in the order of the protocol:
+//!
+//! | Frame | Direction | Encoding |
+//! |---|---|---|
+//! | $(W, X)$ | User to Signer | [Commitment::to_bytes] |
+//! | $\eta$ | Signer to User | [Challenge::to_bytes] |
+//! | $(b_1, b_2, b_3)$ | User to Signer | [Witness::to_bytes] |
+//! | $(Y, R, l)$ | Signer to User | [PartialSignature::to_bytes] |
+
+use super::{Challenge, Commitment, Error, PartialSignature, RandomnessSource, Signature, Signer, User, Witness};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use async_trait::async_trait;
+
+/// A reliable, ordered channel of byte frames between a [User] and a [Signer], e.g. a framed TCP stream or a
+/// websocket
+///
+/// Implementations map their own failures to [Error::TransportFailed].
+#[async_trait]
+pub trait Transport {
+    /// Send one frame to the other party
+    async fn send(&mut self, frame: &[u8]) -> Result<(), Error>;
+
+    /// Receive the next frame from the other party
+    async fn recv(&mut self) -> Result<Vec<u8>, Error>;
+}
+
+/// Run the [User] side of the protocol from [UserState::ReadyToCommit](super::UserState::ReadyToCommit), i.e. after
+/// [User::set_message]
+///
+/// A failed step aborts `user` as usual. A failure of the [Transport] or a frame that does not decode aborts `user`
+/// with that error as well, since the flow cannot continue.
+///
+/// # Returns
+/// The [Signature] from [User::sign]
+pub async fn run_user<R: RandomnessSource, T: Transport + ?Sized>(
+    user: &mut User<'_, R>,
+    transport: &mut T,
+) -> Result<Signature, Error> {
+    let commitment = user.commit()?;
+    let frame = exchange(transport, &commitment.to_bytes()).await;
+    let eta = frame
+        .and_then(|frame| Challenge::from_bytes(frame.as_slice()))
+        .map_err(|e| user.abort_with(e))?;
+
+    let witness = user.compute_witness(&eta)?;
+    let frame = exchange(transport, &witness.to_bytes()).await;
+    let partial = frame
+        .and_then(|frame| PartialSignature::from_bytes(frame.as_slice()))
+        .map_err(|e| user.abort_with(e))?;
+
+    user.sign(&partial)
+}
+
+/// Run the [Signer] side of the protocol from [SignerState::ReadyToCommit](super::SignerState::ReadyToCommit), i.e.
+/// after [Signer::set_message]
+///
+/// A failed step aborts `signer` as usual. A failure of the [Transport] or a frame that does not decode aborts
+/// `signer` with that error as well, since the flow cannot continue.
+pub async fn run_signer<R: RandomnessSource, T: Transport + ?Sized>(
+    signer: &mut Signer<'_, R>,
+    transport: &mut T,
+) -> Result<(), Error> {
+    let frame = transport.recv().await;
+    let commitment = frame
+        .and_then(|frame| Commitment::from_bytes(frame.as_slice()))
+        .map_err(|e| signer.abort_with(e))?;
+
+    let eta = signer.commit(commitment)?;
+    let frame = exchange(transport, &eta.to_bytes()).await;
+    let witness = frame
+        .and_then(|frame| Witness::from_bytes(frame.as_slice()))
+        .map_err(|e| signer.abort_with(e))?;
+
+    signer.verify_witness(witness)?;
+    let partial = signer.sign()?;
+    transport
+        .send(&partial.to_bytes())
+        .await
+        .map_err(|e| signer.abort_with(e))
+}
+
+/// Send `frame` and receive the reply
+async fn exchange<T: Transport + ?Sized>(transport: &mut T, frame: &[u8]) -> Result<Vec<u8>, Error> {
+    transport.send(frame).await?;
+    transport.recv().await
+}
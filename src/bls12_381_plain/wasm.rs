@@ -0,0 +1,69 @@
+//! [wasm-bindgen](https://docs.rs/wasm-bindgen) bindings for verifying signatures from JavaScript
+//!
+//! Every argument is a byte encoding: the [PublicKey] as produced by [PublicKey::to_bytes], $m_0$ and $m_1$ as
+//! 32-byte little-endian scalars and the [Signature] as produced by [Signature::to_bytes]. Malformed input is
+//! reported as a thrown `Error` rather than a panic; a well-formed signature that does not verify returns `false`.
+//!
+//! To produce a WebAssembly module build the library as a `cdylib` without the default features: `std` pulls in
+//! `getrandom`, which does not build for `wasm32-unknown-unknown`, and the verifier needs no randomness.
+//!
+//! ```text
+//! cargo rustc --lib --release --crate-type cdylib --target wasm32-unknown-unknown --no-default-features --features wasm
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/ziglet_okamoto.wasm
+//! ```
+//!
+//! ```js
+//! import init, { verifySignature } from "./pkg/ziglet_okamoto.js";
+//!
+//! await init();
+//! const valid = verifySignature(publicKey, m0, m1, signature);
+//! ```
+
+use super::{Error, PublicKey, Signature, PUBLIC_KEY_BYTES, SIGNATURE_BYTES};
+use alloc::format;
+use bls12_381::Scalar;
+use wasm_bindgen::prelude::*;
+
+/// Verify a [Signature] on $(m_0, m_1)$ given their byte encodings
+///
+/// # Returns
+/// `true` if the signature verifies, `false` if it does not and an error if any input cannot be decoded
+#[wasm_bindgen(js_name = verifySignature)]
+pub fn verify_signature(public_key: &[u8], m0: &[u8], m1: &[u8], signature: &[u8]) -> Result<bool, JsError> {
+    let public_key = decode_public_key(public_key)?;
+    let m0 = decode_scalar(m0, "m0")?;
+    let m1 = decode_scalar(m1, "m1")?;
+    let signature = decode_signature(signature)?;
+
+    Ok(super::verify_signature(
+        &public_key,
+        &m0,
+        &m1,
+        &signature.sigma,
+        &signature.alpha,
+        &signature.beta,
+    )
+    .is_ok())
+}
+
+fn decode_public_key(bytes: &[u8]) -> Result<PublicKey, JsError> {
+    if bytes.len() != PUBLIC_KEY_BYTES {
+        return Err(JsError::new(&format!("public key must be {} bytes", PUBLIC_KEY_BYTES)));
+    }
+    PublicKey::from_bytes(bytes).map_err(|error| JsError::new(&format!("invalid public key: {:?}", error)))
+}
+
+fn decode_scalar(bytes: &[u8], name: &str) -> Result<Scalar, JsError> {
+    match super::decode_scalar(bytes) {
+        Ok(scalar) => Ok(scalar),
+        Err(Error::DecodingFailed) => Err(JsError::new(&format!("{} must be 32 bytes", name))),
+        Err(_) => Err(JsError::new(&format!("{} is not a canonical scalar", name))),
+    }
+}
+
+fn decode_signature(bytes: &[u8]) -> Result<Signature, JsError> {
+    if bytes.len() != SIGNATURE_BYTES {
+        return Err(JsError::new(&format!("signature must be {} bytes", SIGNATURE_BYTES)));
+    }
+    Signature::from_bytes(bytes).map_err(|error| JsError::new(&format!("invalid signature: {:?}", error)))
+}
@@ -2,8 +2,42 @@
 //!
 //! # References
 //! * Based on [Efficient Blind and Partially Blind Signatures Without Random Oracles](https://link.springer.com/content/pdf/10.1007/11681878_5.pdf)
+//!
+//! # Features
+//! * `std` (default): Enables `alloc` and the operating system RNG, including the byte-string tokens of
+//!   [`bls12_381_plain::anonymous_token`]. Without it the crate is `no_std`.
+//! * `alloc`: Enables the helpers that require an allocator, such as prepared verification.
+//! * `arbitrary`: `arbitrary::Arbitrary` for the protocol messages, for structured fuzzing.
+//! * `async`: A `Transport` trait for exchanging frames over an async transport and the `run_user`/`run_signer`
+//!   drivers that run the protocol over it.
+//! * `base64`: Base64 encodings of `PublicKey` and `Signature` next to the hex encodings, and PEM encodings of
+//!   `PublicKey` and `KeyPair`.
+//! * `blinding-factors`: `User::blinding_factors` for application-specific unblinding. Breaks the blindness of any
+//!   signature whose factors leave the user.
+//! * `cache`: `VerificationCache` memoizes the results of `verify_signature` in an LRU cache.
+//! * `debug-errors`: Include both sides of the failed proof equation in `Error::WitnessMismatch`.
+//! * `fiat_shamir`: `User::commit_non_interactive` and `Signer::verify_non_interactive` derive the challenge from the
+//!   commitment, saving a round trip.
+//! * `metrics`: Counters of the state transitions of `Signer` and `User` and of their aborts by reason, through the
+//!   `metrics` crate.
+//! * `rayon`: `verify_many` verifies independent signatures in parallel and reports each result.
+//! * `serde`: Fixed-layout `serde` implementations for the protocol messages in [`bls12_381_plain::serialization`].
+//! * `strict`: Assert the invariants of the values each step of `Signer` and `User` computes, e.g. the pairing
+//!   equation of the partial signature in `Signer::sign`, so that an arithmetic regression panics where it happens
+//!   rather than as a failed verification. Input from the other party is never asserted on; it is checked and
+//!   reported as an `Error` as usual. For development builds.
+//! * `tracing`: `tracing` spans around each step of `Signer` and `User` that record the resulting state. Secret
+//!   arguments are never recorded.
+//! * `test-internals`: Accessors for intermediate protocol values, e.g. `Signer::debug_r`, and the helpers of
+//!   `bls12_381_plain::test_util`. **Breaks the security of the protocol**, for testing only.
+//! * `wasm`: `wasm-bindgen` bindings for the verifier in [`bls12_381_plain::wasm`].
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
-#[cfg(feature = "bls12_381_plain")]
-pub mod bls12_381_plain;
 #[cfg(feature = "bls12_381_crs")]
 pub mod bls12_381_crs;
+#[cfg(feature = "bls12_381_plain")]
+pub mod bls12_381_plain;
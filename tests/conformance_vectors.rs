@@ -0,0 +1,153 @@
+//! Fixed test vectors for the full [User]/[Signer] flow
+//!
+//! `tests/vectors/bls12_381_plain.json` records every message of a deterministic flow for a few seeds. The key is
+//! generated from an [HmacDrbg] seeded with the seed, the [Signer] is [Signer::new_deterministic] and the [User]
+//! draws from its own [HmacDrbg], so the flow can be re-run exactly. A change to any encoding or to the arithmetic
+//! of a step changes the recorded bytes and fails [vectors_are_reproduced].
+//!
+//! After an intentional change, regenerate the file with
+//!
+//! ```text
+//! cargo test --test conformance_vectors -- --ignored regenerate_vectors
+//! ```
+#![cfg(all(feature = "std", feature = "bls12_381_plain"))]
+
+use bls12_381::Scalar;
+use ff::Field;
+use serde::{Deserialize, Serialize};
+use ziglet_okamoto::bls12_381_plain::{
+    verify_signature, Challenge, Commitment, Error, HmacDrbg, KeyPair, PartialSignature, PublicKey, Signature, Signer,
+    User, Witness,
+};
+
+const VECTORS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/vectors/bls12_381_plain.json");
+
+const SEEDS: [&str; 3] = ["okamoto vector 0", "okamoto vector 1", "okamoto vector 2"];
+
+/// Every value is the lowercase hex of its `to_bytes` encoding
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Vector {
+    seed: String,
+    secret_key: String,
+    public_key: String,
+    m0: String,
+    m1: String,
+    commitment: String,
+    challenge: String,
+    witness: String,
+    partial_signature: String,
+    signature: String,
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn unhex(hex: &str) -> Vec<u8> {
+    assert_eq!(hex.len() % 2, 0, "odd length hex {}", hex);
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).expect("hex digits"))
+        .collect()
+}
+
+fn scalar(hex: &str) -> Scalar {
+    let bytes = <[u8; 32]>::try_from(unhex(hex)).expect("32 bytes");
+    Option::from(Scalar::from_bytes(&bytes)).expect("canonical scalar")
+}
+
+/// Run the flow for `seed`, recording each message
+fn run(seed: &str) -> Result<Vector, Error> {
+    let mut rng = HmacDrbg::new(&[b"conformance", seed.as_bytes()]);
+    let key_pair = KeyPair::generate(&mut rng);
+    let m0 = Scalar::random(&mut rng);
+    let m1 = Scalar::random(&mut rng);
+
+    let mut signer = Signer::new_deterministic(&key_pair, seed.as_bytes());
+    let mut user = User::new(&key_pair.public_key, HmacDrbg::new(&[b"user", seed.as_bytes()]));
+
+    user.set_message(m0, m1)?;
+    signer.set_message(m0)?;
+    let commitment = user.commit()?;
+    let challenge = signer.commit(commitment)?;
+    let witness = user.compute_witness(&challenge)?;
+    signer.verify_witness(witness)?;
+    let partial_signature = signer.sign()?;
+    let signature = user.sign(&partial_signature)?;
+
+    Ok(Vector {
+        seed: seed.into(),
+        secret_key: hex(key_pair.export_secret().as_bytes()),
+        public_key: hex(&key_pair.public_key.to_bytes()),
+        m0: hex(&m0.to_bytes()),
+        m1: hex(&m1.to_bytes()),
+        commitment: hex(&commitment.to_bytes()),
+        challenge: hex(&challenge.eta().to_bytes()),
+        witness: hex(&witness.to_bytes()),
+        partial_signature: hex(&partial_signature.to_bytes()),
+        signature: hex(&signature.to_bytes()),
+    })
+}
+
+fn load() -> Vec<Vector> {
+    let json = std::fs::read_to_string(VECTORS).expect("vectors file");
+    serde_json::from_str(&json).expect("vectors parse")
+}
+
+#[test]
+fn vectors_verify() -> Result<(), Error> {
+    let vectors = load();
+    assert_eq!(vectors.len(), SEEDS.len());
+
+    for vector in &vectors {
+        let public_key = PublicKey::from_bytes(&unhex(&vector.public_key))?;
+        let secret_key = <[u8; 32]>::try_from(unhex(&vector.secret_key)).expect("32 bytes");
+        assert_eq!(KeyPair::from_secret_bytes(&secret_key)?.public_key, public_key);
+
+        let (m0, m1) = (scalar(&vector.m0), scalar(&vector.m1));
+        let commitment = Commitment::from_bytes(&unhex(&vector.commitment))?;
+        let challenge = Challenge::from(scalar(&vector.challenge));
+        let witness = Witness::from_bytes(&unhex(&vector.witness))?;
+        let partial_signature = PartialSignature::from_bytes(&unhex(&vector.partial_signature))?;
+        let signature = Signature::from_bytes(&unhex(&vector.signature))?;
+
+        ziglet_okamoto::bls12_381_plain::verify_commitment_proof(
+            &public_key,
+            &m0,
+            &commitment.w,
+            &commitment.x,
+            challenge.eta(),
+            &witness,
+        )?;
+        assert_ne!(partial_signature.l, Scalar::zero());
+        verify_signature(
+            &public_key,
+            &m0,
+            &m1,
+            &signature.sigma,
+            &signature.alpha,
+            &signature.beta,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn vectors_are_reproduced() -> Result<(), Error> {
+    for (vector, seed) in load().iter().zip(SEEDS) {
+        assert_eq!(&run(seed)?, vector, "seed {:?}", seed);
+    }
+
+    Ok(())
+}
+
+#[test]
+#[ignore = "rewrites tests/vectors/bls12_381_plain.json"]
+fn regenerate_vectors() -> Result<(), Error> {
+    let vectors = SEEDS.iter().map(|seed| run(seed)).collect::<Result<Vec<_>, _>>()?;
+    let json = serde_json::to_string_pretty(&vectors).expect("vectors serialize");
+    std::fs::write(VECTORS, json + "\n").expect("vectors file written");
+
+    Ok(())
+}
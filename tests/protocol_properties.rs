@@ -0,0 +1,123 @@
+//! Property-based tests of the full [User]/[Signer] flow over random messages
+#![cfg(all(feature = "std", feature = "bls12_381_plain"))]
+
+use bls12_381::Scalar;
+use proptest::prelude::*;
+use std::sync::OnceLock;
+use ziglet_okamoto::bls12_381_plain::{
+    hash_to_scalar, scalar_from_u64, verify_signature, Error, HmacDrbg, KeyPair, Signature, Signer, User, MESSAGE_DST,
+};
+
+/// Generating a key dominates a single flow, so every case shares one
+fn key_pair() -> &'static KeyPair {
+    static KEY_PAIR: OnceLock<KeyPair> = OnceLock::new();
+    KEY_PAIR.get_or_init(|| KeyPair::generate(HmacDrbg::new(&[b"protocol_properties"])))
+}
+
+/// Run the interactive protocol with both parties drawing from an [HmacDrbg] seeded with `seed`, so a failing case
+/// is reproducible from its inputs
+fn sign(m0: &Scalar, m1: &Scalar, seed: &[u8; 32]) -> Result<Signature, Error> {
+    let key_pair = key_pair();
+    let mut user = User::new(&key_pair.public_key, HmacDrbg::new(&[b"user", seed]));
+    let mut signer = Signer::new(key_pair, HmacDrbg::new(&[b"signer", seed]));
+
+    user.set_message(*m0, *m1)?;
+    signer.set_message(*m0)?;
+    let commitment = user.commit()?;
+    let eta = signer.commit(commitment)?;
+    let witness = user.compute_witness(&eta)?;
+    signer.verify_witness(witness)?;
+    let partial = signer.sign()?;
+    user.sign(&partial)
+}
+
+fn verify(m0: &Scalar, m1: &Scalar, signature: &Signature) -> Result<(), Error> {
+    verify_signature(
+        &key_pair().public_key,
+        m0,
+        m1,
+        &signature.sigma,
+        &signature.alpha,
+        &signature.beta,
+    )
+}
+
+fn scalar() -> impl Strategy<Value = Scalar> {
+    any::<[u8; 32]>().prop_map(|bytes| hash_to_scalar(MESSAGE_DST, &bytes))
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn signatures_verify(m0 in scalar(), m1 in scalar(), seed in any::<[u8; 32]>()) {
+        let signature = sign(&m0, &m1, &seed).unwrap();
+        prop_assert!(verify(&m0, &m1, &signature).is_ok());
+    }
+
+    #[test]
+    fn small_messages_verify(m0 in 1..u64::MAX, m1 in 1..u64::MAX, seed in any::<[u8; 32]>()) {
+        prop_assume!(m0 != m1);
+        let (m0, m1) = (scalar_from_u64(m0).unwrap(), scalar_from_u64(m1).unwrap());
+        let signature = sign(&m0, &m1, &seed).unwrap();
+        prop_assert!(verify(&m0, &m1, &signature).is_ok());
+    }
+
+    #[test]
+    fn signatures_do_not_verify_other_messages(m0 in scalar(), m1 in scalar(), other in scalar(), seed in any::<[u8; 32]>()) {
+        prop_assume!(other != m0 && other != m1);
+        let signature = sign(&m0, &m1, &seed).unwrap();
+        prop_assert!(matches!(verify(&other, &m1, &signature), Err(Error::InvalidSignature)));
+        prop_assert!(matches!(verify(&m0, &other, &signature), Err(Error::InvalidSignature)));
+    }
+
+    /// Flip bits of a single byte of $\sigma$ (0..48), $\alpha$ (48..144) or $\beta$ (144..176) in the encoding
+    ///
+    /// The result either fails to decode or decodes to a signature that does not verify.
+    #[test]
+    fn tampered_signatures_do_not_verify(
+        m0 in scalar(),
+        m1 in scalar(),
+        seed in any::<[u8; 32]>(),
+        index in 0usize..176,
+        mask in 1u8..=255,
+    ) {
+        let signature = sign(&m0, &m1, &seed).unwrap();
+        let mut bytes = signature.to_bytes();
+        bytes[index] ^= mask;
+
+        match Signature::from_bytes(&bytes) {
+            Ok(tampered) => prop_assert!(matches!(verify(&m0, &m1, &tampered), Err(Error::InvalidSignature))),
+            Err(error) => prop_assert!(matches!(
+                error,
+                Error::PointNotOnCurve | Error::NotInSubgroup | Error::NonCanonicalScalar
+            )),
+        }
+    }
+}
+
+/// Every component is covered regardless of which indices the strategy happens to pick
+#[test]
+fn tampering_with_each_component_is_detected() {
+    let m0 = scalar_from_u64(1).unwrap();
+    let m1 = scalar_from_u64(2).unwrap();
+    let signature = sign(&m0, &m1, &[0u8; 32]).unwrap();
+
+    for index in [0, 47, 48, 143, 144, 175] {
+        let mut bytes = signature.to_bytes();
+        bytes[index] ^= 0x01;
+        if let Ok(tampered) = Signature::from_bytes(&bytes) {
+            assert!(matches!(verify(&m0, &m1, &tampered), Err(Error::InvalidSignature)));
+        }
+    }
+
+    let mut tampered = signature;
+    tampered.beta += Scalar::one();
+    assert!(matches!(verify(&m0, &m1, &tampered), Err(Error::InvalidSignature)));
+}
+
+#[test]
+fn scalar_from_u64_rejects_zero() {
+    assert!(matches!(scalar_from_u64(0), Err(Error::ScalarIsZero)));
+    assert_eq!(scalar_from_u64(42).unwrap(), Scalar::from(42u64));
+}